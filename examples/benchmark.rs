@@ -1,5 +1,6 @@
 use std::time::Instant;
 
+#[cfg(feature = "openblas")]
 #[link(name = "openblas")]
 extern "C" {
   fn cblas_sgemm(
@@ -14,7 +15,9 @@ extern "C" {
 }
 
 // Constants for BLAS
+#[cfg(feature = "openblas")]
 const CBLAS_ROW_MAJOR: u8 = 101;
+#[cfg(feature = "openblas")]
 const CBLAS_NO_TRANS: u8 = 111;
 
 // Naive matrix multiplication implementation
@@ -34,7 +37,15 @@ fn naive_matmul(a: &[f32], b: &[f32], m: usize, k: usize, n: usize) -> Vec<f32>
   c
 }
 
+// Built-in blocked GEMM
+fn ferrite_matmul(a: &[f32], b: &[f32], m: usize, k: usize, n: usize) -> Vec<f32> {
+  let mut c = vec![0.0; m * n];
+  ferrite::gemm::sgemm(false, false, m, n, k, 1.0, a, k, b, n, 0.0, &mut c, n);
+  c
+}
+
 // BLAS matrix multiplication wrapper
+#[cfg(feature = "openblas")]
 fn blas_matmul(a: &[f32], b: &[f32], m: usize, k: usize, n: usize) -> Vec<f32> {
   let mut c = vec![0.0; m * n];
   
@@ -76,13 +87,25 @@ fn main() {
     let _c_naive = naive_matmul(&a, &b, m, k, n);
     let naive_time = start.elapsed();
     
-    // Benchmark BLAS implementation
+    // Benchmark the built-in GEMM
     let start = Instant::now();
-    let _c_blas = blas_matmul(&a, &b, m, k, n);
-    let blas_time = start.elapsed();
+    let _c_ferrite = ferrite_matmul(&a, &b, m, k, n);
+    let ferrite_time = start.elapsed();
     
     println!("Naive implementation: {:?}", naive_time);
-    println!("BLAS implementation: {:?}", blas_time);
-    println!("Speedup: {:.2}x", naive_time.as_secs_f64() / blas_time.as_secs_f64());
+    println!("Pure-Rust GEMM: {:?}", ferrite_time);
+    println!("Speedup: {:.2}x", naive_time.as_secs_f64() / ferrite_time.as_secs_f64());
+
+    // Benchmark BLAS implementation
+    #[cfg(feature = "openblas")]
+    {
+      let start = Instant::now();
+      let _c_blas = blas_matmul(&a, &b, m, k, n);
+      let blas_time = start.elapsed();
+
+      println!("BLAS implementation: {:?}", blas_time);
+      println!("Speedup: {:.2}x", naive_time.as_secs_f64() / blas_time.as_secs_f64());
+      println!("Pure-Rust vs BLAS: {:.2}x", ferrite_time.as_secs_f64() / blas_time.as_secs_f64());
+    }
   }
 }
\ No newline at end of file
@@ -0,0 +1,249 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use crate::tensor::*;
+use super::grad::*;
+use super::mode::enable_grad;
+
+
+/// Backward node of an op's output. Every clone of the output shares it, so
+/// freeing the graph after a backward pass is seen by all of them.
+pub(crate) struct GraphNode {
+  grad_fn: RefCell<Option<Rc<dyn GradientFunction>>>,
+}
+
+impl GraphNode {
+  pub(crate) fn new(grad_fn: Rc<dyn GradientFunction>) -> Self {
+    GraphNode { grad_fn: RefCell::new(Some(grad_fn)) }
+  }
+
+  /// The node's backward function, or `None` once the graph has been freed.
+  pub(crate) fn grad_fn(&self) -> Option<Rc<dyn GradientFunction>> {
+    self.grad_fn.borrow().clone()
+  }
+
+  /// Drops the backward function along with the tensors it saved.
+  fn release(&self) {
+    self.grad_fn.borrow_mut().take();
+  }
+}
+
+
+/// The part of the graph reachable from a set of outputs.
+struct Graph {
+  // Backward functions in topological order, inputs first
+  topo: Vec<Rc<dyn GradientFunction>>,
+  // Op outputs, i.e. the non-leaf tensors
+  nodes: Vec<Tensor>,
+  // Leaves that receive a gradient
+  leaves: Vec<Tensor>,
+}
+
+impl Graph {
+  fn build(op: &'static str, outputs: &[&Tensor]) -> Result<Self, FerriteError> {
+    let mut graph = Graph { topo: Vec::new(), nodes: Vec::new(), leaves: Vec::new() };
+    let mut visited = HashSet::new();
+    for output in outputs {
+      graph.visit(op, output, &mut visited)?;
+    }
+    Ok(graph)
+  }
+
+  fn visit(&mut self, op: &'static str, tensor: &Tensor, visited: &mut HashSet<*const GraphNode>) -> Result<(), FerriteError> {
+    let Some(node) = tensor.graph_node() else {
+      if tensor.grad().is_some() {
+        self.leaves.push(tensor.clone());
+      }
+      return Ok(());
+    };
+    if !visited.insert(Rc::as_ptr(node)) {
+      return Ok(());
+    }
+
+    let grad_fn = node.grad_fn().ok_or(FerriteError::GraphReleased { op })?;
+    for parent in grad_fn.prev() {
+      self.visit(op, parent, visited)?;
+    }
+    self.topo.push(grad_fn);
+    self.nodes.push(tensor.clone());
+    Ok(())
+  }
+
+  fn run(&self) {
+    for grad_fn in self.topo.iter().rev() {
+      grad_fn.backward();
+    }
+  }
+
+  /// Like `run`, but keeps the gradients as tensors built from recorded ops so
+  /// they can be differentiated again. Returns them keyed by `grad_key`.
+  fn run_graph(&self, outputs: &[&Tensor], seeds: Vec<Tensor>) -> HashMap<*const (), Tensor> {
+    let _enable_grad = enable_grad();
+    let mut grads: HashMap<*const (), Tensor> = HashMap::new();
+    for (output, seed) in outputs.iter().zip(seeds) {
+      add_grad(&mut grads, output, seed);
+    }
+
+    for (grad_fn, node) in self.topo.iter().zip(&self.nodes).rev() {
+      let Some(out_grad) = grad_key(node).and_then(|key| grads.get(&key)).cloned() else { continue };
+      for (parent, grad) in grad_fn.prev().into_iter().zip(grad_fn.backward_graph(&out_grad)) {
+        if let Some(grad) = grad {
+          add_grad(&mut grads, parent, grad);
+        }
+      }
+    }
+    grads
+  }
+
+  fn release(&self) {
+    for node in self.nodes.iter().filter_map(|tensor| tensor.graph_node()) {
+      node.release();
+    }
+  }
+}
+
+/// Identifies a tensor across clones: its graph node for op outputs, its
+/// gradient slot for leaves.
+fn grad_key(tensor: &Tensor) -> Option<*const ()> {
+  match tensor.graph_node() {
+    Some(node) => Some(Rc::as_ptr(node) as *const ()),
+    None => tensor.grad().map(|slot| Rc::as_ptr(&slot) as *const ()),
+  }
+}
+
+fn add_grad(grads: &mut HashMap<*const (), Tensor>, tensor: &Tensor, grad: Tensor) {
+  let Some(key) = grad_key(tensor) else { return };
+  let grad = if grad.dtype() == tensor.dtype() { grad } else { grad.to_dtype(tensor.dtype()) };
+  let grad = match grads.remove(&key) {
+    Some(existing) => &existing + &grad,
+    None => grad,
+  };
+  grads.insert(key, grad);
+}
+
+
+/// Backpropagates `grad_outputs` from `outputs`, accumulating into the `.grad`
+/// of every leaf (and of non-leaves marked with `retain_grad`). Without
+/// `retain_graph` the graph is freed afterwards. With `create_graph` the
+/// gradients are recorded and the graph is always kept.
+pub(crate) fn run_backward(outputs: &[&Tensor], grad_outputs: &[Storage], retain_graph: bool, create_graph: bool) -> Result<(), FerriteError> {
+  let graph = Graph::build("backward", outputs)?;
+  if create_graph {
+    let seeds = outputs.iter().zip(grad_outputs)
+      .map(|(output, seed)| Tensor::new(seed.clone(), output.device(), false))
+      .collect();
+    let grads = graph.run_graph(outputs, seeds);
+
+    let _enable_grad = enable_grad();
+    let mut seen = HashSet::new();
+    for tensor in graph.nodes.iter().filter(|node| node.retains_grad()).chain(&graph.leaves) {
+      let Some(key) = grad_key(tensor) else { continue };
+      if let (true, Some(grad)) = (seen.insert(key), grads.get(&key)) {
+        tensor.accumulate_graph_grad(grad);
+      }
+    }
+    return Ok(());
+  }
+
+  for (output, grad_output) in outputs.iter().zip(grad_outputs) {
+    if let Some(slot) = output.grad() {
+      accumulate_grad(&slot, grad_output);
+    }
+  }
+
+  graph.run();
+
+  // Non-leaf gradients were only needed to carry the pass through the graph
+  for node in graph.nodes.iter().filter(|node| !node.retains_grad()) {
+    if let Some(slot) = node.grad() {
+      *slot.borrow_mut() = None;
+    }
+  }
+
+  if !retain_graph {
+    graph.release();
+  }
+  Ok(())
+}
+
+/// Gradients of `outputs` with respect to each of `inputs`, computed without
+/// touching any `.grad`. `grad_outputs` seeds each output and defaults to ones,
+/// which needs single-element outputs. Inputs the outputs don't depend on get
+/// `None`. Without `retain_graph` the graph is freed afterwards.
+///
+/// With `create_graph` the backward pass is itself recorded, so the returned
+/// gradients can be differentiated again (second derivatives, Hessian-vector
+/// products, gradient penalties). This also keeps the graph alive.
+///
+/// ```ignore
+/// let y = (&x * &x).sum();
+/// let dx = grad(&[&y], &[&x], None, false, false)[0].clone().unwrap();
+///
+/// // Hessian-vector product
+/// let g = grad(&[&loss], &[&w], None, true, true)[0].clone().unwrap();
+/// let hv = grad(&[&(&g * &v).sum()], &[&w], None, false, false);
+/// ```
+pub fn grad(outputs: &[&Tensor], inputs: &[&Tensor], grad_outputs: Option<&[&Tensor]>, retain_graph: bool, create_graph: bool) -> Vec<Option<Tensor>> {
+  match try_grad(outputs, inputs, grad_outputs, retain_graph, create_graph) {
+    Ok(grads) => grads,
+    Err(error) => panic!("{}", error),
+  }
+}
+
+pub fn try_grad(outputs: &[&Tensor], inputs: &[&Tensor], grad_outputs: Option<&[&Tensor]>, retain_graph: bool, create_graph: bool) -> Result<Vec<Option<Tensor>>, FerriteError> {
+  if let Some(grad_outputs) = grad_outputs {
+    if grad_outputs.len() != outputs.len() {
+      return Err(FerriteError::invalid("grad", format!("expected {} grad_outputs, got {}", outputs.len(), grad_outputs.len())));
+    }
+  }
+  let seeds = outputs.iter().enumerate()
+    .map(|(i, output)| check_backward(output, grad_outputs.map(|grads| grads[i])))
+    .collect::<Result<Vec<_>, _>>()?;
+  if inputs.iter().any(|input| input.grad().is_none()) {
+    return Err(FerriteError::GradNotEnabled { op: "grad" });
+  }
+
+  if create_graph {
+    let graph = Graph::build("grad", outputs)?;
+    // Given grad_outputs stay in the graph, so the result is differentiable in them too
+    let seeds = seeds.into_iter().enumerate()
+      .map(|(i, seed)| match grad_outputs {
+        Some(grads) => grads[i].clone(),
+        None => Tensor::new(seed, outputs[i].device(), false),
+      })
+      .collect();
+    let grads = graph.run_graph(outputs, seeds);
+    return Ok(inputs.iter().map(|input| grad_key(input).and_then(|key| grads.get(&key)).cloned()).collect());
+  }
+
+  let graph = Graph::build("grad", outputs)?;
+
+  // Stash every gradient the pass could write to and restore them afterwards
+  let mut stashed: Vec<(GradientStorage, Option<Storage>)> = Vec::new();
+  let mut seen = HashSet::new();
+  let tensors = graph.nodes.iter().chain(&graph.leaves).chain(inputs.iter().copied()).chain(outputs.iter().copied());
+  for slot in tensors.filter_map(|tensor| tensor.grad()) {
+    if seen.insert(Rc::as_ptr(&slot)) {
+      let value = slot.borrow_mut().take();
+      stashed.push((slot, value));
+    }
+  }
+
+  for (output, seed) in outputs.iter().zip(&seeds) {
+    accumulate_grad(&output.grad().unwrap(), seed);
+  }
+  graph.run();
+
+  let grads = inputs.iter()
+    .map(|input| input.grad().unwrap().borrow().clone().map(|grad| Tensor::new(grad, input.device(), false)))
+    .collect();
+
+  for (slot, value) in stashed {
+    *slot.borrow_mut() = value;
+  }
+  if !retain_graph {
+    graph.release();
+  }
+  Ok(grads)
+}
@@ -0,0 +1,357 @@
+use crate::tensor::*;
+use super::grad_fn::reduction::{expand_reduced, extreme_weights, norm_derivative, product_of_others, reduced_count};
+
+
+/// A value paired with its directional derivative for forward-mode AD. Every op
+/// computes the primal result together with the tangent pushed through it, so a
+/// single pass yields `J v` without recording a graph.
+///
+/// ```ignore
+/// let x = DualTensor::new(primal, tangent);
+/// let y = x.sigmoid().sum();
+/// let dy = y.tangent();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DualTensor {
+  primal: Storage,
+  tangent: Storage,
+}
+
+impl DualTensor {
+  /// Pairs `primal` with a tangent of the same shape; the tangent is cast to
+  /// the primal's dtype.
+  pub fn new(primal: Storage, tangent: Storage) -> Self {
+    if primal.shape() != tangent.shape() {
+      panic!("{}", FerriteError::ShapeMismatch {
+        op: "DualTensor::new",
+        expected: primal.shape().clone(),
+        found: tangent.shape().clone(),
+      });
+    }
+    let tangent = tangent.to_dtype(primal.dtype());
+    DualTensor { primal, tangent }
+  }
+
+  /// A value that doesn't vary with the input, i.e. with a zero tangent.
+  pub fn constant(primal: Storage) -> Self {
+    let tangent = Storage::zeros_like(&primal);
+    DualTensor { primal, tangent }
+  }
+
+  pub fn primal(&self) -> &Storage {
+    &self.primal
+  }
+
+  pub fn tangent(&self) -> &Storage {
+    &self.tangent
+  }
+
+  pub fn shape(&self) -> &Vec<usize> {
+    self.primal.shape()
+  }
+
+  pub fn device(&self) -> Device {
+    self.primal.device()
+  }
+
+  pub fn dtype(&self) -> DType {
+    self.primal.dtype()
+  }
+
+  /// Result of an elementwise op with derivative `local`.
+  fn chain(&self, primal: Storage, local: &Storage) -> Self {
+    let tangent = &self.tangent * local;
+    DualTensor { primal, tangent }
+  }
+
+  /// Result of a reduction whose derivative w.r.t. each input element is `local`.
+  fn reduce(&self, primal: Storage, local: &Storage, dims: &[usize], keepdim: bool) -> Self {
+    let tangent = (&self.tangent * local).sum_dims(dims, keepdim);
+    DualTensor { primal, tangent }
+  }
+}
+
+
+impl ArithmeticOps for DualTensor {
+  fn add_tensor(&self, other: &Self) -> Self {
+    DualTensor { primal: &self.primal + &other.primal, tangent: &self.tangent + &other.tangent }
+  }
+
+  fn add_tensor_assign(&mut self, other: &Self) {
+    *self = self.add_tensor(other);
+  }
+
+  fn sub_tensor(&self, other: &Self) -> Self {
+    DualTensor { primal: &self.primal - &other.primal, tangent: &self.tangent - &other.tangent }
+  }
+
+  fn sub_tensor_assign(&mut self, other: &Self) {
+    *self = self.sub_tensor(other);
+  }
+
+  fn mul_tensor(&self, other: &Self) -> Self {
+    // d(ab) = da b + a db
+    let tangent = &(&self.tangent * &other.primal) + &(&self.primal * &other.tangent);
+    DualTensor { primal: &self.primal * &other.primal, tangent }
+  }
+
+  fn mul_tensor_assign(&mut self, other: &Self) {
+    *self = self.mul_tensor(other);
+  }
+
+  fn div_tensor(&self, other: &Self) -> Self {
+    // d(a / b) = (da - (a / b) db) / b
+    let primal = &self.primal / &other.primal;
+    let tangent = &(&self.tangent - &(&primal * &other.tangent)) / &other.primal;
+    DualTensor { primal, tangent }
+  }
+
+  fn div_tensor_assign(&mut self, other: &Self) {
+    *self = self.div_tensor(other);
+  }
+
+  fn add_f32(&self, other: f32) -> Self {
+    DualTensor { primal: self.primal.add_f32(other), tangent: self.tangent.clone() }
+  }
+
+  fn add_f32_assign(&mut self, other: f32) {
+    self.primal.add_f32_assign(other);
+  }
+
+  fn sub_f32(&self, other: f32) -> Self {
+    DualTensor { primal: self.primal.sub_f32(other), tangent: self.tangent.clone() }
+  }
+
+  fn sub_f32_assign(&mut self, other: f32) {
+    self.primal.sub_f32_assign(other);
+  }
+
+  fn mul_f32(&self, other: f32) -> Self {
+    DualTensor { primal: self.primal.mul_f32(other), tangent: self.tangent.mul_f32(other) }
+  }
+
+  fn mul_f32_assign(&mut self, other: f32) {
+    self.primal.mul_f32_assign(other);
+    self.tangent.mul_f32_assign(other);
+  }
+
+  fn div_f32(&self, other: f32) -> Self {
+    DualTensor { primal: self.primal.div_f32(other), tangent: self.tangent.div_f32(other) }
+  }
+
+  fn div_f32_assign(&mut self, other: f32) {
+    self.primal.div_f32_assign(other);
+    self.tangent.div_f32_assign(other);
+  }
+
+  fn pow_f32(&self, other: f32) -> Self {
+    let local = self.primal.pow_f32(other - 1.).mul_f32(other);
+    self.chain(self.primal.pow_f32(other), &local)
+  }
+
+  fn pow_f32_assign(&mut self, other: f32) {
+    *self = self.pow_f32(other);
+  }
+
+  fn greater_than(&self, other: &Self, make_binary: bool) -> Self {
+    Self::constant(self.primal.greater_than(&other.primal, make_binary))
+  }
+
+  fn greater_than_f32(&self, other: f32, make_binary: bool) -> Self {
+    Self::constant(self.primal.greater_than_f32(other, make_binary))
+  }
+
+  fn less_than(&self, other: &Self, make_binary: bool) -> Self {
+    Self::constant(self.primal.less_than(&other.primal, make_binary))
+  }
+
+  fn less_than_f32(&self, other: f32, make_binary: bool) -> Self {
+    Self::constant(self.primal.less_than_f32(other, make_binary))
+  }
+
+  fn sign(&self) -> Self {
+    Self::constant(self.primal.sign())
+  }
+
+  fn abs(&self) -> Self {
+    self.chain(self.primal.abs(), &self.primal.sign())
+  }
+
+  fn abs_assign(&mut self) {
+    *self = self.abs();
+  }
+}
+
+
+impl ActivationOps for DualTensor {
+  fn binary_step(&self) -> Self {
+    Self::constant(self.primal.binary_step())
+  }
+
+  fn sigmoid(&self) -> Self {
+    let s = self.primal.sigmoid();
+    let local = &s * &s.mul_f32(-1.).add_f32(1.);
+    self.chain(s, &local)
+  }
+
+  fn tanh(&self) -> Self {
+    let t = self.primal.tanh();
+    let local = (&t * &t).mul_f32(-1.).add_f32(1.);
+    self.chain(t, &local)
+  }
+
+  fn relu(&self) -> Self {
+    let local = self.primal.apply(|x| if x <= 0. {0.} else {1.});
+    self.chain(self.primal.relu(), &local)
+  }
+
+  fn leaky_relu(&self) -> Self {
+    let local = self.primal.apply(|x| if x <= 0. {0.1} else {1.});
+    self.chain(self.primal.leaky_relu(), &local)
+  }
+
+  fn parametric_relu(&self, a: f32) -> Self {
+    let local = self.primal.apply(|x| if x <= 0. {a} else {1.});
+    self.chain(self.primal.parametric_relu(a), &local)
+  }
+
+  fn elu(&self, alpha: f32) -> Self {
+    // 1 on the positive side, alpha * exp(x) = elu(x) + alpha elsewhere
+    let primal = self.primal.elu(alpha);
+    let positive = self.primal.apply(|x| if x <= 0. {0.} else {1.});
+    let negative = self.primal.apply(|x| if x <= 0. {1.} else {0.});
+    let local = &positive + &(&primal.add_f32(alpha) * &negative);
+    self.chain(primal, &local)
+  }
+
+  fn softmax(&self, dim: usize) -> Self {
+    // ds = s * (dx - sum(s * dx))
+    let s = self.primal.softmax(dim);
+    let inner = (&s * &self.tangent).sum_dims(&[dim], true);
+    let tangent = &s * &(&self.tangent - &inner);
+    DualTensor { primal: s, tangent }
+  }
+
+  fn swish(&self) -> Self {
+    let s = self.primal.sigmoid();
+    let local = &s + &(&(&self.primal * &s) * &s.mul_f32(-1.).add_f32(1.));
+    self.chain(self.primal.swish(), &local)
+  }
+}
+
+
+impl ReductionOps for DualTensor {
+  fn sum(&self) -> Self {
+    DualTensor { primal: self.primal.sum(), tangent: self.tangent.sum() }
+  }
+
+  fn sum_axis(&self, axis: usize) -> Self {
+    DualTensor { primal: self.primal.sum_axis(axis), tangent: self.tangent.sum_axis(axis) }
+  }
+
+  fn product(&self) -> Self {
+    let others = product_of_others(&self.primal, &[]);
+    DualTensor { primal: self.primal.product(), tangent: (&self.tangent * &others).sum() }
+  }
+
+  fn mean(&self) -> Self {
+    DualTensor { primal: self.primal.mean(), tangent: self.tangent.mean() }
+  }
+
+  fn sum_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    DualTensor { primal: self.primal.sum_dims(dims, keepdim), tangent: self.tangent.sum_dims(dims, keepdim) }
+  }
+
+  fn mean_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    DualTensor { primal: self.primal.mean_dims(dims, keepdim), tangent: self.tangent.mean_dims(dims, keepdim) }
+  }
+
+  fn prod_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    let others = product_of_others(&self.primal, dims);
+    self.reduce(self.primal.prod_dims(dims, keepdim), &others, dims, keepdim)
+  }
+
+  fn max(&self, dims: &[usize], keepdim: bool) -> Self {
+    let primal = self.primal.max(dims, keepdim);
+    let weights = extreme_weights(&self.primal, &primal, dims);
+    self.reduce(primal, &weights, dims, keepdim)
+  }
+
+  fn min(&self, dims: &[usize], keepdim: bool) -> Self {
+    let primal = self.primal.min(dims, keepdim);
+    let weights = extreme_weights(&self.primal, &primal, dims);
+    self.reduce(primal, &weights, dims, keepdim)
+  }
+
+  fn argmax(&self, dims: &[usize], keepdim: bool) -> Self {
+    Self::constant(self.primal.argmax(dims, keepdim))
+  }
+
+  fn argmin(&self, dims: &[usize], keepdim: bool) -> Self {
+    Self::constant(self.primal.argmin(dims, keepdim))
+  }
+
+  fn var(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self {
+    // d var = 2 sum((x - mean) dx) / (n - correction)
+    let correction = if unbiased { 1.0 } else { 0.0 };
+    let denominator = reduced_count(self.shape(), dims) - correction;
+    let mean = expand_reduced(&self.primal.mean_dims(dims, true), self.shape(), dims);
+    let local = self.primal.sub_tensor(&mean).mul_f32(2.0 / denominator);
+    self.reduce(self.primal.var(dims, unbiased, keepdim), &local, dims, keepdim)
+  }
+
+  fn std(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self {
+    // d std = d var / (2 std)
+    let variance = self.var(dims, unbiased, keepdim);
+    let primal = self.primal.std(dims, unbiased, keepdim);
+    let tangent = variance.tangent.div_tensor(&primal.mul_f32(2.0));
+    DualTensor { primal, tangent }
+  }
+
+  fn norm(&self, p: f32, dims: &[usize], keepdim: bool) -> Self {
+    let primal = self.primal.norm(p, dims, keepdim);
+    let local = norm_derivative(&self.primal, &primal, p, dims);
+    self.reduce(primal, &local, dims, keepdim)
+  }
+
+  fn logsumexp(&self, dims: &[usize], keepdim: bool) -> Self {
+    // The derivative is the softmax over the reduced lane
+    let primal = self.primal.logsumexp(dims, keepdim);
+    let lse = expand_reduced(&primal, self.shape(), dims);
+    let softmax = self.primal.elementwise_op(&lse, |x, l| (x - l).exp());
+    self.reduce(primal, &softmax, dims, keepdim)
+  }
+
+  fn all(&self, dims: &[usize], keepdim: bool) -> Self {
+    Self::constant(self.primal.all(dims, keepdim))
+  }
+
+  fn any(&self, dims: &[usize], keepdim: bool) -> Self {
+    Self::constant(self.primal.any(dims, keepdim))
+  }
+}
+
+
+impl BlasOps for DualTensor {
+  fn matmul(&self, other: &Self, trans_a: bool, trans_b: bool) -> Self {
+    // d(AB) = dA B + A dB
+    let primal = self.primal.matmul(&other.primal, trans_a, trans_b);
+    let tangent = &self.tangent.matmul(&other.primal, trans_a, trans_b)
+      + &self.primal.matmul(&other.tangent, trans_a, trans_b);
+    DualTensor { primal, tangent }
+  }
+}
+
+
+/// Evaluates `f` at `input` in forward mode and returns the output along with
+/// the Jacobian-vector product `J tangent`, in one pass and without a graph.
+///
+/// ```ignore
+/// let w = DualTensor::constant(weights.tensor().clone());
+/// let (y, jv) = jvp(|x| x.matmul(&w, false, false).tanh(), &x, &v);
+/// ```
+pub fn jvp<F: Fn(&DualTensor) -> DualTensor>(f: F, input: &Tensor, tangent: &Tensor) -> (Tensor, Tensor) {
+  let output = f(&DualTensor::new(input.tensor().clone(), tangent.tensor().clone()));
+  let device = input.device();
+  (Tensor::new(output.primal, device, false), Tensor::new(output.tangent, device, false))
+}
@@ -0,0 +1,119 @@
+use crate::tensor::*;
+use super::engine::grad;
+use super::mode::enable_grad;
+
+
+/// A fresh leaf holding the data of `input`, so the closure's graph starts here.
+fn prepare(input: &Tensor) -> Tensor {
+  let mut input = input.detach();
+  input.requires_grad_(true);
+  input
+}
+
+fn one_hot(index: usize, like: &Tensor) -> Tensor {
+  let len: usize = like.shape().iter().product();
+  let mut data = vec![0.0f32; len];
+  data[index] = 1.0;
+  Tensor::from_vec(data, like.shape().clone(), like.device(), None)
+}
+
+/// Stacks the gradient of every element of `output` w.r.t. `input` into a
+/// tensor of shape `output.shape ++ input.shape`.
+fn jacobian_of(output: &Tensor, input: &Tensor) -> Tensor {
+  let len: usize = output.shape().iter().product();
+  let rows: Vec<Tensor> = (0..len).map(|index| {
+    let row = if *output.requires_grad() {
+      grad(&[output], &[input], Some(&[&one_hot(index, output)]), true, false)[0].clone()
+    } else {
+      None
+    };
+    row.unwrap_or_else(|| Tensor::zeros_like(input, None))
+  }).collect();
+
+  let mut shape = output.shape().clone();
+  shape.extend(input.shape());
+  Tensor::stack(&rows.iter().collect::<Vec<_>>(), 0).view(shape)
+}
+
+
+/// Evaluates `f` at `input` and returns the output along with the
+/// vector-Jacobian product `v^T J`, where `v` has the output's shape.
+///
+/// ```ignore
+/// let (y, vjp) = vjp(|x| x.tanh(), &x, &v);
+/// ```
+pub fn vjp<F: Fn(&Tensor) -> Tensor>(f: F, input: &Tensor, v: &Tensor) -> (Tensor, Tensor) {
+  let _enable_grad = enable_grad();
+  let input = prepare(input);
+  let output = f(&input);
+  let vjp = if *output.requires_grad() {
+    grad(&[&output], &[&input], Some(&[v]), false, false)[0].clone()
+  } else {
+    None
+  };
+  (output.detach(), vjp.unwrap_or_else(|| Tensor::zeros_like(&input, None)))
+}
+
+/// Evaluates `f` at `input` and returns the output along with the
+/// Jacobian-vector product `J v`, where `v` has the input's shape. Computed as
+/// the vjp of a vjp, which is linear in its seed.
+pub fn jvp<F: Fn(&Tensor) -> Tensor>(f: F, input: &Tensor, v: &Tensor) -> (Tensor, Tensor) {
+  let _enable_grad = enable_grad();
+  let input = prepare(input);
+  let output = f(&input);
+  let seed = Tensor::zeros_like(&output, Some(true));
+
+  let vjp = if *output.requires_grad() {
+    grad(&[&output], &[&input], Some(&[&seed]), false, true)[0].clone()
+  } else {
+    None
+  };
+  let jvp = match vjp {
+    Some(vjp) if *vjp.requires_grad() => grad(&[&vjp], &[&seed], Some(&[v]), false, false)[0].clone(),
+    _ => None,
+  };
+  (output.detach(), jvp.unwrap_or_else(|| Tensor::zeros_like(&output, None)))
+}
+
+/// The Jacobian of `f` at `input`, of shape `output.shape ++ input.shape`.
+pub fn jacobian<F: Fn(&Tensor) -> Tensor>(f: F, input: &Tensor) -> Tensor {
+  let _enable_grad = enable_grad();
+  let input = prepare(input);
+  let output = f(&input);
+  jacobian_of(&output, &input)
+}
+
+/// The Hessian of a single-element `f` at `input`, of shape
+/// `input.shape ++ input.shape`.
+pub fn hessian<F: Fn(&Tensor) -> Tensor>(f: F, input: &Tensor) -> Tensor {
+  let _enable_grad = enable_grad();
+  let input = prepare(input);
+  let output = f(&input);
+  if output.shape().iter().product::<usize>() != 1 {
+    panic!("{}", FerriteError::invalid("hessian", format!("expected a single-element output, got shape {:?}", output.shape())));
+  }
+
+  let gradient = if *output.requires_grad() {
+    grad(&[&output], &[&input], None, false, true)[0].clone()
+  } else {
+    None
+  };
+  let gradient = gradient.unwrap_or_else(|| Tensor::zeros_like(&input, None));
+  jacobian_of(&gradient, &input)
+}
+
+/// Runs `f` on every slice of `input` along `dim` and stacks the results along
+/// a new leading dimension. Slices stay connected to `input`, so gradients flow
+/// back through the map.
+///
+/// ```ignore
+/// // Per-example gradients
+/// let grads = vmap(|x| {
+///   let loss = criterion.loss(&x.matmul(&w, false, false), &target);
+///   grad(&[&loss], &[&w], None, false, false)[0].clone().unwrap()
+/// }, &batch, 0);
+/// ```
+pub fn vmap<F: Fn(&Tensor) -> Tensor>(f: F, input: &Tensor, dim: usize) -> Tensor {
+  let outputs: Vec<Tensor> = input.unbind(dim).iter().map(&f).collect();
+  Tensor::stack(&outputs.iter().collect::<Vec<_>>(), 0)
+}
@@ -4,18 +4,61 @@ use crate::tensor::*;
 macro_rules! reduce_grad {
   ($grad:expr, $shape:expr) => {{
     let mut reduced_grad = $grad.clone();
-    for (dim, (grad_size, shape_size)) in $grad.shape().iter().zip($shape.iter()).enumerate() {
-      if shape_size == &1 && grad_size != &1 {
-        let mut sum_dims = vec![false; $grad.shape().len()];
-        sum_dims[dim] = true;
-        reduced_grad = reduced_grad.sum_dim(&sum_dims);
+    // Sum away leading dimensions that broadcasting prepended
+    while reduced_grad.shape().len() > $shape.len() {
+      reduced_grad = reduced_grad.sum_axis(0);
+    }
+    // Sum over dimensions that were stretched from size 1, keeping them as 1
+    for dim in 0..$shape.len() {
+      if $shape[dim] == 1 && reduced_grad.shape()[dim] != 1 {
+        if reduced_grad.shape().len() == 1 {
+          reduced_grad = reduced_grad.sum();
+        } else {
+          reduced_grad = reduced_grad.sum_axis(dim);
+          reduced_grad.unsqueeze(dim);
+        }
       }
     }
     reduced_grad
   }};
 }
 
+/// Adds `grad` into a gradient slot, materializing the slot if it was cleared
+/// with `zero_grad(true)`.
+pub fn accumulate_grad(slot: &GradientStorage, grad: &Storage) {
+  let mut slot = slot.borrow_mut();
+  match slot.as_mut() {
+    Some(existing) => existing.add_tensor_assign(grad),
+    None => *slot = Some(grad.clone()),
+  }
+}
+
+/// Tensor counterpart of `reduce_grad!`: sums `grad` down to `shape`, undoing
+/// broadcasting with recorded ops so the result stays differentiable.
+pub(crate) fn sum_to(grad: &Tensor, shape: &[usize]) -> Tensor {
+  if grad.shape().as_slice() == shape {
+    return grad.clone();
+  }
+  let lead = grad.shape().len() - shape.len();
+  let dims: Vec<usize> = (0..grad.shape().len())
+    .filter(|&dim| dim < lead || (shape[dim - lead] == 1 && grad.shape()[dim] != 1))
+    .collect();
+  let reduced = if dims.is_empty() { grad.clone() } else { grad.sum_dims(&dims, true) };
+  reduced.view(shape.to_vec())
+}
+
+/// Wraps a storage computed by the backward pass as a tensor outside the graph.
+pub(crate) fn constant(storage: Storage, device: Device) -> Tensor {
+  Tensor::new(storage, device, false)
+}
+
 pub trait GradientFunction: std::fmt::Debug {
   fn backward(&self);
   fn prev(&self) -> Vec<&Tensor>;
+
+  /// Differentiable version of `backward`, used with `create_graph`: given the
+  /// output's gradient, returns the gradient of every input in `prev()` order,
+  /// built from recorded tensor ops so it can be differentiated again. Inputs
+  /// that don't require grad get `None`.
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>>;
 }
\ No newline at end of file
@@ -21,13 +21,18 @@ impl GradientFunction for BinaryStepGrad {
   fn backward(&self) {
     if let Some(lhs_grad) = &self.lhs.grad() {
       let zeros = Storage::zeros(self.lhs.tensor().shape().to_vec(), Some(self.lhs.device()), None);    
-      lhs_grad.borrow_mut().add_tensor_assign(&zeros);
+      accumulate_grad(lhs_grad, &zeros);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| Tensor::zeros(self.lhs.shape().clone(), self.lhs.device(), None));
+    vec![lhs]
+  }
 }
 
 
@@ -52,21 +57,30 @@ impl GradientFunction for SigmoidGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
       let sigmoid_op = |x: f32| 1./(1. + f32::exp(-x));
-      let grad_for_lhs = &*out_grad * &self.lhs.storage.apply(|x| sigmoid_op(x) * (1. - sigmoid_op(x)));
+      let grad_for_lhs = out_grad * &self.lhs.storage.apply(|x| sigmoid_op(x) * (1. - sigmoid_op(x)));
 
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
     
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| {
+      let s = self.lhs.sigmoid();
+      &(out_grad * &s) * &s.mul_f32(-1.).add_f32(1.)
+    });
+    vec![lhs]
+  }
 }
 
 
@@ -91,21 +105,29 @@ impl GradientFunction for TanhGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let tanh_op = |x: f32| (f32::exp(x) - f32::exp(-x))/(f32::exp(x) + f32::exp(-x));
-      let grad_for_lhs = &*out_grad * &self.lhs.storage.apply(|x| 1. - tanh_op(x));
+      let grad_for_lhs = out_grad * &self.lhs.storage.apply(|x| 1. - x.tanh().powi(2));
 
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
     
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| {
+      let t = self.lhs.tanh();
+      out_grad * &(&t * &t).mul_f32(-1.).add_f32(1.)
+    });
+    vec![lhs]
+  }
 }
 
 
@@ -129,19 +151,25 @@ impl GradientFunction for ReluGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad * &self.lhs.storage.apply(|x| if x <= 0. {0.} else {1.});
+      let grad_for_lhs = out_grad * &self.lhs.storage.apply(|x| if x <= 0. {0.} else {1.});
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
     
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| out_grad * &constant(self.lhs.storage.apply(|x| if x <= 0. {0.} else {1.}), self.lhs.device()));
+    vec![lhs]
+  }
 }
 
 
@@ -166,19 +194,25 @@ impl GradientFunction for LeakyReluGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad * &self.lhs.storage.apply(|x| if x <= 0. {0.1} else {1.});
+      let grad_for_lhs = out_grad * &self.lhs.storage.apply(|x| if x <= 0. {0.1} else {1.});
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
     
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| out_grad * &constant(self.lhs.storage.apply(|x| if x <= 0. {0.1} else {1.}), self.lhs.device()));
+    vec![lhs]
+  }
 }
 
 
@@ -204,19 +238,25 @@ impl GradientFunction for ParametricReluGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad * &self.lhs.storage.apply(|x| if x <= 0. {self.a} else {1.});
+      let grad_for_lhs = out_grad * &self.lhs.storage.apply(|x| if x <= 0. {self.a} else {1.});
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
     
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| out_grad * &constant(self.lhs.storage.apply(|x| if x <= 0. {self.a} else {1.}), self.lhs.device()));
+    vec![lhs]
+  }
 }
 
 
@@ -244,19 +284,31 @@ impl GradientFunction for EluGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad * &self.lhs.storage.apply(|x| if x <= 0. {self.alpha * f32::exp(x)} else {1.});
+      let grad_for_lhs = out_grad * &self.lhs.storage.apply(|x| if x <= 0. {self.alpha * f32::exp(x)} else {1.});
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
     
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| {
+      // alpha * exp(x) = elu(x) + alpha on the negative side, 1 elsewhere
+      let positive = constant(self.lhs.storage.apply(|x| if x <= 0. {0.} else {1.}), self.lhs.device());
+      let negative = constant(self.lhs.storage.apply(|x| if x <= 0. {1.} else {0.}), self.lhs.device());
+      let local = &positive + &(&self.lhs.elu(self.alpha).add_f32(self.alpha) * &negative);
+      out_grad * &local
+    });
+    vec![lhs]
+  }
 }
 
 
@@ -265,13 +317,15 @@ impl GradientFunction for EluGrad {
 #[derive(Debug)]
 pub struct SoftmaxGrad {
   lhs: Tensor,
+  dim: usize,
   output: Tensor,
 }
 
 impl SoftmaxGrad {
-  pub fn new(lhs: &Tensor, output: &Tensor) -> Self {
+  pub fn new(lhs: &Tensor, dim: usize, output: &Tensor) -> Self {
     SoftmaxGrad {
       lhs: lhs.clone(),
+      dim,
       output: output.clone(),
     }
   }
@@ -282,6 +336,7 @@ impl GradientFunction for SoftmaxGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
@@ -289,31 +344,35 @@ impl GradientFunction for SoftmaxGrad {
       let s = self.output.tensor();
 
       // Compute the elementwise product: (dL/ds * s)
-      let grad_times_s = &*out_grad * s;
+      let grad_times_s = out_grad * s;
 
-      // Determine the axis over which softmax was computed.
-      // For example, if softmax is computed over the last dimension:
-      let axis = s.shape().len() - 1;
-      
       // Sum the product along the softmax axis. This gives, for each sample,
       // the inner product \(\sum_j s_j * (dL/ds)_j\).
-      let sum_along_axis = grad_times_s.sum_axis(axis);
+      let sum_along_axis = grad_times_s.sum_dims(&[self.dim], true);
       
       // Efficient gradient for softmax:
       // dL/dx = s * (dL/ds - sum(s * dL/ds))
-      let grad_for_lhs = s * &(&*out_grad - &sum_along_axis);
+      let grad_for_lhs = s * &(out_grad - &sum_along_axis);
 
       // If necessary, reduce the gradient to match the shape of the lhs Tensor.
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
       
       // Add the computed gradient to the lhs gradient.
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| {
+      let s = self.lhs.softmax(self.dim);
+      &s * &(out_grad - &(out_grad * &s).sum_dims(&[self.dim], true))
+    });
+    vec![lhs]
+  }
 }
 
 
@@ -335,11 +394,29 @@ impl SwishGrad {
 
 impl GradientFunction for SwishGrad {
   fn backward(&self) {
-    // Get output gradient
-    unimplemented!()
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    // d/dx x * sigmoid(x) = sigmoid(x) + x * sigmoid(x) * (1 - sigmoid(x))
+    if let Some(lhs_grad) = &self.lhs.grad() {
+      let sigmoid_op = |x: f32| 1./(1. + f32::exp(-x));
+      let grad_for_lhs = out_grad * &self.lhs.storage.apply(|x| sigmoid_op(x) * (1. + x * (1. - sigmoid_op(x))));
+
+      accumulate_grad(lhs_grad, &grad_for_lhs);
+    }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| {
+      let s = self.lhs.sigmoid();
+      let local = &s + &(&(&self.lhs * &s) * &s.mul_f32(-1.).add_f32(1.));
+      out_grad * &local
+    });
+    vec![lhs]
+  }
 }
\ No newline at end of file
@@ -24,25 +24,32 @@ impl GradientFunction for AddGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
       let reduced_grad = reduce_grad!(out_grad, self.lhs.tensor().shape());
     
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
     
     // Propagate to rhs
     if let Some(rhs_grad) = &self.rhs.grad() {
       let reduced_grad = reduce_grad!(out_grad, self.rhs.tensor().shape());
       
-      rhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(rhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs, &self.rhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(out_grad, self.lhs.shape()));
+    let rhs = self.rhs.grad().map(|_| sum_to(out_grad, self.rhs.shape()));
+    vec![lhs, rhs]
+  }
 }
 
 
@@ -68,26 +75,33 @@ impl GradientFunction for SubGrad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
       let reduced_grad = reduce_grad!(out_grad, self.lhs.tensor().shape());
       
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
     
     // Propagate to rhs
     if let Some(rhs_grad) = &self.rhs.grad() {
-      let grad_for_rhs = &*out_grad * -1.;
+      let grad_for_rhs = out_grad * -1.;
       let reduced_grad = reduce_grad!(grad_for_rhs, self.rhs.tensor().shape());
       
-      rhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(rhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs, &self.rhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(out_grad, self.lhs.shape()));
+    let rhs = self.rhs.grad().map(|_| sum_to(&out_grad.mul_f32(-1.), self.rhs.shape()));
+    vec![lhs, rhs]
+  }
 }
 
 #[derive(Debug)]
@@ -111,29 +125,36 @@ impl GradientFunction for MulGrad {
   fn backward(&self) {
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad * self.rhs.tensor();
+      let grad_for_lhs = out_grad * self.rhs.tensor();
       
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
       
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
     
     // Propagate to rhs
     if let Some(rhs_grad) = &self.rhs.grad() {
-      let grad_for_rhs = &*out_grad * self.lhs.tensor();
+      let grad_for_rhs = out_grad * self.lhs.tensor();
       
       let reduced_grad = reduce_grad!(grad_for_rhs, self.rhs.tensor().shape());
 
-      rhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(rhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs, &self.rhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(&(out_grad * &self.rhs), self.lhs.shape()));
+    let rhs = self.rhs.grad().map(|_| sum_to(&(out_grad * &self.lhs), self.rhs.shape()));
+    vec![lhs, rhs]
+  }
 }
 
 
@@ -158,30 +179,40 @@ impl GradientFunction for DivGrad {
   fn backward(&self) {
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad / self.rhs.tensor();
+      let grad_for_lhs = out_grad / self.rhs.tensor();
       
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
       
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
     
     // Propagate to rhs
     if let Some(rhs_grad) = &self.rhs.grad() {
       // Form grad for rhs
-      let grad_for_rhs = &(&*out_grad * self.lhs.tensor()).mul_f32(-1.) / &(self.rhs.tensor().pow_f32(2.));
+      let grad_for_rhs = &(out_grad * self.lhs.tensor()).mul_f32(-1.) / &(self.rhs.tensor().pow_f32(2.));
       
       let reduced_grad = reduce_grad!(grad_for_rhs, self.rhs.tensor().shape());
 
-      rhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(rhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs, &self.rhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(&(out_grad / &self.rhs), self.lhs.shape()));
+    let rhs = self.rhs.grad().map(|_| {
+      let grad = &(out_grad * &self.lhs).mul_f32(-1.) / &(&self.rhs * &self.rhs);
+      sum_to(&grad, self.rhs.shape())
+    });
+    vec![lhs, rhs]
+  }
 }
 
 #[derive(Debug)]
@@ -205,14 +236,15 @@ impl GradientFunction for PowF32Grad {
   fn backward(&self) {
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &(&*out_grad * self.rhs) * &self.lhs.tensor().pow_f32(self.rhs-1.);
+      let grad_for_lhs = &(out_grad * self.rhs) * &self.lhs.tensor().pow_f32(self.rhs-1.);
       
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
       
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
 
     }
   }
@@ -220,6 +252,11 @@ impl GradientFunction for PowF32Grad {
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(&(&(out_grad * self.rhs) * &self.lhs.pow_f32(self.rhs - 1.)), self.lhs.shape()));
+    vec![lhs]
+  }
 }
 
 
@@ -245,18 +282,24 @@ impl GradientFunction for AddF32Grad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
       let reduced_grad = reduce_grad!(out_grad, self.lhs.tensor().shape());
     
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(out_grad, self.lhs.shape()));
+    vec![lhs]
+  }
 }
 
 
@@ -282,18 +325,24 @@ impl GradientFunction for SubF32Grad {
     // Get output gradient
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
       let reduced_grad = reduce_grad!(out_grad, self.lhs.tensor().shape());
       
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(out_grad, self.lhs.shape()));
+    vec![lhs]
+  }
 }
 
 #[derive(Debug)]
@@ -317,14 +366,15 @@ impl GradientFunction for MulF32Grad {
   fn backward(&self) {
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad * self.rhs;
+      let grad_for_lhs = out_grad * self.rhs;
       
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
       
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   
   }
@@ -332,6 +382,11 @@ impl GradientFunction for MulF32Grad {
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(&(out_grad * self.rhs), self.lhs.shape()));
+    vec![lhs]
+  }
 }
 
 
@@ -356,20 +411,26 @@ impl GradientFunction for DivF32Grad {
   fn backward(&self) {
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad / self.rhs;
+      let grad_for_lhs = out_grad / self.rhs;
       
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
       
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(&(out_grad / self.rhs), self.lhs.shape()));
+    vec![lhs]
+  }
 }
 
 
@@ -392,18 +453,24 @@ impl GradientFunction for AbsGrad {
   fn backward(&self) {
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
     // Propagate to lhs
     if let Some(lhs_grad) = &self.lhs.grad() {
-      let grad_for_lhs = &*out_grad * &self.lhs.tensor().sign();
+      let grad_for_lhs = out_grad * &self.lhs.tensor().sign();
       
       let reduced_grad = reduce_grad!(grad_for_lhs, self.lhs.tensor().shape());
       
-      lhs_grad.borrow_mut().add_tensor_assign(&reduced_grad);
+      accumulate_grad(lhs_grad, &reduced_grad);
     }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let lhs = self.lhs.grad().map(|_| sum_to(&(out_grad * &constant(self.lhs.tensor().sign(), self.lhs.device())), self.lhs.shape()));
+    vec![lhs]
+  }
 }
@@ -1,4 +1,4 @@
-use crate::tensor::*;
+use crate::{reduce_grad, tensor::*};
 use super::super::grad::*;
 
 
@@ -23,38 +23,111 @@ impl MatMulGrad {
   }
 }
 
+/// Promotes a 1-D matmul operand to the matrix the kernel treated it as.
+fn as_matrix(storage: &Storage, is_lhs: bool) -> Storage {
+  match storage.shape().len() {
+    1 if is_lhs => storage.contiguous().view(vec![1, storage.shape()[0]]),
+    1 => storage.contiguous().view(vec![storage.shape()[0], 1]),
+    _ => storage.clone(),
+  }
+}
+
+/// Sums a gradient over broadcast batch dimensions and restores the operand's shape.
+fn reduce_to(grad: Storage, matrix: &Storage, original: &Storage) -> Storage {
+  let reduced = reduce_grad!(grad, matrix.shape());
+  reduced.contiguous().view(original.shape().clone())
+}
+
+/// Tensor counterpart of `as_matrix`.
+fn as_matrix_graph(tensor: &Tensor, is_lhs: bool) -> Tensor {
+  match tensor.shape().len() {
+    1 if is_lhs => tensor.view(vec![1, tensor.shape()[0]]),
+    1 => tensor.view(vec![tensor.shape()[0], 1]),
+    _ => tensor.clone(),
+  }
+}
+
 impl GradientFunction for MatMulGrad {
   fn backward(&self) {
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
-    // Case: C = t × w^T  (your test case)
-    // t: (2,3), w: (2,3), C: (2,2)
-    // dL/dt = dL/dC × w^T  - note: no transpose here since we already have w^T
-    // dL/dw = (dL/dC × t)^T
+    // Work on the promoted operands: C = op_a(A) × op_b(B), with the vector
+    // dimensions restored on the output gradient
+    let lhs = as_matrix(self.lhs.tensor(), true);
+    let rhs = as_matrix(self.rhs.tensor(), false);
+    let trans_a = self.trans_a && self.lhs.shape().len() > 1;
+    let trans_b = self.trans_b && self.rhs.shape().len() > 1;
+
+    let lhs_rank = lhs.shape().len();
+    let rhs_rank = rhs.shape().len();
+    let m = if trans_a { lhs.shape()[lhs_rank - 1] } else { lhs.shape()[lhs_rank - 2] };
+    let n = if trans_b { rhs.shape()[rhs_rank - 2] } else { rhs.shape()[rhs_rank - 1] };
+    let batch_rank = lhs_rank.max(rhs_rank) - 2;
+    let mut full_shape: Vec<usize> = out_grad.shape()[..batch_rank].to_vec();
+    full_shape.extend([m, n]);
+    let out_grad = out_grad.contiguous().view(full_shape);
 
     if let Some(lhs_grad) = &self.lhs.grad() {
-      // For input t: dL/dt = dL/dC × w^T
-      let grad_for_lhs = if !self.trans_b {
-        out_grad.matmul(self.rhs.tensor(), false, true)
+      // d op_a(A) = G × op_b(B)^T, transposed back when A was used transposed
+      let grad_for_lhs = if !trans_a {
+        out_grad.matmul(&rhs, false, !trans_b)
       } else {
-        out_grad.matmul(self.rhs.tensor(), false, false)
+        rhs.matmul(&out_grad, trans_b, true)
       };
-      lhs_grad.borrow_mut().add_tensor_assign(&grad_for_lhs);
+      accumulate_grad(lhs_grad, &reduce_to(grad_for_lhs, &lhs, self.lhs.tensor()));
     }
 
     if let Some(rhs_grad) = &self.rhs.grad() {
-      // For weight w: dL/dw = (dL/dC × t)^T
-      let grad_for_rhs = if !self.trans_b {
-        self.lhs.tensor().matmul(&out_grad, true, false)
+      // d op_b(B) = op_a(A)^T × G, transposed back when B was used transposed
+      let grad_for_rhs = if !trans_b {
+        lhs.matmul(&out_grad, !trans_a, false)
       } else {
-        out_grad.matmul(&self.lhs.tensor(), true, false)
+        out_grad.matmul(&lhs, true, trans_a)
       };
-      rhs_grad.borrow_mut().add_tensor_assign(&grad_for_rhs);
+      accumulate_grad(rhs_grad, &reduce_to(grad_for_rhs, &rhs, self.rhs.tensor()));
     }
   } 
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.lhs, &self.rhs]
   }
-}
\ No newline at end of file
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    // Mirrors `backward` with recorded ops
+    let lhs = as_matrix_graph(&self.lhs, true);
+    let rhs = as_matrix_graph(&self.rhs, false);
+    let trans_a = self.trans_a && self.lhs.shape().len() > 1;
+    let trans_b = self.trans_b && self.rhs.shape().len() > 1;
+
+    let lhs_rank = lhs.shape().len();
+    let rhs_rank = rhs.shape().len();
+    let m = if trans_a { lhs.shape()[lhs_rank - 1] } else { lhs.shape()[lhs_rank - 2] };
+    let n = if trans_b { rhs.shape()[rhs_rank - 2] } else { rhs.shape()[rhs_rank - 1] };
+    let batch_rank = lhs_rank.max(rhs_rank) - 2;
+    let mut full_shape: Vec<usize> = out_grad.shape()[..batch_rank].to_vec();
+    full_shape.extend([m, n]);
+    let out_grad = out_grad.view(full_shape);
+
+    let grad_for_lhs = self.lhs.grad().map(|_| {
+      let grad = if !trans_a {
+        out_grad.matmul(&rhs, false, !trans_b)
+      } else {
+        rhs.matmul(&out_grad, trans_b, true)
+      };
+      sum_to(&grad, lhs.shape()).view(self.lhs.shape().clone())
+    });
+
+    let grad_for_rhs = self.rhs.grad().map(|_| {
+      let grad = if !trans_b {
+        lhs.matmul(&out_grad, !trans_a, false)
+      } else {
+        out_grad.matmul(&lhs, true, trans_a)
+      };
+      sum_to(&grad, rhs.shape()).view(self.rhs.shape().clone())
+    });
+
+    vec![grad_for_lhs, grad_for_rhs]
+  }
+}
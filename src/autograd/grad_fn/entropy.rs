@@ -0,0 +1,65 @@
+use crate::tensor::*;
+use super::super::grad::*;
+
+
+#[derive(Debug)]
+pub struct CrossEntropyGrad {
+  input: Tensor,
+  output: Tensor,
+  softmax: Storage,
+  target: Storage,
+}
+
+impl CrossEntropyGrad {
+  /// `softmax` holds softmax(logits) and `target` the (weighted, smoothed) target
+  /// distribution, both laid out as [N, C]. The target already includes the
+  /// reduction factor (1 / N for a mean, 1 for a sum).
+  pub fn new(input: &Tensor, output: &Tensor, softmax: Storage, target: Storage) -> Self {
+    CrossEntropyGrad {
+      input: input.clone(),
+      output: output.clone(),
+      softmax,
+      target,
+    }
+  }
+}
+
+impl GradientFunction for CrossEntropyGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      // For loss_i = -sum_c t_ic * log_softmax(x)_ic the gradient w.r.t. the logits
+      // is softmax_i * sum_c t_ic - t_i, which collapses to softmax - target when
+      // every row of the target sums to one.
+      let mut row_mass = self.target.sum_axis(1);
+      row_mass.unsqueeze(1);
+
+      let grad = &(&self.softmax * &row_mass) - &self.target;
+      let mut grad = &grad * out_grad;
+      grad.reshape(self.input.tensor().shape().clone());
+
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      // Same expression as above, with the softmax recomputed from the logits
+      let device = self.input.device();
+      let softmax = self.input.view(self.softmax.shape().clone()).softmax(1);
+      let mut row_mass = self.target.sum_axis(1);
+      row_mass.unsqueeze(1);
+
+      let grad = &(&softmax * &constant(row_mass, device)) - &constant(self.target.clone(), device);
+      (&grad * out_grad).view(self.input.shape().clone())
+    });
+    vec![input]
+  }
+}
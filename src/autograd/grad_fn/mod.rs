@@ -3,9 +3,11 @@ pub mod reduction;
 pub mod transform;
 pub mod blas;
 pub mod activation;
+pub mod entropy;
 
 pub use arithmetic::*;
 pub use reduction::*;
 pub use transform::*;
 pub use blas::*;
-pub use activation::*;
\ No newline at end of file
+pub use activation::*;
+pub use entropy::*;
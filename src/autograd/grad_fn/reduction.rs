@@ -19,14 +19,12 @@ impl SumGrad {
 
 impl GradientFunction for SumGrad {
   fn backward(&self) {
-    let device = self.output.device();
     if let Some(input_grad) = &self.input.grad() {
-      if let Some(out_grad) = &self.output.grad() {
+      if let Some(out_grad) = self.output.grad().unwrap().borrow().as_ref() {
         // For sum, we need to expand the gradient to match input shape
         let input_shape = self.input.tensor().shape();
-        let ones = Storage::ones(input_shape.clone(), Some(device), None);
-        let expanded_grad = &ones * out_grad.borrow().get(&[0]);
-        input_grad.borrow_mut().add_tensor_assign(&expanded_grad);
+        let expanded_grad = expand_reduced(out_grad, input_shape, &[]);
+        accumulate_grad(input_grad, &expanded_grad);
       }
     }
   }
@@ -34,6 +32,13 @@ impl GradientFunction for SumGrad {
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.input]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      expand_reduced_graph(out_grad, self.input.shape(), &[])
+    });
+    vec![input]
+  }
 }
 
 #[derive(Debug)]
@@ -53,16 +58,13 @@ impl MeanGrad {
 
 impl GradientFunction for MeanGrad {
   fn backward(&self) {
-    let device = self.output.device();
-
     if let Some(input_grad) = &self.input.grad() {
-      if let Some(out_grad) = &self.output.grad() {
+      if let Some(out_grad) = self.output.grad().unwrap().borrow().as_ref() {
         // For mean, expand gradient and divide by number of elements
         let input_shape = self.input.tensor().shape();
         let n_elements = input_shape.iter().product::<usize>() as f32;
-        let ones = Storage::ones(input_shape.clone(), Some(device), None);
-        let expanded_grad = &ones * (out_grad.borrow().get(&[0]) / n_elements);
-        input_grad.borrow_mut().add_tensor_assign(&expanded_grad);
+        let expanded_grad = expand_reduced(out_grad, input_shape, &[]).div_f32(n_elements);
+        accumulate_grad(input_grad, &expanded_grad);
       }
     }
   }
@@ -70,6 +72,14 @@ impl GradientFunction for MeanGrad {
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.input]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let n_elements = self.input.shape().iter().product::<usize>() as f32;
+      expand_reduced_graph(out_grad, self.input.shape(), &[]).div_f32(n_elements)
+    });
+    vec![input]
+  }
 }
 
 #[derive(Debug)]
@@ -89,32 +99,449 @@ impl ProductGrad {
 
 impl GradientFunction for ProductGrad {
   fn backward(&self) {
-    let device = self.output.device();
-
     if let Some(input_grad) = &self.input.grad() {
-      if let Some(out_grad) = &self.output.grad() {
-        // For product, each element's gradient is the product of all other elements
-        let input_data = self.input.tensor();
-        let mut grad = Storage::zeros(input_data.shape().clone(), Some(device), None);
-        let total_product = self.output.tensor().get(&[0]);
-        
-        // For each element, divide total product by that element to get product of others
-        for i in 0..input_data.data().read().unwrap().len() {
-          let element = input_data.data().read().unwrap()[i];
-          if element != 0.0 {
-            grad.data_mut()[i] = total_product / element;
-          }
+      if let Some(out_grad) = self.output.grad().unwrap().borrow().as_ref() {
+        // Each element's gradient is the product of all the other elements
+        let grad = &product_of_others(self.input.tensor(), &[]) * out_grad;
+        accumulate_grad(input_grad, &grad);
+      }
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      product_graph(&self.input, &[], out_grad)
+    });
+    vec![input]
+  }
+}
+
+
+/// Marks the reduced dimensions; an empty `dims` selects every dimension.
+fn reduced_mask(rank: usize, dims: &[usize]) -> Vec<bool> {
+  let mut mask = vec![dims.is_empty(); rank];
+  for &dim in dims {
+    mask[dim] = true;
+  }
+  mask
+}
+
+/// Number of input elements that fold into each output element.
+pub(crate) fn reduced_count(shape: &[usize], dims: &[usize]) -> f32 {
+  let mask = reduced_mask(shape.len(), dims);
+  shape.iter().zip(mask).filter(|(_, reduced)| *reduced).map(|(&size, _)| size).product::<usize>() as f32
+}
+
+/// Broadcasts a reduced tensor (with or without keepdim) back over the input shape.
+pub(crate) fn expand_reduced(reduced: &Storage, input_shape: &[usize], dims: &[usize]) -> Storage {
+  let mask = reduced_mask(input_shape.len(), dims);
+  let keepdim_shape: Vec<usize> = input_shape.iter().zip(mask)
+    .map(|(&size, reduced)| if reduced { 1 } else { size })
+    .collect();
+  reduced.contiguous().view(keepdim_shape).broadcast(input_shape).contiguous()
+}
+
+/// Tensor counterpart of `expand_reduced`, recorded so it stays differentiable.
+fn expand_reduced_graph(reduced: &Tensor, input_shape: &[usize], dims: &[usize]) -> Tensor {
+  let mask = reduced_mask(input_shape.len(), dims);
+  let keepdim_shape: Vec<usize> = input_shape.iter().zip(mask)
+    .map(|(&size, reduced)| if reduced { 1 } else { size })
+    .collect();
+  reduced.view(keepdim_shape).broadcast(input_shape).contiguous()
+}
+
+/// For every element, the product of the other elements in its reduced lane.
+/// Computed with prefix/suffix products so zeros are handled exactly.
+pub(crate) fn product_of_others(input: &Storage, dims: &[usize]) -> Storage {
+  let shape = input.shape().clone();
+  let mask = reduced_mask(shape.len(), dims);
+  let order: Vec<usize> = (0..shape.len()).filter(|&d| !mask[d])
+    .chain((0..shape.len()).filter(|&d| mask[d]))
+    .collect();
+  let mut inverse = vec![0; order.len()];
+  for (position, &dim) in order.iter().enumerate() {
+    inverse[dim] = position;
+  }
+
+  let mut permuted = input.clone();
+  permuted.permute(&order);
+  let lane_len: usize = (0..shape.len()).filter(|&d| mask[d]).map(|d| shape[d]).product();
+
+  // Computed in f64 and cast back to the input dtype
+  let values = permuted.to_vec::<f64>();
+  let mut others = vec![0.0; values.len()];
+  if lane_len > 0 {
+    for (lane, out) in values.chunks(lane_len).zip(others.chunks_mut(lane_len)) {
+      let mut prefix = 1.0;
+      for (i, &x) in lane.iter().enumerate() {
+        out[i] = prefix;
+        prefix *= x;
+      }
+      let mut suffix = 1.0;
+      for (i, &x) in lane.iter().enumerate().rev() {
+        out[i] *= suffix;
+        suffix *= x;
+      }
+    }
+  }
+
+  let mut result = Storage::from_vec(others, permuted.shape().clone(), Some(input.device())).to_dtype(input.dtype());
+  result.permute(&inverse);
+  result.contiguous()
+}
+
+/// Derivative of `max`/`min` w.r.t. the input: the gradient is shared evenly
+/// between all elements of a lane that attain the extreme.
+pub(crate) fn extreme_weights(input: &Storage, output: &Storage, dims: &[usize]) -> Storage {
+  let extreme = expand_reduced(output, input.shape(), dims);
+  let mask = input.elementwise_op(&extreme, |x, e| if x == e { 1.0 } else { 0.0 });
+  let ties = expand_reduced(&mask.sum_dims(dims, true), input.shape(), dims);
+  mask.div_tensor(&ties)
+}
+
+/// Derivative of the `p`-norm w.r.t. the input, given the computed norm.
+pub(crate) fn norm_derivative(input: &Storage, output: &Storage, p: f32, dims: &[usize]) -> Storage {
+  let norm = expand_reduced(output, input.shape(), dims);
+  if p == f32::INFINITY {
+    // Like max: split evenly between the elements of largest magnitude
+    let mask = input.elementwise_op(&norm, |x, n| if x.abs() == n { x.signum() } else { 0.0 });
+    let ties = expand_reduced(&mask.abs().sum_dims(dims, true), input.shape(), dims);
+    mask.div_tensor(&ties)
+  } else {
+    // d ||x||_p / dx = sign(x) |x|^(p-1) / ||x||_p^(p-1), taken as 0 at a zero norm
+    input.elementwise_op(&norm, |x, n| {
+      if n == 0.0 { 0.0 } else { x.signum() * (x.abs() / n).powf(p - 1.0) }
+    })
+  }
+}
+
+/// Differentiable gradient of a product, i.e. the product of the other elements
+/// of each lane. Zeros are split off with constant masks so no element is ever
+/// divided by zero: the non-zero elements give their product divided by each
+/// one, and the zeros of a lane contribute the product of the other zeros.
+fn product_graph(input: &Tensor, dims: &[usize], out_grad: &Tensor) -> Tensor {
+  let shape = input.shape().clone();
+  let device = input.device();
+  let dims: Vec<usize> = if dims.is_empty() { (0..shape.len()).collect() } else { dims.to_vec() };
+  let expand = |reduced: &Tensor| expand_reduced_graph(reduced, &shape, &dims);
+
+  let zero_mask = input.tensor().apply(|x| if x == 0.0 { 1.0 } else { 0.0 });
+  let lane_zeros = expand_reduced(&zero_mask.sum_dims(&dims, true), &shape, &dims);
+  let only_zero = constant(zero_mask.elementwise_op(&lane_zeros, |z, n| if z == 1.0 && n == 1.0 { 1.0 } else { 0.0 }), device);
+  let one_of_two = constant(zero_mask.elementwise_op(&lane_zeros, |z, n| if z == 1.0 && n == 2.0 { 1.0 } else { 0.0 }), device);
+  let nonzero = constant(zero_mask.apply(|z| 1.0 - z), device);
+  let zero = constant(zero_mask, device);
+
+  // Zeros replaced by ones, and the other way around
+  let nonzero_values = &(input * &nonzero) + &zero;
+  let zero_values = &(input * &zero) + &nonzero;
+  let nonzero_others = &expand(&nonzero_values.prod_dims(&dims, true)) / &nonzero_values;
+  let zeros_product = expand(&zero_values.prod_dims(&dims, true));
+
+  // For a zero: 1 when it is the lane's only zero, the other zero when there are
+  // two, and 0 beyond that, where its gradient vanishes as well
+  let zeros_sum = expand(&(input * &zero).sum_dims(&dims, true));
+  let other_zeros = &(&(&zeros_sum - input) * &one_of_two) + &only_zero;
+
+  let others = &nonzero_others * &(&(&zeros_product * &nonzero) + &other_zeros);
+  &expand(out_grad) * &others
+}
+
+/// Softmax over the lane reduced by `dims`. The reduced dims are moved to the
+/// back and merged into one axis, which keeps it differentiable for any dims.
+fn lane_softmax(input: &Tensor, dims: &[usize]) -> Tensor {
+  let rank = input.shape().len();
+  let mask = reduced_mask(rank, dims);
+  let order: Vec<usize> = (0..rank).filter(|&d| !mask[d])
+    .chain((0..rank).filter(|&d| mask[d]))
+    .collect();
+  let mut inverse = vec![0; rank];
+  for (position, &dim) in order.iter().enumerate() {
+    inverse[dim] = position;
+  }
+
+  let mut permuted = input.clone();
+  permuted.permute(&order);
+  let permuted_shape = permuted.shape().clone();
+  let kept = mask.iter().filter(|&&reduced| !reduced).count();
+  let mut merged = permuted_shape[..kept].to_vec();
+  merged.push(permuted_shape[kept..].iter().product());
+
+  let mut softmax = permuted.view(merged).softmax(kept).view(permuted_shape);
+  softmax.permute(&inverse);
+  softmax.contiguous()
+}
+
+
+macro_rules! reduction_grad_struct {
+  ($name:ident $(, $field:ident: $ty:ty)*) => {
+    #[derive(Debug)]
+    pub struct $name {
+      input: Tensor,
+      dims: Vec<usize>,
+      $($field: $ty,)*
+      output: Tensor,
+    }
+
+    impl $name {
+      pub fn new(input: &Tensor, dims: &[usize], $($field: $ty,)* output: &Tensor) -> Self {
+        $name {
+          input: input.clone(),
+          dims: dims.to_vec(),
+          $($field,)*
+          output: output.clone(),
         }
-        
-        // Multiply by output gradient
-        grad = &grad * out_grad.borrow().get(&[0]);
-        input_grad.borrow_mut().add_tensor_assign(&grad);
       }
     }
+  };
+}
+
+reduction_grad_struct!(SumDimsGrad);
+reduction_grad_struct!(MeanDimsGrad);
+reduction_grad_struct!(ProdDimsGrad);
+reduction_grad_struct!(MaxMinGrad);
+reduction_grad_struct!(VarGrad, unbiased: bool);
+reduction_grad_struct!(StdGrad, unbiased: bool);
+reduction_grad_struct!(NormGrad, p: f32);
+reduction_grad_struct!(LogSumExpGrad);
+
+impl GradientFunction for SumDimsGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      let grad = expand_reduced(out_grad, self.input.shape(), &self.dims);
+      accumulate_grad(input_grad, &grad);
+    }
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.input]
   }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      expand_reduced_graph(out_grad, self.input.shape(), &self.dims)
+    });
+    vec![input]
+  }
+}
+
+impl GradientFunction for MeanDimsGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      let count = reduced_count(self.input.shape(), &self.dims);
+      let grad = expand_reduced(out_grad, self.input.shape(), &self.dims).div_f32(count);
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let count = reduced_count(self.input.shape(), &self.dims);
+      expand_reduced_graph(out_grad, self.input.shape(), &self.dims).div_f32(count)
+    });
+    vec![input]
+  }
 }
 
+impl GradientFunction for ProdDimsGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      let others = product_of_others(self.input.tensor(), &self.dims);
+      let grad = expand_reduced(out_grad, self.input.shape(), &self.dims).mul_tensor(&others);
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      product_graph(&self.input, &self.dims, out_grad)
+    });
+    vec![input]
+  }
+}
+
+impl GradientFunction for MaxMinGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      let weights = extreme_weights(self.input.tensor(), self.output.tensor(), &self.dims);
+      let grad = expand_reduced(out_grad, self.input.shape(), &self.dims).mul_tensor(&weights);
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      // The tie mask is piecewise constant, so only the incoming gradient is recorded
+      let weights = extreme_weights(self.input.tensor(), self.output.tensor(), &self.dims);
+      let local = constant(weights, self.input.device());
+      &expand_reduced_graph(out_grad, self.input.shape(), &self.dims) * &local
+    });
+    vec![input]
+  }
+}
+
+impl GradientFunction for VarGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      // d var / dx = 2 (x - mean) / (n - correction)
+      let input = self.input.tensor();
+      let correction = if self.unbiased { 1.0 } else { 0.0 };
+      let denominator = reduced_count(self.input.shape(), &self.dims) - correction;
+      let mean = expand_reduced(&input.mean_dims(&self.dims, true), self.input.shape(), &self.dims);
+      let grad = input.sub_tensor(&mean)
+        .mul_tensor(&expand_reduced(out_grad, self.input.shape(), &self.dims))
+        .mul_f32(2.0 / denominator);
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let correction = if self.unbiased { 1.0 } else { 0.0 };
+      let denominator = reduced_count(self.input.shape(), &self.dims) - correction;
+      let mean = expand_reduced_graph(&self.input.mean_dims(&self.dims, true), self.input.shape(), &self.dims);
+      (&(&self.input - &mean) * &expand_reduced_graph(out_grad, self.input.shape(), &self.dims))
+        .mul_f32(2.0 / denominator)
+    });
+    vec![input]
+  }
+}
+
+impl GradientFunction for StdGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      // d std / dx = (x - mean) / ((n - correction) * std)
+      let input = self.input.tensor();
+      let correction = if self.unbiased { 1.0 } else { 0.0 };
+      let denominator = reduced_count(self.input.shape(), &self.dims) - correction;
+      let mean = expand_reduced(&input.mean_dims(&self.dims, true), self.input.shape(), &self.dims);
+      let std = expand_reduced(self.output.tensor(), self.input.shape(), &self.dims);
+      let grad = input.sub_tensor(&mean)
+        .div_tensor(&std)
+        .mul_tensor(&expand_reduced(out_grad, self.input.shape(), &self.dims))
+        .div_f32(denominator);
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let correction = if self.unbiased { 1.0 } else { 0.0 };
+      let denominator = reduced_count(self.input.shape(), &self.dims) - correction;
+      let mean = expand_reduced_graph(&self.input.mean_dims(&self.dims, true), self.input.shape(), &self.dims);
+      let std = expand_reduced_graph(&self.input.std(&self.dims, self.unbiased, true), self.input.shape(), &self.dims);
+      (&(&(&self.input - &mean) / &std) * &expand_reduced_graph(out_grad, self.input.shape(), &self.dims))
+        .div_f32(denominator)
+    });
+    vec![input]
+  }
+}
+
+impl GradientFunction for NormGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      let local = norm_derivative(self.input.tensor(), self.output.tensor(), self.p, &self.dims);
+      let grad = local.mul_tensor(&expand_reduced(out_grad, self.input.shape(), &self.dims));
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let has_zero_norm = self.output.tensor().to_vec::<f32>().contains(&0.0);
+      let local = if self.p == f32::INFINITY || has_zero_norm {
+        // Piecewise constant (or undefined) in the input, so kept out of the graph
+        constant(norm_derivative(self.input.tensor(), self.output.tensor(), self.p, &self.dims), self.input.device())
+      } else {
+        // sign(x) |x|^(p-1) / ||x||_p^(p-1)
+        let sign = constant(self.input.tensor().sign(), self.input.device());
+        let norm = expand_reduced_graph(&self.input.norm(self.p, &self.dims, true), self.input.shape(), &self.dims);
+        &(&sign * &self.input.abs().pow_f32(self.p - 1.0)) / &norm.pow_f32(self.p - 1.0)
+      };
+      &local * &expand_reduced_graph(out_grad, self.input.shape(), &self.dims)
+    });
+    vec![input]
+  }
+}
+
+impl GradientFunction for LogSumExpGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      // The gradient of logsumexp is the softmax over the reduced lane
+      let softmax = lane_softmax(&self.input.detach(), &self.dims);
+      let grad = softmax.tensor().mul_tensor(&expand_reduced(out_grad, self.input.shape(), &self.dims));
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      &lane_softmax(&self.input, &self.dims) * &expand_reduced_graph(out_grad, self.input.shape(), &self.dims)
+    });
+    vec![input]
+  }
+}
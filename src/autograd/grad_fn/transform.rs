@@ -5,59 +5,711 @@ use super::super::grad::*;
 #[derive(Debug)]
 pub struct PermuteGrad {
   input: Tensor,
+  dims: Vec<usize>,
   output: Tensor,
 }
 
 
 impl PermuteGrad {
-  pub fn new(input: &Tensor, output: &Tensor) -> Self {
+  /// `dims` is the permutation applied to the input, as passed to `permute`.
+  pub fn new(input: &Tensor, dims: &[usize], output: &Tensor) -> Self {
     PermuteGrad {
       input: input.clone(),
+      dims: dims.to_vec(),
       output: output.clone(),
     }
   }
+
+  fn inverse(&self) -> Vec<usize> {
+    let mut inverse = vec![0; self.dims.len()];
+    for (i, &dim) in self.dims.iter().enumerate() {
+      inverse[dim] = i;
+    }
+    inverse
+  }
 }
 
 impl GradientFunction for PermuteGrad {
   fn backward(&self) {
     let out_grad = self.output.grad().unwrap();
     let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      // Undo the permutation on the incoming gradient
+      let mut grad_tensor = out_grad.clone();
+      grad_tensor.permute(&self.inverse());
+      accumulate_grad(input_grad, &grad_tensor);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let mut grad = out_grad.clone();
+      grad.permute(&self.inverse());
+      grad
+    });
+    vec![input]
+  }
+}
+
+
+#[derive(Debug)]
+pub struct IndexSelectGrad {
+  input: Tensor,
+  dim: usize,
+  indices: Vec<usize>,
+  output: Tensor,
+}
+
+impl IndexSelectGrad {
+  pub fn new(input: &Tensor, dim: usize, indices: &[usize], output: &Tensor) -> Self {
+    IndexSelectGrad {
+      input: input.clone(),
+      dim,
+      indices: indices.to_vec(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for IndexSelectGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    // Route each selected slice back to the row it came from; repeated indices accumulate
+    if let Some(input_grad) = &self.input.grad() {
+      // `take` produces the same elements with the index dimension unflattened
+      let mut selected_shape = self.input.shape().clone();
+      selected_shape[self.dim] = self.indices.len();
+      let out_grad = out_grad.view(selected_shape);
+
+      let zeros = Storage::zeros(self.input.shape().clone(), Some(self.input.device()), None).to_dtype(out_grad.dtype());
+      let grad = zeros.index_add(self.dim, &self.indices, &out_grad);
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let mut selected_shape = self.input.shape().clone();
+      selected_shape[self.dim] = self.indices.len();
+      let zeros = Tensor::zeros(self.input.shape().clone(), self.input.device(), None).to_dtype(out_grad.dtype());
+      zeros.index_add(self.dim, &self.indices, &out_grad.view(selected_shape))
+    });
+    vec![input]
+  }
+}
+
+
+
+#[derive(Debug)]
+pub struct IndexAddGrad {
+  input: Tensor,
+  dim: usize,
+  indices: Vec<usize>,
+  source: Tensor,
+  output: Tensor,
+}
+
+impl IndexAddGrad {
+  pub fn new(input: &Tensor, dim: usize, indices: &[usize], source: &Tensor, output: &Tensor) -> Self {
+    IndexAddGrad {
+      input: input.clone(),
+      dim,
+      indices: indices.to_vec(),
+      source: source.clone(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for IndexAddGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
 
-    // Get input gradient if it exists (it should since we're backpropagating)
     if let Some(input_grad) = &self.input.grad() {
-      // Determine the permutation that was applied
-      // Compare input and output shapes/strides
-      let input_shape = self.input.tensor().shape();
-      let output_shape = self.output.tensor().shape();
-      
-      // Find the permutation by matching dimensions
-      let mut permutation: Vec<usize> = Vec::new();
-      for i in 0..input_shape.len() {
-        for j in 0..output_shape.len() {
-          if input_shape[i] == output_shape[j] {
-            permutation.push(j);
-            break;
-          }
-        }
+      accumulate_grad(input_grad, out_grad);
+    }
+
+    if let Some(source_grad) = &self.source.grad() {
+      let grad = out_grad.index_select(self.dim, &self.indices);
+      accumulate_grad(source_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input, &self.source]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| out_grad.clone());
+    let source = self.source.grad().map(|_| out_grad.index_select(self.dim, &self.indices));
+    vec![input, source]
+  }
+}
+
+
+
+#[derive(Debug)]
+pub struct GatherGrad {
+  input: Tensor,
+  dim: usize,
+  index: Storage,
+  output: Tensor,
+}
+
+impl GatherGrad {
+  pub fn new(input: &Tensor, dim: usize, index: &Tensor, output: &Tensor) -> Self {
+    GatherGrad {
+      input: input.clone(),
+      dim,
+      index: index.tensor().clone(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for GatherGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      let zeros = Storage::zeros(self.input.shape().clone(), Some(self.input.device()), None).to_dtype(out_grad.dtype());
+      let grad = zeros.scatter_add(self.dim, &self.index, out_grad);
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let zeros = Tensor::zeros(self.input.shape().clone(), self.input.device(), None).to_dtype(out_grad.dtype());
+      zeros.scatter_add(self.dim, &constant(self.index.clone(), self.input.device()), out_grad)
+    });
+    vec![input]
+  }
+}
+
+
+
+/// Backward for both `scatter` and `scatter_add`. Positions overwritten by `scatter`
+/// receive no gradient on the input side.
+#[derive(Debug)]
+pub struct ScatterGrad {
+  input: Tensor,
+  dim: usize,
+  index: Storage,
+  src: Tensor,
+  output: Tensor,
+  accumulate: bool,
+}
+
+impl ScatterGrad {
+  pub fn new(input: &Tensor, dim: usize, index: &Tensor, src: &Tensor, output: &Tensor, accumulate: bool) -> Self {
+    ScatterGrad {
+      input: input.clone(),
+      dim,
+      index: index.tensor().clone(),
+      src: src.clone(),
+      output: output.clone(),
+      accumulate,
+    }
+  }
+}
+
+impl GradientFunction for ScatterGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      if self.accumulate {
+        accumulate_grad(input_grad, out_grad);
+      } else {
+        let zeros = Storage::zeros(self.index.shape().clone(), Some(self.input.device()), None);
+        let grad = out_grad.scatter(self.dim, &self.index, &zeros);
+        accumulate_grad(input_grad, &grad);
       }
-      
-      // Create inverse permutation array
-      let mut inverse_perm = vec![0; permutation.len()];
-      for (i, &p) in permutation.iter().enumerate() {
-        inverse_perm[p] = i;
+    }
+
+    if let Some(src_grad) = &self.src.grad() {
+      let gathered = out_grad.gather(self.dim, &self.index);
+      // `src` may be larger than `index`; only the covered region receives gradient
+      let grad = if gathered.shape() == self.src.shape() {
+        gathered
+      } else {
+        let region: Vec<SliceArg> = gathered.shape().iter().map(|&size| SliceArg::from(0..size)).collect();
+        Storage::zeros(self.src.shape().clone(), Some(self.src.device()), None)
+          .to_dtype(gathered.dtype())
+          .slice_scatter(&region, &gathered)
+      };
+      accumulate_grad(src_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input, &self.src]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let index = constant(self.index.clone(), self.input.device());
+    let input = self.input.grad().map(|_| {
+      if self.accumulate {
+        out_grad.clone()
+      } else {
+        let zeros = Tensor::zeros(self.index.shape().clone(), self.input.device(), None);
+        out_grad.scatter(self.dim, &index, &zeros)
       }
-      
-      // Apply inverse permutation to gradient
-      let mut grad_tensor = out_grad.clone();
-      grad_tensor.permute(&inverse_perm);
-      
-      // Accumulate the gradient
-      input_grad.borrow_mut().add_tensor_assign(&grad_tensor);
+    });
+    let src = self.src.grad().map(|_| {
+      let gathered = out_grad.gather(self.dim, &index);
+      if gathered.shape() == self.src.shape() {
+        return gathered;
+      }
+      let region: Vec<SliceArg> = gathered.shape().iter().map(|&size| SliceArg::from(0..size)).collect();
+      Tensor::zeros(self.src.shape().clone(), self.src.device(), None).to_dtype(gathered.dtype()).slice_scatter(&region, &gathered)
+    });
+    vec![input, src]
+  }
+}
+
+
+
+/// Backward for `slice`, `narrow`, `select` and `contiguous`: the gradient of the
+/// view is written back into the region of the source it was taken from.
+#[derive(Debug)]
+pub struct SliceGrad {
+  input: Tensor,
+  args: Vec<SliceArg>,
+  output: Tensor,
+}
+
+impl SliceGrad {
+  pub fn new(input: &Tensor, args: &[SliceArg], output: &Tensor) -> Self {
+    SliceGrad {
+      input: input.clone(),
+      args: args.to_vec(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for SliceGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      let zeros = Storage::zeros(self.input.shape().clone(), Some(self.input.device()), None).to_dtype(out_grad.dtype());
+      let region_shape = zeros.slice(&self.args).shape().clone();
+      let grad = zeros.slice_scatter(&self.args, &out_grad.view(region_shape));
+      accumulate_grad(input_grad, &grad);
     }
-  
   }
 
   fn prev(&self) -> Vec<&Tensor> {
     vec![&self.input]
   }
-}
\ No newline at end of file
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let zeros = Tensor::zeros(self.input.shape().clone(), self.input.device(), None).to_dtype(out_grad.dtype());
+      let region_shape = zeros.tensor().slice(&self.args).shape().clone();
+      zeros.slice_scatter(&self.args, &out_grad.view(region_shape))
+    });
+    vec![input]
+  }
+}
+
+
+
+#[derive(Debug)]
+pub struct SliceScatterGrad {
+  input: Tensor,
+  args: Vec<SliceArg>,
+  src: Tensor,
+  output: Tensor,
+}
+
+impl SliceScatterGrad {
+  pub fn new(input: &Tensor, args: &[SliceArg], src: &Tensor, output: &Tensor) -> Self {
+    SliceScatterGrad {
+      input: input.clone(),
+      args: args.to_vec(),
+      src: src.clone(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for SliceScatterGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    // The overwritten region does not depend on the input
+    if let Some(input_grad) = &self.input.grad() {
+      let zeros = Storage::zeros(self.src.shape().clone(), Some(self.src.device()), None);
+      let grad = out_grad.slice_scatter(&self.args, &zeros);
+      accumulate_grad(input_grad, &grad);
+    }
+
+    if let Some(src_grad) = &self.src.grad() {
+      let grad = out_grad.slice(&self.args).contiguous();
+      accumulate_grad(src_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input, &self.src]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let zeros = Tensor::zeros(self.src.shape().clone(), self.src.device(), None);
+      out_grad.slice_scatter(&self.args, &zeros)
+    });
+    let src = self.src.grad().map(|_| out_grad.slice(&self.args).contiguous());
+    vec![input, src]
+  }
+}
+
+
+
+#[derive(Debug)]
+pub struct MaskedSelectGrad {
+  input: Tensor,
+  mask: Storage,
+  output: Tensor,
+}
+
+impl MaskedSelectGrad {
+  pub fn new(input: &Tensor, mask: &Tensor, output: &Tensor) -> Self {
+    MaskedSelectGrad {
+      input: input.clone(),
+      mask: mask.tensor().clone(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for MaskedSelectGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      let zeros = Storage::zeros(self.input.shape().clone(), Some(self.input.device()), None).to_dtype(out_grad.dtype());
+      let grad = zeros.masked_scatter(&self.mask, out_grad);
+      accumulate_grad(input_grad, &grad);
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| {
+      let zeros = Tensor::zeros(self.input.shape().clone(), self.input.device(), None).to_dtype(out_grad.dtype());
+      zeros.masked_scatter(&constant(self.mask.clone(), self.input.device()), out_grad)
+    });
+    vec![input]
+  }
+}
+
+
+
+#[derive(Debug)]
+pub struct MaskedScatterGrad {
+  input: Tensor,
+  mask: Storage,
+  source: Tensor,
+  output: Tensor,
+}
+
+impl MaskedScatterGrad {
+  pub fn new(input: &Tensor, mask: &Tensor, source: &Tensor, output: &Tensor) -> Self {
+    MaskedScatterGrad {
+      input: input.clone(),
+      mask: mask.tensor().clone(),
+      source: source.clone(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for MaskedScatterGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    // Gradient reaching the masked positions belongs to `source`
+    let selected = out_grad.masked_select(&self.mask);
+
+    if let Some(input_grad) = &self.input.grad() {
+      let zeros = Storage::zeros(selected.shape().clone(), Some(self.input.device()), None);
+      let grad = out_grad.masked_scatter(&self.mask, &zeros);
+      accumulate_grad(input_grad, &grad);
+    }
+
+    if let Some(source_grad) = &self.source.grad() {
+      // Only the first `selected` elements of `source` were consumed
+      let len: usize = self.source.shape().iter().product();
+      let flat = Storage::zeros(vec![len], Some(self.source.device()), None).to_dtype(selected.dtype());
+      let grad = flat.slice_scatter(&[SliceArg::from(0..selected.shape()[0])], &selected);
+      accumulate_grad(source_grad, &grad.view(self.source.shape().clone()));
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input, &self.source]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let mask = constant(self.mask.clone(), self.input.device());
+    let selected = out_grad.masked_select(&mask);
+    let input = self.input.grad().map(|_| {
+      let zeros = Tensor::zeros(selected.shape().clone(), self.input.device(), None);
+      out_grad.masked_scatter(&mask, &zeros)
+    });
+    let source = self.source.grad().map(|_| {
+      let len: usize = self.source.shape().iter().product();
+      let flat = Tensor::zeros(vec![len], self.source.device(), None).to_dtype(selected.dtype());
+      flat.slice_scatter(&[SliceArg::from(0..selected.shape()[0])], &selected).view(self.source.shape().clone())
+    });
+    vec![input, source]
+  }
+}
+
+
+
+#[derive(Debug)]
+pub struct CatGrad {
+  inputs: Vec<Tensor>,
+  dim: usize,
+  output: Tensor,
+}
+
+impl CatGrad {
+  pub fn new(inputs: &[&Tensor], dim: usize, output: &Tensor) -> Self {
+    CatGrad {
+      inputs: inputs.iter().map(|&input| input.clone()).collect(),
+      dim,
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for CatGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    // Each input receives the slice of the gradient it was copied into
+    let mut start = 0;
+    for input in &self.inputs {
+      let len = input.shape()[self.dim];
+      if let Some(input_grad) = &input.grad() {
+        let grad = out_grad.narrow(self.dim, start, len).contiguous();
+        accumulate_grad(input_grad, &grad);
+      }
+      start += len;
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    self.inputs.iter().collect()
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let mut start = 0;
+    self.inputs.iter().map(|input| {
+      let len = input.shape()[self.dim];
+      let grad = input.grad().map(|_| out_grad.narrow(self.dim, start, len).contiguous());
+      start += len;
+      grad
+    }).collect()
+  }
+}
+
+
+
+#[derive(Debug)]
+pub struct StackGrad {
+  inputs: Vec<Tensor>,
+  dim: usize,
+  output: Tensor,
+}
+
+impl StackGrad {
+  pub fn new(inputs: &[&Tensor], dim: usize, output: &Tensor) -> Self {
+    StackGrad {
+      inputs: inputs.iter().map(|&input| input.clone()).collect(),
+      dim,
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for StackGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    for (index, input) in self.inputs.iter().enumerate() {
+      if let Some(input_grad) = &input.grad() {
+        let grad = out_grad.select(self.dim, index).contiguous().view(input.shape().clone());
+        accumulate_grad(input_grad, &grad);
+      }
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    self.inputs.iter().collect()
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    self.inputs.iter().enumerate().map(|(index, input)| {
+      input.grad().map(|_| out_grad.select(self.dim, index).contiguous().view(input.shape().clone()))
+    }).collect()
+  }
+}
+
+
+#[derive(Debug)]
+pub struct CastGrad {
+  input: Tensor,
+  output: Tensor,
+}
+
+impl CastGrad {
+  pub fn new(input: &Tensor, output: &Tensor) -> Self {
+    CastGrad {
+      input: input.clone(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for CastGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    if let Some(input_grad) = &self.input.grad() {
+      accumulate_grad(input_grad, &out_grad.to_dtype(self.input.dtype()));
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| out_grad.to_dtype(self.input.dtype()));
+    vec![input]
+  }
+}
+
+
+#[derive(Debug)]
+pub struct ViewGrad {
+  input: Tensor,
+  output: Tensor,
+}
+
+impl ViewGrad {
+  pub fn new(input: &Tensor, output: &Tensor) -> Self {
+    ViewGrad {
+      input: input.clone(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for ViewGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    // The view is a row-major reinterpretation, so reshaping maps the gradient back
+    if let Some(input_grad) = &self.input.grad() {
+      accumulate_grad(input_grad, &out_grad.contiguous().view(self.input.shape().clone()));
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| out_grad.view(self.input.shape().clone()));
+    vec![input]
+  }
+}
+
+
+#[derive(Debug)]
+pub struct BroadcastGrad {
+  input: Tensor,
+  output: Tensor,
+}
+
+impl BroadcastGrad {
+  pub fn new(input: &Tensor, output: &Tensor) -> Self {
+    BroadcastGrad {
+      input: input.clone(),
+      output: output.clone(),
+    }
+  }
+}
+
+impl GradientFunction for BroadcastGrad {
+  fn backward(&self) {
+    let out_grad = self.output.grad().unwrap();
+    let out_grad = out_grad.borrow();
+    let Some(out_grad) = out_grad.as_ref() else { return };
+
+    // Sum over the broadcast dimensions
+    if let Some(input_grad) = &self.input.grad() {
+      accumulate_grad(input_grad, &reduce_grad!(out_grad, self.input.shape()));
+    }
+  }
+
+  fn prev(&self) -> Vec<&Tensor> {
+    vec![&self.input]
+  }
+
+  fn backward_graph(&self, out_grad: &Tensor) -> Vec<Option<Tensor>> {
+    let input = self.input.grad().map(|_| sum_to(out_grad, self.input.shape()));
+    vec![input]
+  }
+}
@@ -0,0 +1,132 @@
+use std::fmt;
+
+use crate::tensor::*;
+use super::engine::grad;
+use super::mode::enable_grad;
+
+
+/// One Jacobian entry where the backward pass disagrees with finite differences.
+#[derive(Debug, Clone)]
+pub struct GradMismatch {
+  /// Position of the input in the `inputs` slice
+  pub input: usize,
+  /// Index of the perturbed input element
+  pub element: Vec<usize>,
+  /// Index of the output element
+  pub output: Vec<usize>,
+  pub analytical: f64,
+  pub numerical: f64,
+}
+
+/// Result of `gradcheck`: every mismatching Jacobian entry.
+#[derive(Debug, Clone, Default)]
+pub struct GradcheckReport {
+  pub mismatches: Vec<GradMismatch>,
+}
+
+impl GradcheckReport {
+  pub fn is_ok(&self) -> bool {
+    self.mismatches.is_empty()
+  }
+}
+
+impl fmt::Display for GradcheckReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.is_ok() {
+      return write!(f, "gradcheck passed");
+    }
+    write!(f, "gradcheck failed with {} mismatches", self.mismatches.len())?;
+    for mismatch in &self.mismatches {
+      write!(f, "\n  input {} element {:?}, output {:?}: analytical {}, numerical {}",
+        mismatch.input, mismatch.element, mismatch.output, mismatch.analytical, mismatch.numerical)?;
+    }
+    Ok(())
+  }
+}
+
+
+/// Row-major multi-index of the flat position `index` in `shape`.
+fn unravel(mut index: usize, shape: &[usize]) -> Vec<usize> {
+  let mut position = vec![0; shape.len()];
+  for dim in (0..shape.len()).rev() {
+    position[dim] = index % shape[dim];
+    index /= shape[dim];
+  }
+  position
+}
+
+fn evaluate<F: Fn(&[Tensor]) -> Tensor>(f: &F, inputs: &[Vec<f64>], like: &[Tensor]) -> Vec<f64> {
+  let inputs: Vec<Tensor> = inputs.iter().zip(like)
+    .map(|(data, input)| Tensor::from_vec(data.clone(), input.shape().clone(), input.device(), Some(true)))
+    .collect();
+  f(&inputs).tensor().to_vec::<f64>()
+}
+
+/// Compares the gradients computed by backward against central finite
+/// differences, for every output element w.r.t. every element of `inputs`.
+/// Inputs are promoted to f64 first so the numerical side is accurate; an
+/// entry mismatches when `|analytical - numerical| > atol + rtol * |numerical|`.
+///
+/// ```ignore
+/// let report = gradcheck(|x| x[0].softmax(1), &[&x], 1e-6, 1e-5, 1e-3);
+/// assert!(report.is_ok(), "{}", report);
+/// ```
+pub fn gradcheck<F: Fn(&[Tensor]) -> Tensor>(f: F, inputs: &[&Tensor], eps: f64, atol: f64, rtol: f64) -> GradcheckReport {
+  let _enable_grad = enable_grad();
+  let inputs: Vec<Tensor> = inputs.iter().map(|input| {
+    let mut input = input.to_dtype(DType::F64).detach();
+    input.requires_grad_(true);
+    input
+  }).collect();
+
+  // Analytical Jacobian, one output element at a time
+  let output = f(&inputs);
+  let output_shape = output.shape().clone();
+  let output_len: usize = output_shape.iter().product();
+  let input_refs: Vec<&Tensor> = inputs.iter().collect();
+  let analytical: Vec<Vec<Vec<f64>>> = (0..output_len).map(|index| {
+    let grads = if *output.requires_grad() {
+      let mut seed = vec![0.0f64; output_len];
+      seed[index] = 1.0;
+      let seed = Tensor::from_vec(seed, output_shape.clone(), output.device(), None);
+      grad(&[&output], &input_refs, Some(&[&seed]), true, false)
+    } else {
+      vec![None; inputs.len()]
+    };
+    grads.iter().zip(&inputs).map(|(grad, input)| match grad {
+      Some(grad) => grad.tensor().to_vec::<f64>(),
+      None => vec![0.0; input.shape().iter().product()],
+    }).collect()
+  }).collect();
+
+  // Numerical Jacobian, one input element at a time. The perturbed inputs still
+  // require grad so `f` may itself call `grad`, e.g. to check second derivatives.
+  let values: Vec<Vec<f64>> = inputs.iter().map(|input| input.tensor().to_vec::<f64>()).collect();
+  let mut report = GradcheckReport::default();
+  for (input, data) in values.iter().enumerate() {
+    for element in 0..data.len() {
+      let mut perturbed = values.clone();
+      perturbed[input][element] = data[element] + eps;
+      let plus = evaluate(&f, &perturbed, &inputs);
+      perturbed[input][element] = data[element] - eps;
+      let minus = evaluate(&f, &perturbed, &inputs);
+
+      for index in 0..output_len {
+        let numerical = (plus[index] - minus[index]) / (2.0 * eps);
+        let analytical = analytical[index][input][element];
+        // Written so that NaN on either side counts as a mismatch
+        let close = (analytical - numerical).abs() <= atol + rtol * numerical.abs();
+        if !close {
+          report.mismatches.push(GradMismatch {
+            input,
+            element: unravel(element, inputs[input].shape()),
+            output: unravel(index, &output_shape),
+            analytical,
+            numerical,
+          });
+        }
+      }
+    }
+  }
+  report
+}
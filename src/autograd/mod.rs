@@ -1,7 +1,15 @@
 pub mod scalar;
 pub mod grad;
 pub mod grad_fn;
+pub mod mode;
+pub mod functional;
+pub mod forward;
+mod engine;
+mod gradcheck;
 
 // Re-export everything we want to be publicly accessible
 pub use grad::*;
 pub use grad_fn::*;
+pub use mode::*;
+pub use engine::*;
+pub use gradcheck::*;
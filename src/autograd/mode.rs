@@ -0,0 +1,68 @@
+use std::cell::Cell;
+
+
+#[derive(Clone, Copy)]
+struct GradMode {
+  enabled: bool,
+  inference: bool,
+}
+
+thread_local! {
+  static GRAD_MODE: Cell<GradMode> = const { Cell::new(GradMode { enabled: true, inference: false }) };
+}
+
+fn enter(mode: GradMode) -> GradModeGuard {
+  GradModeGuard { previous: GRAD_MODE.with(|state| state.replace(mode)) }
+}
+
+/// Disables graph construction on the current thread until the returned guard
+/// is dropped. Ops still compute their results but never record a backward
+/// node, so evaluation loops and manual parameter updates stay cheap.
+///
+/// ```ignore
+/// let _no_grad = no_grad();
+/// let prediction = model.forward(&x);
+/// ```
+pub fn no_grad() -> GradModeGuard {
+  set_grad_enabled(false)
+}
+
+/// Re-enables graph construction inside a `no_grad` scope.
+pub fn enable_grad() -> GradModeGuard {
+  set_grad_enabled(true)
+}
+
+/// Enables or disables graph construction until the guard is dropped. Has no
+/// effect inside `inference_mode`.
+pub fn set_grad_enabled(enabled: bool) -> GradModeGuard {
+  let current = GRAD_MODE.with(|state| state.get());
+  enter(GradMode { enabled: enabled && !current.inference, ..current })
+}
+
+/// Like `no_grad`, but tensors created inside the scope never require grad,
+/// so `Tensor::new` doesn't allocate gradient buffers either.
+pub fn inference_mode() -> GradModeGuard {
+  enter(GradMode { enabled: false, inference: true })
+}
+
+/// Whether ops on the current thread record backward nodes.
+pub fn is_grad_enabled() -> bool {
+  GRAD_MODE.with(|state| state.get().enabled)
+}
+
+/// Whether the current thread is inside an `inference_mode` scope.
+pub fn is_inference_mode_enabled() -> bool {
+  GRAD_MODE.with(|state| state.get().inference)
+}
+
+/// Restores the previous grad mode when dropped.
+#[must_use = "the grad mode is restored as soon as the guard is dropped"]
+pub struct GradModeGuard {
+  previous: GradMode,
+}
+
+impl Drop for GradModeGuard {
+  fn drop(&mut self) {
+    GRAD_MODE.with(|state| state.set(self.previous));
+  }
+}
@@ -7,7 +7,7 @@
 #![allow(unused_variables)]
 #![allow(dead_code)]
 
-mod autograd;
+pub mod autograd;
 mod tensor;
 mod network;
 
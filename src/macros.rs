@@ -60,3 +60,49 @@ macro_rules! match_storage_assign {
 }
 
 
+/// Builds a `Vec<SliceArg>` for `slice`, using `;` for steps:
+/// `x.slice(&s![1..3, .., ..;2])` is `x[1:3, :, ::2]` and `s![-1]` picks the last row.
+#[macro_export]
+macro_rules! s {
+  (@parse [$($acc:expr),*]) => {
+    vec![$($acc),*]
+  };
+  (@parse [$($acc:expr),*] $r:expr ; $step:expr $(, $($rest:tt)*)?) => {
+    $crate::s!(@parse [$($acc,)* $crate::SliceArg::from($r).step_by($step)] $($($rest)*)?)
+  };
+  (@parse [$($acc:expr),*] $r:expr $(, $($rest:tt)*)?) => {
+    $crate::s!(@parse [$($acc,)* $crate::SliceArg::from($r)] $($($rest)*)?)
+  };
+  ($($t:tt)*) => {
+    $crate::s!(@parse [] $($t)*)
+  };
+}
+
+/// Runs `$body` with `$T` bound to the Rust element type of `$dtype`.
+#[macro_export]
+macro_rules! with_dtype {
+  ($dtype:expr, $T:ident => $body:expr) => {
+    match $dtype {
+      $crate::DType::Bool => { type $T = bool; $body }
+      $crate::DType::U8 => { type $T = u8; $body }
+      $crate::DType::I32 => { type $T = i32; $body }
+      $crate::DType::I64 => { type $T = i64; $body }
+      $crate::DType::F16 => { type $T = $crate::f16; $body }
+      $crate::DType::BF16 => { type $T = $crate::bf16; $body }
+      $crate::DType::F32 => { type $T = f32; $body }
+      $crate::DType::F64 => { type $T = f64; $body }
+    }
+  };
+}
+
+/// Runs `$body` with `$F` bound to `f64` for `F64` and to `f32` otherwise
+/// (half-precision values are computed in `f32`).
+#[macro_export]
+macro_rules! with_float {
+  ($dtype:expr, $F:ident => $body:expr) => {
+    match $dtype {
+      $crate::DType::F64 => { type $F = f64; $body }
+      _ => { type $F = f32; $body }
+    }
+  };
+}
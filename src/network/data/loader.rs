@@ -1,7 +1,6 @@
 use crate::tensor::*;
 use std::sync::{Arc, RwLock};
 use rand::seq::SliceRandom;
-use rand::thread_rng;
 
 // Enum to specify which dataset split to access
 pub enum Split {
@@ -20,10 +19,12 @@ pub struct DataLoader {
   train_size: usize,
   test_size: usize,
   validation_size: usize,
+  generator: Generator,
 }
 
 impl DataLoader {
   /// Creates a new DataLoader instance, computing split sizes based on ratios.
+  /// Shuffling draws from `generator`, or from the default generator when `None`.
   pub fn new(
     x_data: Arc<RwLock<Tensor>>,
     y_data: Arc<RwLock<Tensor>>,
@@ -31,6 +32,7 @@ impl DataLoader {
     train_split: f32,
     test_split: f32,
     validation_split: f32,
+    generator: Option<&Generator>,
   ) -> Self {
     // Ensure splits sum to 1.0 (with small tolerance for floating-point errors)
     let total_split = train_split + test_split + validation_split;
@@ -40,8 +42,12 @@ impl DataLoader {
     );
 
     // Get the total number of samples from x_data
-    let x_data_read = x_data.read().unwrap();
-    let n_samples = x_data_read.shape()[0];
+    let n_samples = x_data.read().unwrap().shape()[0];
+    assert_eq!(
+      n_samples,
+      y_data.read().unwrap().shape()[0],
+      "x_data and y_data must hold the same number of samples"
+    );
 
     // Compute sizes for each split
     let train_size = (n_samples as f32 * train_split).floor() as usize;
@@ -58,6 +64,7 @@ impl DataLoader {
       train_size,
       test_size,
       validation_size,
+      generator: generator_or_default(generator),
     }
   }
 
@@ -79,8 +86,7 @@ impl DataLoader {
 
     // Shuffle indices if requested
     if shuffle {
-      let mut rng = thread_rng();
-      indices.shuffle(&mut rng);
+      self.generator.with_rng(|rng| indices.shuffle(rng));
     }
 
     BatchIterator {
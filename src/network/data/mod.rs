@@ -1,3 +1,3 @@
-pub mod loader;
+mod loader;
 
-pub use loader;
\ No newline at end of file
+pub use loader::*;
@@ -0,0 +1,28 @@
+use super::init::*;
+use crate::tensor::*;
+
+/// Fills the tensor with a single value.
+pub struct Constant {
+  value: f32,
+}
+
+impl Constant {
+  pub fn new(value: f32) -> Self {
+    Self { value }
+  }
+
+  pub fn zeros() -> Self {
+    Self::new(0.)
+  }
+
+  pub fn ones() -> Self {
+    Self::new(1.)
+  }
+}
+
+impl InitializerTrait for Constant {
+  fn init(&self, tensor: &mut Tensor) {
+    let len = tensor.shape().iter().product();
+    fill(tensor, vec![self.value; len]);
+  }
+}
@@ -0,0 +1,91 @@
+use rand::distributions::{Distribution, Uniform};
+use rand_distr::Normal;
+
+use crate::tensor::*;
+
+/// Fills a tensor in place. Any `Fn(&mut Tensor)` closure is also an initializer,
+/// so layers can be built with a custom scheme. The random initializers take an
+/// optional `Generator` and draw from the default generator when it is `None`.
+pub trait InitializerTrait {
+  fn init(&self, tensor: &mut Tensor);
+}
+
+impl<F: Fn(&mut Tensor)> InitializerTrait for F {
+  fn init(&self, tensor: &mut Tensor) {
+    self(tensor)
+  }
+}
+
+/// Recommended gain for a nonlinearity, following the usual conventions
+/// (`param` is the negative slope for `leaky_relu`, default 0.01).
+pub fn calculate_gain(nonlinearity: &str, param: Option<f32>) -> f32 {
+  match nonlinearity {
+    "linear" | "identity" | "sigmoid" => 1.,
+    "tanh" => 5. / 3.,
+    "relu" => f32::sqrt(2.),
+    "leaky_relu" => {
+      let slope = param.unwrap_or(0.01);
+      f32::sqrt(2. / (1. + slope * slope))
+    },
+    "selu" => 3. / 4.,
+    _ => panic!("Unsupported nonlinearity: {}", nonlinearity),
+  }
+}
+
+/// Returns `(fan_in, fan_out)` for a weight of shape [out, in, *receptive_field].
+pub fn compute_fans(shape: &[usize]) -> (usize, usize) {
+  if shape.len() < 2 {
+    panic!("Fan in and fan out can not be computed for tensors with fewer than 2 dimensions");
+  }
+
+  let receptive_field: usize = shape[2..].iter().product();
+  (shape[1] * receptive_field, shape[0] * receptive_field)
+}
+
+/// Picks the fan selected by `mode` ("fan_in" or "fan_out").
+pub(crate) fn select_fan(shape: &[usize], mode: &str) -> usize {
+  let (fan_in, fan_out) = compute_fans(shape);
+  match mode {
+    "fan_in" => fan_in,
+    "fan_out" => fan_out,
+    _ => panic!("Mode must be either 'fan_in' or 'fan_out'"),
+  }
+}
+
+/// Draws `len` samples from U(low, high) using `generator`, or the default generator when `None`.
+pub(crate) fn uniform_values(len: usize, low: f32, high: f32, generator: Option<&Generator>) -> Vec<f32> {
+  let uniform = Uniform::new_inclusive(low, high);
+  generator_or_default(generator).with_rng(|rng| (0..len).map(|_| uniform.sample(rng)).collect())
+}
+
+/// Draws `len` samples from N(mean, std^2) using `generator`, or the default generator when `None`.
+pub(crate) fn normal_values(len: usize, mean: f32, std: f32, generator: Option<&Generator>) -> Vec<f32> {
+  let normal = Normal::new(mean, std).expect("Standard deviation must be finite and non-negative");
+  generator_or_default(generator).with_rng(|rng| (0..len).map(|_| normal.sample(rng)).collect())
+}
+
+/// Overwrites the elements of `tensor` with `values`, given in row-major order.
+pub(crate) fn fill(tensor: &mut Tensor, values: Vec<f32>) {
+  let storage = tensor.tensor_mut();
+  let shape = storage.shape().clone();
+  if values.len() != shape.iter().product::<usize>() {
+    panic!("Initializer produced {} values for a tensor of shape {:?}", values.len(), shape);
+  }
+
+  if storage.dtype() == DType::F32 && storage.is_contiguous() && storage.offset() == 0 && storage.data().read().unwrap().len() == values.len() {
+    *storage.data_mut() = values;
+    return;
+  }
+
+  let mut index = vec![0; shape.len()];
+  for value in values {
+    storage.set(&index, value);
+    for dim in (0..shape.len()).rev() {
+      index[dim] += 1;
+      if index[dim] < shape[dim] {
+        break;
+      }
+      index[dim] = 0;
+    }
+  }
+}
@@ -0,0 +1,61 @@
+use super::init::*;
+use crate::tensor::*;
+
+/// He initialization from U(-bound, bound) with `bound = gain * sqrt(3 / fan)`.
+/// `a` is the negative slope used by `leaky_relu`.
+pub struct KaimingUniform {
+  a: f32,
+  mode: String,
+  nonlinearity: String,
+  generator: Option<Generator>,
+}
+
+impl KaimingUniform {
+  pub fn new(a: f32, mode: &str, nonlinearity: &str, generator: Option<&Generator>) -> Self {
+    if mode != "fan_in" && mode != "fan_out" {
+      panic!("Mode must be either 'fan_in' or 'fan_out'");
+    }
+
+    Self { a, mode: mode.to_string(), nonlinearity: nonlinearity.to_string(), generator: generator.cloned() }
+  }
+}
+
+impl InitializerTrait for KaimingUniform {
+  fn init(&self, tensor: &mut Tensor) {
+    let fan = select_fan(tensor.shape(), &self.mode);
+    let std = calculate_gain(&self.nonlinearity, Some(self.a)) / f32::sqrt(fan as f32);
+    let bound = f32::sqrt(3.) * std;
+
+    let len = tensor.shape().iter().product();
+    fill(tensor, uniform_values(len, -bound, bound, self.generator.as_ref()));
+  }
+}
+
+
+/// He initialization from N(0, std^2) with `std = gain / sqrt(fan)`.
+pub struct KaimingNormal {
+  a: f32,
+  mode: String,
+  nonlinearity: String,
+  generator: Option<Generator>,
+}
+
+impl KaimingNormal {
+  pub fn new(a: f32, mode: &str, nonlinearity: &str, generator: Option<&Generator>) -> Self {
+    if mode != "fan_in" && mode != "fan_out" {
+      panic!("Mode must be either 'fan_in' or 'fan_out'");
+    }
+
+    Self { a, mode: mode.to_string(), nonlinearity: nonlinearity.to_string(), generator: generator.cloned() }
+  }
+}
+
+impl InitializerTrait for KaimingNormal {
+  fn init(&self, tensor: &mut Tensor) {
+    let fan = select_fan(tensor.shape(), &self.mode);
+    let std = calculate_gain(&self.nonlinearity, Some(self.a)) / f32::sqrt(fan as f32);
+
+    let len = tensor.shape().iter().product();
+    fill(tensor, normal_values(len, 0., std, self.generator.as_ref()));
+  }
+}
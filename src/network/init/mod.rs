@@ -0,0 +1,13 @@
+mod init;
+mod kaiming;
+mod xavier;
+mod orthogonal;
+mod normal;
+mod constant;
+
+pub use init::*;
+pub use kaiming::*;
+pub use xavier::*;
+pub use orthogonal::*;
+pub use normal::*;
+pub use constant::*;
@@ -0,0 +1,140 @@
+use super::init::*;
+use crate::tensor::*;
+
+/// Fills the tensor with values drawn from U(low, high).
+pub struct Uniform {
+  low: f32,
+  high: f32,
+  generator: Option<Generator>,
+}
+
+impl Uniform {
+  pub fn new(low: f32, high: f32, generator: Option<&Generator>) -> Self {
+    if low > high {
+      panic!("Uniform bounds must satisfy low <= high");
+    }
+
+    Self { low, high, generator: generator.cloned() }
+  }
+}
+
+impl InitializerTrait for Uniform {
+  fn init(&self, tensor: &mut Tensor) {
+    let len = tensor.shape().iter().product();
+    fill(tensor, uniform_values(len, self.low, self.high, self.generator.as_ref()));
+  }
+}
+
+
+/// Fills the tensor with values drawn from N(mean, std^2).
+pub struct Normal {
+  mean: f32,
+  std: f32,
+  generator: Option<Generator>,
+}
+
+impl Normal {
+  pub fn new(mean: f32, std: f32, generator: Option<&Generator>) -> Self {
+    Self { mean, std, generator: generator.cloned() }
+  }
+}
+
+impl InitializerTrait for Normal {
+  fn init(&self, tensor: &mut Tensor) {
+    let len = tensor.shape().iter().product();
+    fill(tensor, normal_values(len, self.mean, self.std, self.generator.as_ref()));
+  }
+}
+
+
+/// Fills the tensor with values drawn from N(mean, std^2) truncated to [a, b].
+/// Samples are drawn by inverting the CDF, so bounds far out in the tails cost
+/// the same as bounds around the mean.
+pub struct TruncatedNormal {
+  mean: f32,
+  std: f32,
+  a: f32,
+  b: f32,
+  generator: Option<Generator>,
+}
+
+impl TruncatedNormal {
+  pub fn new(mean: f32, std: f32, a: f32, b: f32, generator: Option<&Generator>) -> Self {
+    if a >= b {
+      panic!("Truncation bounds must satisfy a < b");
+    }
+    if !(std >= 0. && std.is_finite()) {
+      panic!("Standard deviation must be finite and non-negative");
+    }
+    if std == 0. && !(a..=b).contains(&mean) {
+      panic!("Mean must lie within [a, b] when the standard deviation is 0");
+    }
+
+    Self { mean, std, a, b, generator: generator.cloned() }
+  }
+}
+
+impl InitializerTrait for TruncatedNormal {
+  fn init(&self, tensor: &mut Tensor) {
+    let len = tensor.shape().iter().product();
+    if self.std == 0. {
+      fill(tensor, vec![self.mean; len]);
+      return;
+    }
+
+    let (mean, std) = (self.mean as f64, self.std as f64);
+    let alpha = (self.a as f64 - mean) / std;
+    let beta = (self.b as f64 - mean) / std;
+    // Work in whichever tail keeps the CDF values small, where they are precise
+    let flip = alpha > 0.;
+    let (low, high) = if flip { (-beta, -alpha) } else { (alpha, beta) };
+    let (cdf_low, cdf_high) = (normal_cdf(low), normal_cdf(high));
+
+    let values = uniform_values(len, 0., 1., self.generator.as_ref()).into_iter().map(|u| {
+      let p = cdf_low + u as f64 * (cdf_high - cdf_low);
+      let z = normal_quantile(p).clamp(low, high);
+      let z = if flip { -z } else { z };
+      ((mean + std * z) as f32).clamp(self.a, self.b)
+    }).collect();
+    fill(tensor, values);
+  }
+}
+
+
+/// Standard normal CDF, via a Chebyshev fit of erfc (relative error < 1.2e-7).
+fn normal_cdf(x: f64) -> f64 {
+  let z = x.abs() / std::f64::consts::SQRT_2;
+  let t = 1. / (1. + 0.5 * z);
+  let erfc = t * f64::exp(-z * z - 1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418
+    + t * (-0.18628806 + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587
+    + t * (-0.82215223 + t * 0.17087277)))))))));
+  if x >= 0. { 1. - 0.5 * erfc } else { 0.5 * erfc }
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation).
+fn normal_quantile(p: f64) -> f64 {
+  const A: [f64; 6] = [-3.969683028665376e1, 2.209460984245205e2, -2.759285104469687e2, 1.38357751867269e2, -3.066479806614716e1, 2.506628277459239];
+  const B: [f64; 5] = [-5.447609879822406e1, 1.615858368580409e2, -1.556989798598866e2, 6.680131188771972e1, -1.328068155288572e1];
+  const C: [f64; 6] = [-7.784894002430293e-3, -3.223964580411365e-1, -2.400758277161838, -2.549732539343734, 4.374664141464968, 2.938163982698783];
+  const D: [f64; 4] = [7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996, 3.754408661907416];
+
+  if p <= 0. {
+    return f64::NEG_INFINITY;
+  }
+  if p >= 1. {
+    return f64::INFINITY;
+  }
+
+  let tail = |q: f64| (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+    / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.);
+  if p < 0.02425 {
+    tail(f64::sqrt(-2. * p.ln()))
+  } else if p > 1. - 0.02425 {
+    -tail(f64::sqrt(-2. * (1. - p).ln()))
+  } else {
+    let q = p - 0.5;
+    let r = q * q;
+    (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+      / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+  }
+}
@@ -0,0 +1,65 @@
+use super::init::*;
+use crate::tensor::*;
+
+/// Fills the tensor, viewed as a [shape[0], rest] matrix, with a (semi-)orthogonal
+/// matrix scaled by `gain`. The rows are orthonormal when there are fewer rows than
+/// columns, the columns otherwise.
+pub struct Orthogonal {
+  gain: f32,
+  generator: Option<Generator>,
+}
+
+impl Orthogonal {
+  pub fn new(gain: f32, generator: Option<&Generator>) -> Self {
+    Self { gain, generator: generator.cloned() }
+  }
+}
+
+impl InitializerTrait for Orthogonal {
+  fn init(&self, tensor: &mut Tensor) {
+    if tensor.shape().len() < 2 {
+      panic!("Orthogonal initialization requires a tensor with at least 2 dimensions");
+    }
+
+    let rows = tensor.shape()[0];
+    let cols: usize = tensor.shape()[1..].iter().product();
+    let flat = normal_values(rows * cols, 0., 1., self.generator.as_ref());
+
+    // Orthonormalize the vectors along the longer side with modified Gram-Schmidt.
+    // Each vector is flipped so that the diagonal of the implied R factor is positive,
+    // which makes the result uniformly distributed.
+    let transposed = rows < cols;
+    let (len, count) = if transposed { (cols, rows) } else { (rows, cols) };
+    let mut vectors: Vec<Vec<f64>> = (0..count)
+      .map(|j| (0..len).map(|i| {
+        let value = if transposed { flat[j * cols + i] } else { flat[i * cols + j] };
+        value as f64
+      }).collect())
+      .collect();
+
+    for j in 0..count {
+      for k in 0..j {
+        let (done, rest) = vectors.split_at_mut(j);
+        let projection: f64 = done[k].iter().zip(rest[0].iter()).map(|(a, b)| a * b).sum();
+        for (v, q) in rest[0].iter_mut().zip(done[k].iter()) {
+          *v -= projection * q;
+        }
+      }
+
+      let norm = vectors[j].iter().map(|v| v * v).sum::<f64>().sqrt();
+      for v in vectors[j].iter_mut() {
+        *v /= norm;
+      }
+    }
+
+    let mut values = vec![0.0; rows * cols];
+    for (j, vector) in vectors.iter().enumerate() {
+      for (i, &v) in vector.iter().enumerate() {
+        let index = if transposed { j * cols + i } else { i * cols + j };
+        values[index] = self.gain * v as f32;
+      }
+    }
+
+    fill(tensor, values);
+  }
+}
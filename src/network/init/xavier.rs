@@ -0,0 +1,48 @@
+use super::init::*;
+use crate::tensor::*;
+
+/// Glorot initialization from U(-bound, bound) with
+/// `bound = gain * sqrt(6 / (fan_in + fan_out))`.
+pub struct XavierUniform {
+  gain: f32,
+  generator: Option<Generator>,
+}
+
+impl XavierUniform {
+  pub fn new(gain: f32, generator: Option<&Generator>) -> Self {
+    Self { gain, generator: generator.cloned() }
+  }
+}
+
+impl InitializerTrait for XavierUniform {
+  fn init(&self, tensor: &mut Tensor) {
+    let (fan_in, fan_out) = compute_fans(tensor.shape());
+    let bound = self.gain * f32::sqrt(6. / (fan_in + fan_out) as f32);
+
+    let len = tensor.shape().iter().product();
+    fill(tensor, uniform_values(len, -bound, bound, self.generator.as_ref()));
+  }
+}
+
+
+/// Glorot initialization from N(0, std^2) with `std = gain * sqrt(2 / (fan_in + fan_out))`.
+pub struct XavierNormal {
+  gain: f32,
+  generator: Option<Generator>,
+}
+
+impl XavierNormal {
+  pub fn new(gain: f32, generator: Option<&Generator>) -> Self {
+    Self { gain, generator: generator.cloned() }
+  }
+}
+
+impl InitializerTrait for XavierNormal {
+  fn init(&self, tensor: &mut Tensor) {
+    let (fan_in, fan_out) = compute_fans(tensor.shape());
+    let std = self.gain * f32::sqrt(2. / (fan_in + fan_out) as f32);
+
+    let len = tensor.shape().iter().product();
+    fill(tensor, normal_values(len, 0., std, self.generator.as_ref()));
+  }
+}
@@ -1,19 +1,156 @@
+use std::rc::Rc;
+use num_traits::Float;
+
 use super::loss::*;
 use crate::tensor::*;
+use crate::with_float;
+use crate::autograd::CrossEntropyGrad;
 
 pub struct CrossEntropyLoss {
-  
+  is_mean_reduction: bool,
+  weight: Option<Tensor>,
+  ignore_index: Option<i64>,
+  label_smoothing: f32,
 }
 
 impl CrossEntropyLoss {
-  pub fn new() -> Self {
-    CrossEntropyLoss { }
+  /// `weight` rescales the loss of each class, `ignore_index` drops samples whose
+  /// class index matches it (it may be negative, e.g. -100), and `label_smoothing`
+  /// mixes the target with a uniform distribution over the classes.
+  pub fn new(reduction: &str, weight: Option<Tensor>, ignore_index: Option<i64>, label_smoothing: f32) -> Self {
+    let is_mean_reduction = match reduction {
+      "mean" => true,
+      "sum" => false,
+      _ => panic!("Reduction must be either 'mean' or 'sum'"),
+    };
+
+    if !(0.0..=1.0).contains(&label_smoothing) {
+      panic!("Label smoothing must be in [0, 1]");
+    }
+
+    Self{ is_mean_reduction, weight, ignore_index, label_smoothing }
+  }
+
+  /// Builds the [N, C] target distribution, scaled by the class weights and the
+  /// reduction factor (1 / total weight for a mean, 1 for a sum).
+  fn target_distribution(&self, y: &Tensor, rows: usize, classes: usize, probabilities: bool) -> Vec<f64> {
+    let weights: Vec<f64> = match &self.weight {
+      Some(weight) => weight.tensor().to_vec(),
+      None => vec![1.; classes],
+    };
+    let label_smoothing = self.label_smoothing as f64;
+    let smoothing = label_smoothing / classes as f64;
+    let mut target = vec![0.0; rows * classes];
+
+    if probabilities {
+      let y_data = y.tensor().to_vec::<f64>();
+      for (i, value) in target.iter_mut().enumerate() {
+        let c = i % classes;
+        *value = weights[c] * ((1. - label_smoothing) * y_data[i] + smoothing);
+      }
+      return self.reduce(target, rows as f64);
+    }
+
+    let mut total_weight = 0.0;
+    let labels = y.tensor().to_vec::<f64>();
+    for (i, &label) in labels.iter().enumerate() {
+      if self.ignore_index.is_some_and(|ignore| label == ignore as f64) {
+        continue;
+      }
+      if label < 0. || label.fract() != 0. || label >= classes as f64 {
+        panic!("{}", FerriteError::invalid("cross_entropy", format!("target {} is not a class index in [0, {})", label, classes)));
+      }
+      let class = label as usize;
+      total_weight += weights[class];
+
+      for c in 0..classes {
+        target[i * classes + c] = weights[c] * smoothing;
+      }
+      target[i * classes + class] += weights[class] * (1. - label_smoothing);
+    }
+
+    self.reduce(target, total_weight)
+  }
+
+  fn reduce(&self, target: Vec<f64>, total_weight: f64) -> Vec<f64> {
+    if !self.is_mean_reduction {
+      return target;
+    }
+    target.into_iter().map(|t| t / total_weight).collect()
   }
 }
 
+/// Fused log-softmax over rows of `classes` logits: log p_ic = x_ic - logsumexp(x_i).
+/// Returns the loss `-sum t_ic * log p_ic` and the softmax.
+fn fused_log_softmax<F: Float>(logits: &[F], target: &[f64], classes: usize) -> (F, Vec<F>) {
+  let mut softmax = vec![F::zero(); logits.len()];
+  let mut loss = F::zero();
+  for (i, row) in logits.chunks(classes).enumerate() {
+    let max = row.iter().cloned().fold(F::neg_infinity(), F::max);
+    let log_sum_exp = max + row.iter().map(|&v| (v - max).exp()).fold(F::zero(), |a, b| a + b).ln();
+
+    for c in 0..classes {
+      let log_p = row[c] - log_sum_exp;
+      softmax[i * classes + c] = log_p.exp();
+
+      let t = target[i * classes + c];
+      if t != 0. {
+        loss = loss - F::from(t).unwrap() * log_p;
+      }
+    }
+  }
+
+  (loss, softmax)
+}
+
 impl LossTrait for CrossEntropyLoss {
+  /// `x` holds raw logits of shape [N, C] (or [C] for a single sample). `y` is
+  /// either a tensor of N class indices or a tensor of target probabilities with
+  /// the same shape as `x`.
   fn loss(&self, x: &Tensor, y: &Tensor) -> Tensor {
-    unimplemented!()
+    if let Some(x) = autocast_full(x) {
+      return self.loss(&x, y);
+    }
+
+    let (rows, classes) = match x.shape().len() {
+      1 => (1, x.shape()[0]),
+      2 => (x.shape()[0], x.shape()[1]),
+      _ => panic!("CrossEntropyLoss expects logits of shape [N, C] or [C]"),
+    };
+
+    if let Some(weight) = &self.weight {
+      if weight.shape() != &vec![classes] {
+        panic!("Class weights must have shape [{}]", classes);
+      }
+    }
 
+    let probabilities = y.shape() == x.shape();
+    if !probabilities && y.shape().iter().product::<usize>() != rows {
+      panic!("Target must hold either {} class indices or probabilities of shape {:?}", rows, x.shape());
+    }
+
+    let target = self.target_distribution(y, rows, classes, probabilities);
+
+    // Evaluated in the compute dtype of the logits so f64 inputs keep their precision
+    let dtype = x.dtype().to_float();
+    let device = x.device();
+    let (loss, softmax) = with_float!(dtype.compute_dtype(), F => {
+      let (loss, softmax) = fused_log_softmax(&x.tensor().to_vec::<F>(), &target, classes);
+      (Storage::from_vec(vec![loss], vec![1], Some(device)), Storage::from_vec(softmax, vec![rows, classes], Some(device)))
+    });
+
+    let requires_grad = x.tracks_grad();
+    let mut result = Tensor::new(loss.to_dtype(dtype), device, requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(CrossEntropyGrad::new(
+        x,
+        &result,
+        softmax,
+        Storage::from_vec(target, vec![rows, classes], Some(device)).to_dtype(dtype.compute_dtype())
+      ))));
+    }
+
+    result
   }
-}
\ No newline at end of file
+}
@@ -19,6 +19,10 @@ impl MSELoss {
 
 impl LossTrait for MSELoss {
   fn loss(&self, x: &Tensor, y: &Tensor) -> Tensor {
+    if let Some(x) = autocast_full(x) {
+      return self.loss(&x, y);
+    }
+
     let z_1 = x.sub_tensor(y);
     let z_2 = z_1.pow_f32(2.); 
 
@@ -49,6 +53,10 @@ impl MAELoss {
 
 impl LossTrait for MAELoss {
   fn loss(&self, x: &Tensor, y: &Tensor) -> Tensor {
+    if let Some(x) = autocast_full(x) {
+      return self.loss(&x, y);
+    }
+
     let z_1 = x.sub_tensor(y);
     let z_2 = z_1.abs(); 
 
@@ -1,9 +1,17 @@
 pub mod module;
 pub mod loss;
 pub mod optimizer;
+pub mod scheduler;
+pub mod init;
+pub mod data;
 
 pub use module as Layer;
 pub use loss::LossTrait;
 pub use loss as Loss;
 pub use optimizer::OptimizerTrait;
-pub use optimizer as Optimizer;
\ No newline at end of file
+pub use optimizer as Optimizer;
+pub use scheduler::SchedulerTrait;
+pub use scheduler as Scheduler;
+pub use init::InitializerTrait;
+pub use init as Init;
+pub use data as Data;
\ No newline at end of file
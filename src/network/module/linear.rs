@@ -3,6 +3,8 @@ use std::sync::{Arc, RwLock};
 
 use super::module::*;
 use crate::tensor::*;
+use crate::no_grad;
+use crate::network::init::*;
 
 // Linear layer implementation
 pub struct Linear {
@@ -13,20 +15,43 @@ pub struct Linear {
 
 
 impl Linear {
-  pub fn new(in_features: usize, out_features: usize, bias: bool, device: Device) -> Self {
+  /// Weights and bias are drawn from U(-1/sqrt(in_features), 1/sqrt(in_features)),
+  /// i.e. Kaiming uniform with `a = sqrt(5)`. Both draw from `generator`, or from
+  /// the default generator when `None`.
+  pub fn new(in_features: usize, out_features: usize, bias: bool, device: Device, generator: Option<&Generator>) -> Self {
     let bound = f32::sqrt(1./in_features as f32);
-    let weight = Arc::new(RwLock::new(
-      //Tensor::uniform(-bound, bound, vec![out_features, in_features], Some(true))
-      Tensor::ones(vec![out_features, in_features], device, Some(true))
-    ));
+    Linear::with_init(
+      in_features,
+      out_features,
+      bias,
+      device,
+      &KaimingUniform::new(f32::sqrt(5.), "fan_in", "leaky_relu", generator),
+      &Uniform::new(-bound, bound, generator),
+    )
+  }
+
+  /// Builds the layer with custom initializers for the [out, in] weight and the [out] bias.
+  pub fn with_init(
+    in_features: usize,
+    out_features: usize,
+    bias: bool,
+    device: Device,
+    weight_init: &dyn InitializerTrait,
+    bias_init: &dyn InitializerTrait,
+  ) -> Self {
+    let mut weight = Tensor::zeros(vec![out_features, in_features], device, Some(true));
+    weight_init.init(&mut weight);
+    let weight = Arc::new(RwLock::new(weight));
 
     let bias = if bias {
-      Some(Arc::new(RwLock::new(Tensor::zeros(vec![out_features], device, Some(false)))))
+      let mut bias = Tensor::zeros(vec![out_features], device, Some(true));
+      bias_init.init(&mut bias);
+      Some(Arc::new(RwLock::new(bias)))
     } else {
       None
     };
 
-    Linear{weight, bias, training: false,}
+    Linear{weight, bias, training: true,}
   }
 
   fn visit_parameters(&self, f: &mut dyn FnMut(&str, &Tensor)) {
@@ -46,6 +71,9 @@ impl Linear {
 
 impl Module for Linear {
   fn forward(&mut self, input: &Tensor) -> Tensor {
+    // Evaluation doesn't build a graph
+    let _no_grad = (!self.training).then(no_grad);
+
     // Get weight parameter and access its tensor
     let weight = self.weight.read().unwrap();
 
@@ -55,7 +83,10 @@ impl Module for Linear {
     // Add bias if present
     if let Some(bias) = &self.bias {
       let bias = bias.read().unwrap();
-      output = &output + &*bias;
+      output = match autocast_lower(&bias) {
+        Some(bias) => &output + &bias,
+        None => &output + &*bias,
+      };
     } 
     output
   }
@@ -77,8 +108,11 @@ impl Module for Linear {
     self.training = false;
   }
 
-  fn zero_grad(&mut self) {
-    todo!("Implement zero_grad for Linear");
+  fn zero_grad(&mut self, set_to_none: bool) {
+    self.weight.read().unwrap().zero_grad(set_to_none);
+    if let Some(bias) = &self.bias {
+      bias.read().unwrap().zero_grad(set_to_none);
+    }
   }
 
 }
\ No newline at end of file
@@ -12,9 +12,18 @@ pub trait Module {
     HashMap::new()
   }
   
+  /// Modules start in training mode. After `eval()`, `forward` runs under
+  /// `no_grad` and builds no graph.
   fn train(&mut self) { }
   fn eval(&mut self) { }
-  fn zero_grad(&mut self) { }
+
+  /// Clears the gradients of every parameter. With `set_to_none` the gradient
+  /// buffers are released instead of zero-filled.
+  fn zero_grad(&mut self, set_to_none: bool) {
+    for param in self.parameters().values() {
+      param.read().unwrap().zero_grad(set_to_none);
+    }
+  }
 
   /// Visit all parameters with a callback function
   fn visit_parameters(&self, f: &mut dyn FnMut(&str, &Tensor)) {
@@ -3,6 +3,7 @@ use std::sync::{Arc, RwLock};
 
 use super::module::*;
 use crate::tensor::*;
+use crate::no_grad;
 
 
 pub struct Sequential {
@@ -14,7 +15,7 @@ impl Sequential {
   pub fn new(layers: Vec<Box<dyn Module>>) -> Self {
     Self {
       layers,
-      training: false,
+      training: true,
     }
   }
 
@@ -36,6 +37,7 @@ impl Sequential {
 
 impl Module for Sequential {
   fn forward(&mut self, input: &Tensor) -> Tensor {
+    let _no_grad = (!self.training).then(no_grad);
     let mut current = input.clone();
     for layer in self.layers.iter_mut() {
       current = layer.forward(&current);
@@ -68,9 +70,9 @@ impl Module for Sequential {
     }
   }
 
-  fn zero_grad(&mut self) {
+  fn zero_grad(&mut self, set_to_none: bool) {
     for layer in &mut self.layers {
-      layer.zero_grad();
+      layer.zero_grad(set_to_none);
     }
   }
 }
\ No newline at end of file
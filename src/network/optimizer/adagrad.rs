@@ -0,0 +1,102 @@
+use super::{group::*, optimizer::*};
+use crate::tensor::*;
+use std::{collections::HashMap, sync::{Arc, RwLock}};
+
+struct AdagradState {
+  step: i32,
+  sum: Storage,
+}
+
+pub struct Adagrad {
+  param_groups: Vec<ParamGroup>,
+  lr: f32,
+  lr_decay: f32,
+  weight_decay: f32,
+  initial_accumulator_value: f32,
+  eps: f32,
+  state: HashMap<String, AdagradState>,
+}
+
+impl Adagrad {
+  /// Adagrad scales each parameter's step by the root of its accumulated squared
+  /// gradients, which starts at `initial_accumulator_value`.
+  pub fn new(
+    model_params: HashMap<String, Arc<RwLock<Tensor>>>,
+    lr: f32,
+    lr_decay: f32,
+    weight_decay: f32,
+    initial_accumulator_value: f32,
+    eps: f32,
+  ) -> Self {
+    Self::from_groups(vec![ParamGroup::new(model_params)], lr, lr_decay, weight_decay, initial_accumulator_value, eps)
+  }
+
+  /// Same as `new`, with `lr` and `weight_decay` acting as defaults for groups that
+  /// don't set their own.
+  pub fn from_groups(
+    mut param_groups: Vec<ParamGroup>,
+    lr: f32,
+    lr_decay: f32,
+    weight_decay: f32,
+    initial_accumulator_value: f32,
+    eps: f32,
+  ) -> Self {
+    check_param_groups(&param_groups);
+    for group in param_groups.iter_mut() {
+      group.fill_defaults(lr, 0., weight_decay);
+    }
+
+    Self {
+      param_groups,
+      lr,
+      lr_decay,
+      weight_decay,
+      initial_accumulator_value,
+      eps,
+      state: HashMap::new(),
+    }
+  }
+}
+
+impl OptimizerTrait for Adagrad {
+  fn step(&mut self) {
+    for group in self.param_groups.iter() {
+      let lr = group.lr.unwrap_or(self.lr);
+      let weight_decay = group.weight_decay.unwrap_or(self.weight_decay);
+
+      for (key, value) in group.params.iter() {
+        let mut tensor = value.write().unwrap();
+
+        let mut grad = match tensor.grad().and_then(|grad| grad.borrow().clone()) {
+          Some(grad) => grad,
+          None => continue,
+        };
+
+        if weight_decay != 0. {
+          grad = &grad + &(tensor.tensor() * weight_decay);
+        }
+
+        let initial_accumulator_value = self.initial_accumulator_value;
+        let state = self.state.entry(key.clone()).or_insert_with(|| AdagradState {
+          step: 0,
+          sum: Storage::full_like(tensor.tensor(), initial_accumulator_value),
+        });
+        state.step += 1;
+
+        let clr = lr / (1. + (state.step - 1) as f32 * self.lr_decay);
+        state.sum = &state.sum + &grad.pow_f32(2.);
+
+        let std = &state.sum.pow_f32(0.5) + self.eps;
+        tensor.tensor_mut().sub_tensor_assign(&(&(&grad / &std) * clr));
+      }
+    }
+  }
+
+  fn param_groups(&self) -> &[ParamGroup] {
+    &self.param_groups
+  }
+
+  fn param_groups_mut(&mut self) -> &mut [ParamGroup] {
+    &mut self.param_groups
+  }
+}
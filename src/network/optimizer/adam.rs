@@ -0,0 +1,184 @@
+use super::{group::*, optimizer::*};
+use crate::tensor::*;
+use std::{collections::HashMap, sync::{Arc, RwLock}};
+
+struct AdamState {
+  step: i32,
+  exp_avg: Storage,
+  exp_avg_sq: Storage,
+  max_exp_avg_sq: Option<Storage>,
+}
+
+/// Elementwise maximum, computed with a mask so f64 moments stay f64.
+fn maximum(a: &Storage, b: &Storage) -> Storage {
+  let mask = a.greater_than(b, true);
+  &(a * &mask) - &(b * &mask.sub_f32(1.))
+}
+
+pub struct Adam {
+  param_groups: Vec<ParamGroup>,
+  lr: f32,
+  betas: (f32, f32),
+  eps: f32,
+  weight_decay: f32,
+  amsgrad: bool,
+  state: HashMap<String, AdamState>,
+}
+
+impl Adam {
+  /// Adam with bias-corrected moment estimates. `weight_decay` is applied as an L2
+  /// penalty on the gradient; use `AdamW` for decoupled weight decay.
+  pub fn new(
+    model_params: HashMap<String, Arc<RwLock<Tensor>>>,
+    lr: f32,
+    betas: (f32, f32),
+    eps: f32,
+    weight_decay: f32,
+    amsgrad: bool,
+  ) -> Self {
+    Self::from_groups(vec![ParamGroup::new(model_params)], lr, betas, eps, weight_decay, amsgrad)
+  }
+
+  /// Same as `new`, with `lr` and `weight_decay` acting as defaults for groups that
+  /// don't set their own.
+  pub fn from_groups(
+    mut param_groups: Vec<ParamGroup>,
+    lr: f32,
+    betas: (f32, f32),
+    eps: f32,
+    weight_decay: f32,
+    amsgrad: bool,
+  ) -> Self {
+    check_param_groups(&param_groups);
+    for group in param_groups.iter_mut() {
+      group.fill_defaults(lr, 0., weight_decay);
+    }
+
+    Self {
+      param_groups,
+      lr,
+      betas,
+      eps,
+      weight_decay,
+      amsgrad,
+      state: HashMap::new(),
+    }
+  }
+
+  fn update(&mut self, decoupled_weight_decay: bool) {
+    let (beta1, beta2) = self.betas;
+
+    for group in self.param_groups.iter() {
+      let lr = group.lr.unwrap_or(self.lr);
+      let weight_decay = group.weight_decay.unwrap_or(self.weight_decay);
+
+      for (key, value) in group.params.iter() {
+        let mut tensor = value.write().unwrap();
+
+        let mut grad = match tensor.grad().and_then(|grad| grad.borrow().clone()) {
+          Some(grad) => grad,
+          None => continue,
+        };
+
+        if weight_decay != 0. {
+          if decoupled_weight_decay {
+            tensor.tensor_mut().mul_f32_assign(1. - lr * weight_decay);
+          } else {
+            grad = &grad + &(tensor.tensor() * weight_decay);
+          }
+        }
+
+        let state = self.state.entry(key.clone()).or_insert_with(|| AdamState {
+          step: 0,
+          exp_avg: Storage::zeros_like(tensor.tensor()),
+          exp_avg_sq: Storage::zeros_like(tensor.tensor()),
+          max_exp_avg_sq: None,
+        });
+        state.step += 1;
+
+        state.exp_avg = &(&state.exp_avg * beta1) + &(&grad * (1. - beta1));
+        state.exp_avg_sq = &(&state.exp_avg_sq * beta2) + &(&grad.pow_f32(2.) * (1. - beta2));
+
+        let second_moment = if self.amsgrad {
+          let max_exp_avg_sq = match &state.max_exp_avg_sq {
+            Some(max) => maximum(max, &state.exp_avg_sq),
+            None => state.exp_avg_sq.clone(),
+          };
+          state.max_exp_avg_sq = Some(max_exp_avg_sq.clone());
+          max_exp_avg_sq
+        } else {
+          state.exp_avg_sq.clone()
+        };
+
+        let bias_correction1 = 1. - beta1.powi(state.step);
+        let bias_correction2 = 1. - beta2.powi(state.step);
+
+        let denom = &(&second_moment / bias_correction2).pow_f32(0.5) + self.eps;
+        let update = &(&state.exp_avg / &denom) * (lr / bias_correction1);
+
+        tensor.tensor_mut().sub_tensor_assign(&update);
+      }
+    }
+  }
+}
+
+impl OptimizerTrait for Adam {
+  fn step(&mut self) {
+    self.update(false);
+  }
+
+  fn param_groups(&self) -> &[ParamGroup] {
+    &self.param_groups
+  }
+
+  fn param_groups_mut(&mut self) -> &mut [ParamGroup] {
+    &mut self.param_groups
+  }
+}
+
+
+pub struct AdamW {
+  adam: Adam,
+}
+
+impl AdamW {
+  /// Adam with decoupled weight decay: parameters are shrunk by `lr * weight_decay`
+  /// directly instead of adding an L2 term to the gradient.
+  pub fn new(
+    model_params: HashMap<String, Arc<RwLock<Tensor>>>,
+    lr: f32,
+    betas: (f32, f32),
+    eps: f32,
+    weight_decay: f32,
+    amsgrad: bool,
+  ) -> Self {
+    Self::from_groups(vec![ParamGroup::new(model_params)], lr, betas, eps, weight_decay, amsgrad)
+  }
+
+  pub fn from_groups(
+    param_groups: Vec<ParamGroup>,
+    lr: f32,
+    betas: (f32, f32),
+    eps: f32,
+    weight_decay: f32,
+    amsgrad: bool,
+  ) -> Self {
+    Self {
+      adam: Adam::from_groups(param_groups, lr, betas, eps, weight_decay, amsgrad),
+    }
+  }
+}
+
+impl OptimizerTrait for AdamW {
+  fn step(&mut self) {
+    self.adam.update(true);
+  }
+
+  fn param_groups(&self) -> &[ParamGroup] {
+    self.adam.param_groups()
+  }
+
+  fn param_groups_mut(&mut self) -> &mut [ParamGroup] {
+    self.adam.param_groups_mut()
+  }
+}
@@ -1,30 +1,106 @@
-use super::optimizer::*;
+use super::{group::*, optimizer::*};
 use crate::tensor::*;
 use std::{collections::HashMap, sync::{Arc, RwLock}};
 
 pub struct SGD {
-  model_params: HashMap<String, Arc<RwLock<Tensor>>>,
+  param_groups: Vec<ParamGroup>,
   lr: f32,
-  momentum: f32
+  momentum: f32,
+  dampening: f32,
+  weight_decay: f32,
+  nesterov: bool,
+  velocity: HashMap<String, Storage>,
 }
 
 impl SGD {
-  pub fn new(model_params: HashMap<String, Arc<RwLock<Tensor>>>, lr: f32, momentum: f32) -> Self {
-    Self{ model_params, lr, momentum }
+  /// Stochastic gradient descent with optional (Nesterov) momentum and L2 weight decay.
+  /// Velocity buffers are kept per parameter, keyed by the names from `Module::parameters()`.
+  pub fn new(
+    model_params: HashMap<String, Arc<RwLock<Tensor>>>,
+    lr: f32,
+    momentum: f32,
+    dampening: f32,
+    weight_decay: f32,
+    nesterov: bool,
+  ) -> Self {
+    Self::from_groups(vec![ParamGroup::new(model_params)], lr, momentum, dampening, weight_decay, nesterov)
+  }
+
+  /// Same as `new`, with `lr`, `momentum` and `weight_decay` acting as defaults for
+  /// groups that don't set their own.
+  pub fn from_groups(
+    mut param_groups: Vec<ParamGroup>,
+    lr: f32,
+    momentum: f32,
+    dampening: f32,
+    weight_decay: f32,
+    nesterov: bool,
+  ) -> Self {
+    check_param_groups(&param_groups);
+    for group in param_groups.iter_mut() {
+      group.fill_defaults(lr, momentum, weight_decay);
+
+      if nesterov && (group.momentum.unwrap() <= 0. || dampening != 0.) {
+        panic!("Nesterov momentum requires a momentum and zero dampening");
+      }
+    }
+
+    Self {
+      param_groups,
+      lr,
+      momentum,
+      dampening,
+      weight_decay,
+      nesterov,
+      velocity: HashMap::new(),
+    }
   }
 }
 
 impl OptimizerTrait for SGD {
-  fn step(&self) {
-    for (key, value) in self.model_params.iter() {
-      let mut tensor = value.write().unwrap();
+  fn step(&mut self) {
+    for group in self.param_groups.iter() {
+      let lr = group.lr.unwrap_or(self.lr);
+      let momentum = group.momentum.unwrap_or(self.momentum);
+      let weight_decay = group.weight_decay.unwrap_or(self.weight_decay);
+
+      for (key, value) in group.params.iter() {
+        let mut tensor = value.write().unwrap();
+
+        let mut d_p = match tensor.grad().and_then(|grad| grad.borrow().clone()) {
+          Some(grad) => grad,
+          None => continue,
+        };
 
-      let mut temp = tensor.grad().unwrap();
-      let grad = temp.borrow();
+        if weight_decay != 0. {
+          d_p = &d_p + &(tensor.tensor() * weight_decay);
+        }
 
-      let mut storage = tensor.tensor_mut();
-      
-      storage.sub_tensor_assign(&(&*grad * self.lr));
+        if momentum != 0. {
+          let buf = match self.velocity.remove(key) {
+            Some(buf) => &(&buf * momentum) + &(&d_p * (1. - self.dampening)),
+            None => d_p.clone(),
+          };
+
+          d_p = if self.nesterov {
+            &d_p + &(&buf * momentum)
+          } else {
+            buf.clone()
+          };
+
+          self.velocity.insert(key.clone(), buf);
+        }
+
+        tensor.tensor_mut().sub_tensor_assign(&(&d_p * lr));
+      }
     }
   }
-}
\ No newline at end of file
+
+  fn param_groups(&self) -> &[ParamGroup] {
+    &self.param_groups
+  }
+
+  fn param_groups_mut(&mut self) -> &mut [ParamGroup] {
+    &mut self.param_groups
+  }
+}
@@ -0,0 +1,94 @@
+use crate::tensor::*;
+use std::{collections::{HashMap, HashSet}, sync::{Arc, RwLock}};
+
+/// A subset of a model's parameters with its own hyperparameters. Options left as
+/// `None` inherit the optimizer's defaults when the optimizer is constructed.
+/// `momentum` is ignored by optimizers without a momentum term.
+pub struct ParamGroup {
+  pub params: HashMap<String, Arc<RwLock<Tensor>>>,
+  pub lr: Option<f32>,
+  pub momentum: Option<f32>,
+  pub weight_decay: Option<f32>,
+}
+
+impl ParamGroup {
+  pub fn new(params: HashMap<String, Arc<RwLock<Tensor>>>) -> Self {
+    Self {
+      params,
+      lr: None,
+      momentum: None,
+      weight_decay: None,
+    }
+  }
+
+  /// Parameters whose name satisfies `predicate`
+  pub fn filter<F>(params: &HashMap<String, Arc<RwLock<Tensor>>>, predicate: F) -> Self
+  where
+    F: Fn(&str) -> bool,
+  {
+    let selected = params.iter()
+      .filter(|(name, _)| predicate(name))
+      .map(|(name, param)| (name.clone(), param.clone()))
+      .collect();
+
+    Self::new(selected)
+  }
+
+  /// Parameters whose name matches a glob `pattern`, where `*` matches any
+  /// sequence of characters (e.g. `layer_0.*` or `*.bias`).
+  pub fn select(params: &HashMap<String, Arc<RwLock<Tensor>>>, pattern: &str) -> Self {
+    Self::filter(params, |name| glob_match(pattern, name))
+  }
+
+  /// Parameters listed explicitly by name
+  pub fn from_names(params: &HashMap<String, Arc<RwLock<Tensor>>>, names: &[&str]) -> Self {
+    for name in names {
+      if !params.contains_key(*name) {
+        panic!("Unknown parameter '{}'", name);
+      }
+    }
+
+    Self::filter(params, |name| names.contains(&name))
+  }
+
+  pub(crate) fn fill_defaults(&mut self, lr: f32, momentum: f32, weight_decay: f32) {
+    self.lr.get_or_insert(lr);
+    self.momentum.get_or_insert(momentum);
+    self.weight_decay.get_or_insert(weight_decay);
+  }
+}
+
+/// Optimizer state is keyed by parameter name, so a parameter may only belong to one group.
+pub(crate) fn check_param_groups(groups: &[ParamGroup]) {
+  let mut seen = HashSet::new();
+  for group in groups {
+    for name in group.params.keys() {
+      if !seen.insert(name) {
+        panic!("Parameter '{}' appears in more than one parameter group", name);
+      }
+    }
+  }
+}
+
+fn glob_match(pattern: &str, name: &str) -> bool {
+  let parts: Vec<&str> = pattern.split('*').collect();
+  if parts.len() == 1 {
+    return pattern == name;
+  }
+
+  let first = parts[0];
+  let last = parts[parts.len() - 1];
+  if name.len() < first.len() + last.len() || !name.starts_with(first) || !name.ends_with(last) {
+    return false;
+  }
+
+  // Match the middle pieces greedily from left to right
+  let mut rest = &name[first.len()..name.len() - last.len()];
+  for part in &parts[1..parts.len() - 1] {
+    match rest.find(part) {
+      Some(idx) => rest = &rest[idx + part.len()..],
+      None => return false,
+    }
+  }
+  true
+}
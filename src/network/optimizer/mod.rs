@@ -1,5 +1,15 @@
 mod optimizer;
+mod group;
 mod gd;
+mod adam;
+mod rmsprop;
+mod adagrad;
+mod scaler;
 
 pub use optimizer::*;
+pub use group::*;
 pub use gd::*;
+pub use adam::*;
+pub use rmsprop::*;
+pub use adagrad::*;
+pub use scaler::*;
@@ -1,6 +1,32 @@
-use crate::tensor::*;
+use super::group::*;
 
 pub trait OptimizerTrait {
-  fn step(&self);
+  fn step(&mut self);
+
+  fn param_groups(&self) -> &[ParamGroup];
+
+  fn param_groups_mut(&mut self) -> &mut [ParamGroup];
+
+  /// Clears the gradients of every parameter owned by the optimizer. With
+  /// `set_to_none` the gradient buffers are released instead of zero-filled.
+  fn zero_grad(&mut self, set_to_none: bool) {
+    for group in self.param_groups() {
+      for param in group.params.values() {
+        param.read().unwrap().zero_grad(set_to_none);
+      }
+    }
+  }
+
+  /// Learning rate of the first parameter group
+  fn lr(&self) -> f32 {
+    self.param_groups()[0].lr.expect("Parameter group has no learning rate")
+  }
+
+  /// Overrides the learning rate of every parameter group
+  fn set_lr(&mut self, lr: f32) {
+    for group in self.param_groups_mut() {
+      group.lr = Some(lr);
+    }
+  }
 }
 
@@ -0,0 +1,128 @@
+use super::{group::*, optimizer::*};
+use crate::tensor::*;
+use std::{collections::HashMap, sync::{Arc, RwLock}};
+
+struct RMSpropState {
+  square_avg: Storage,
+  grad_avg: Option<Storage>,
+  momentum_buffer: Option<Storage>,
+}
+
+pub struct RMSprop {
+  param_groups: Vec<ParamGroup>,
+  lr: f32,
+  alpha: f32,
+  eps: f32,
+  weight_decay: f32,
+  momentum: f32,
+  centered: bool,
+  state: HashMap<String, RMSpropState>,
+}
+
+impl RMSprop {
+  /// RMSprop with a running average of squared gradients controlled by `alpha`.
+  /// When `centered` is set the gradient is normalized by its estimated variance.
+  pub fn new(
+    model_params: HashMap<String, Arc<RwLock<Tensor>>>,
+    lr: f32,
+    alpha: f32,
+    eps: f32,
+    weight_decay: f32,
+    momentum: f32,
+    centered: bool,
+  ) -> Self {
+    Self::from_groups(vec![ParamGroup::new(model_params)], lr, alpha, eps, weight_decay, momentum, centered)
+  }
+
+  /// Same as `new`, with `lr`, `weight_decay` and `momentum` acting as defaults for
+  /// groups that don't set their own.
+  pub fn from_groups(
+    mut param_groups: Vec<ParamGroup>,
+    lr: f32,
+    alpha: f32,
+    eps: f32,
+    weight_decay: f32,
+    momentum: f32,
+    centered: bool,
+  ) -> Self {
+    check_param_groups(&param_groups);
+    for group in param_groups.iter_mut() {
+      group.fill_defaults(lr, momentum, weight_decay);
+    }
+
+    Self {
+      param_groups,
+      lr,
+      alpha,
+      eps,
+      weight_decay,
+      momentum,
+      centered,
+      state: HashMap::new(),
+    }
+  }
+}
+
+impl OptimizerTrait for RMSprop {
+  fn step(&mut self) {
+    for group in self.param_groups.iter() {
+      let lr = group.lr.unwrap_or(self.lr);
+      let momentum = group.momentum.unwrap_or(self.momentum);
+      let weight_decay = group.weight_decay.unwrap_or(self.weight_decay);
+
+      for (key, value) in group.params.iter() {
+        let mut tensor = value.write().unwrap();
+
+        let mut grad = match tensor.grad().and_then(|grad| grad.borrow().clone()) {
+          Some(grad) => grad,
+          None => continue,
+        };
+
+        if weight_decay != 0. {
+          grad = &grad + &(tensor.tensor() * weight_decay);
+        }
+
+        let state = self.state.entry(key.clone()).or_insert_with(|| RMSpropState {
+          square_avg: Storage::zeros_like(tensor.tensor()),
+          grad_avg: None,
+          momentum_buffer: None,
+        });
+
+        state.square_avg = &(&state.square_avg * self.alpha) + &(&grad.pow_f32(2.) * (1. - self.alpha));
+
+        let avg = if self.centered {
+          let grad_avg = match &state.grad_avg {
+            Some(grad_avg) => &(grad_avg * self.alpha) + &(&grad * (1. - self.alpha)),
+            None => &grad * (1. - self.alpha),
+          };
+          let variance = &state.square_avg - &grad_avg.pow_f32(2.);
+          state.grad_avg = Some(grad_avg);
+          &variance.pow_f32(0.5) + self.eps
+        } else {
+          &state.square_avg.pow_f32(0.5) + self.eps
+        };
+
+        let update = if momentum > 0. {
+          let buf = match &state.momentum_buffer {
+            Some(buf) => &(buf * momentum) + &(&grad / &avg),
+            None => &grad / &avg,
+          };
+          state.momentum_buffer = Some(buf.clone());
+          buf
+        } else {
+          &grad / &avg
+        };
+
+        tensor.tensor_mut().sub_tensor_assign(&(&update * lr));
+      }
+    }
+  }
+
+  fn param_groups(&self) -> &[ParamGroup] {
+    &self.param_groups
+  }
+
+  fn param_groups_mut(&mut self) -> &mut [ParamGroup] {
+    &mut self.param_groups
+  }
+}
@@ -0,0 +1,118 @@
+use super::optimizer::*;
+use crate::tensor::*;
+
+
+/// Dynamic loss scaling for mixed-precision training. The loss is multiplied by
+/// a large factor before `backward` so small half-precision gradients don't
+/// flush to zero; `step` unscales the gradients and skips the update when any
+/// of them overflowed.
+///
+/// ```ignore
+/// scaler.scale(&loss).backward();
+/// scaler.step(&mut optimizer);
+/// scaler.update();
+/// ```
+pub struct GradScaler {
+  scale: f32,
+  growth_factor: f32,
+  backoff_factor: f32,
+  growth_interval: usize,
+  growth_tracker: usize,
+  found_inf: Option<bool>,
+}
+
+impl GradScaler {
+  /// Starts at a scale of 2^16, doubling it after 2000 finite steps in a row
+  /// and halving it on every overflow.
+  pub fn new() -> Self {
+    Self::with_options(65536., 2., 0.5, 2000)
+  }
+
+  pub fn with_options(init_scale: f32, growth_factor: f32, backoff_factor: f32, growth_interval: usize) -> Self {
+    if init_scale <= 0. || growth_factor <= 1. || backoff_factor <= 0. || backoff_factor >= 1. || growth_interval == 0 {
+      panic!("{}", FerriteError::invalid("GradScaler", "expected init_scale > 0, growth_factor > 1, 0 < backoff_factor < 1 and growth_interval > 0"));
+    }
+
+    Self {
+      scale: init_scale,
+      growth_factor,
+      backoff_factor,
+      growth_interval,
+      growth_tracker: 0,
+      found_inf: None,
+    }
+  }
+
+  /// Current scale factor
+  pub fn get_scale(&self) -> f32 {
+    self.scale
+  }
+
+  /// `loss` multiplied by the current scale factor
+  pub fn scale(&self, loss: &Tensor) -> Tensor {
+    loss.mul_f32(self.scale)
+  }
+
+  /// Divides the gradients of every parameter in `optimizer` by the scale
+  /// factor, e.g. before gradient clipping. Only the first call per iteration
+  /// has an effect.
+  pub fn unscale<O: OptimizerTrait + ?Sized>(&mut self, optimizer: &O) {
+    if self.found_inf.is_some() {
+      return;
+    }
+
+    let mut found_inf = false;
+    for group in optimizer.param_groups() {
+      for param in group.params.values() {
+        let grad = match param.read().unwrap().grad() {
+          Some(grad) => grad,
+          None => continue,
+        };
+
+        let mut grad = grad.borrow_mut();
+        if let Some(grad) = grad.as_mut() {
+          grad.div_f32_assign(self.scale);
+          found_inf |= grad.to_vec::<f32>().iter().any(|value| !value.is_finite());
+        }
+      }
+    }
+    self.found_inf = Some(found_inf);
+  }
+
+  /// Unscales the gradients if needed and steps `optimizer` unless one of them
+  /// is infinite or NaN. Returns whether the step was taken.
+  pub fn step<O: OptimizerTrait + ?Sized>(&mut self, optimizer: &mut O) -> bool {
+    self.unscale(optimizer);
+    if self.found_inf == Some(true) {
+      return false;
+    }
+
+    optimizer.step();
+    true
+  }
+
+  /// Adjusts the scale factor for the next iteration: it backs off after an
+  /// overflow and grows after `growth_interval` finite steps.
+  pub fn update(&mut self) {
+    match self.found_inf.take() {
+      Some(true) => {
+        self.scale *= self.backoff_factor;
+        self.growth_tracker = 0;
+      }
+      Some(false) => {
+        self.growth_tracker += 1;
+        if self.growth_tracker == self.growth_interval {
+          self.scale *= self.growth_factor;
+          self.growth_tracker = 0;
+        }
+      }
+      None => {}
+    }
+  }
+}
+
+impl Default for GradScaler {
+  fn default() -> Self {
+    Self::new()
+  }
+}
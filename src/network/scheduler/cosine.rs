@@ -0,0 +1,63 @@
+use std::f32::consts::PI;
+
+use super::scheduler::*;
+
+fn cosine_anneal(base_lr: f32, eta_min: f32, t_cur: f32, t_max: f32) -> f32 {
+  eta_min + (base_lr - eta_min) * (1. + f32::cos(PI * t_cur / t_max)) / 2.
+}
+
+/// Anneals the learning rate from its base value down to `eta_min` along a
+/// half cosine over `t_max` epochs.
+pub struct CosineAnnealingLR {
+  t_max: usize,
+  eta_min: f32,
+}
+
+impl CosineAnnealingLR {
+  pub fn new(t_max: usize, eta_min: f32) -> Self {
+    if t_max == 0 {
+      panic!("T_max must be positive");
+    }
+
+    Self { t_max, eta_min }
+  }
+}
+
+impl SchedulerTrait for CosineAnnealingLR {
+  fn lr_at(&self, base_lr: f32, epoch: usize) -> f32 {
+    cosine_anneal(base_lr, self.eta_min, epoch as f32, self.t_max as f32)
+  }
+}
+
+
+/// Cosine annealing with warm restarts (SGDR). The first cycle lasts `t_0` epochs
+/// and every following cycle is `t_mult` times longer than the previous one.
+pub struct CosineAnnealingWarmRestarts {
+  t_0: usize,
+  t_mult: usize,
+  eta_min: f32,
+}
+
+impl CosineAnnealingWarmRestarts {
+  pub fn new(t_0: usize, t_mult: usize, eta_min: f32) -> Self {
+    if t_0 == 0 || t_mult == 0 {
+      panic!("T_0 and T_mult must be positive");
+    }
+
+    Self { t_0, t_mult, eta_min }
+  }
+}
+
+impl SchedulerTrait for CosineAnnealingWarmRestarts {
+  fn lr_at(&self, base_lr: f32, epoch: usize) -> f32 {
+    // Walk through the cycles until we find the one containing `epoch`
+    let mut t_cur = epoch;
+    let mut t_i = self.t_0;
+    while t_cur >= t_i {
+      t_cur -= t_i;
+      t_i *= self.t_mult;
+    }
+
+    cosine_anneal(base_lr, self.eta_min, t_cur as f32, t_i as f32)
+  }
+}
@@ -0,0 +1,85 @@
+use std::f32::consts::PI;
+
+use super::scheduler::*;
+
+/// Ramps the learning rate linearly from `start_factor * base_lr` up to `base_lr`
+/// over `warmup_epochs`, then hands over to `after` (or stays constant).
+pub struct LinearWarmup {
+  warmup_epochs: usize,
+  start_factor: f32,
+  after: Option<Box<dyn SchedulerTrait>>,
+}
+
+impl LinearWarmup {
+  pub fn new(warmup_epochs: usize, start_factor: f32, after: Option<Box<dyn SchedulerTrait>>) -> Self {
+    Self { warmup_epochs, start_factor, after }
+  }
+}
+
+impl SchedulerTrait for LinearWarmup {
+  fn lr_at(&self, base_lr: f32, epoch: usize) -> f32 {
+    if epoch < self.warmup_epochs {
+      let progress = epoch as f32 / self.warmup_epochs as f32;
+      return base_lr * (self.start_factor + (1. - self.start_factor) * progress);
+    }
+
+    match &self.after {
+      Some(after) => after.lr_at(base_lr, epoch - self.warmup_epochs),
+      None => base_lr,
+    }
+  }
+}
+
+
+/// The 1cycle policy. The optimizer's learning rate is the peak: the schedule
+/// starts at `base_lr / div_factor`, warms up to `base_lr` during the first
+/// `pct_start` of `total_steps`, then anneals to `base_lr / (div_factor * final_div_factor)`.
+pub struct OneCycleLR {
+  total_steps: usize,
+  pct_start: f32,
+  div_factor: f32,
+  final_div_factor: f32,
+  cosine: bool,
+}
+
+impl OneCycleLR {
+  /// `anneal_strategy` is either "cos" or "linear".
+  pub fn new(total_steps: usize, pct_start: f32, div_factor: f32, final_div_factor: f32, anneal_strategy: &str) -> Self {
+    let cosine = match anneal_strategy {
+      "cos" => true,
+      "linear" => false,
+      _ => panic!("Anneal strategy must be either 'cos' or 'linear'"),
+    };
+
+    if total_steps < 2 || !(0.0..1.0).contains(&pct_start) {
+      panic!("OneCycleLR needs at least 2 steps and pct_start in [0, 1)");
+    }
+
+    Self { total_steps, pct_start, div_factor, final_div_factor, cosine }
+  }
+
+  fn anneal(&self, start: f32, end: f32, pct: f32) -> f32 {
+    if self.cosine {
+      end + (start - end) / 2. * (f32::cos(PI * pct) + 1.)
+    } else {
+      start + (end - start) * pct
+    }
+  }
+}
+
+impl SchedulerTrait for OneCycleLR {
+  fn lr_at(&self, base_lr: f32, epoch: usize) -> f32 {
+    let initial_lr = base_lr / self.div_factor;
+    let min_lr = initial_lr / self.final_div_factor;
+
+    let step = epoch.min(self.total_steps - 1) as f32;
+    let warmup_end = (self.pct_start * self.total_steps as f32 - 1.).max(0.);
+    let last_step = (self.total_steps - 1) as f32;
+
+    if step <= warmup_end && warmup_end > 0. {
+      self.anneal(initial_lr, base_lr, step / warmup_end)
+    } else {
+      self.anneal(base_lr, min_lr, (step - warmup_end) / (last_step - warmup_end))
+    }
+  }
+}
@@ -0,0 +1,11 @@
+mod scheduler;
+mod step;
+mod cosine;
+mod cycle;
+mod plateau;
+
+pub use scheduler::*;
+pub use step::*;
+pub use cosine::*;
+pub use cycle::*;
+pub use plateau::*;
@@ -0,0 +1,76 @@
+use crate::network::optimizer::OptimizerTrait;
+
+/// Reduces the learning rate by `factor` once a monitored metric has stopped
+/// improving for more than `patience` epochs. Every parameter group is scaled.
+pub struct ReduceLROnPlateau {
+  is_min_mode: bool,
+  factor: f32,
+  patience: usize,
+  threshold: f32,
+  cooldown: usize,
+  min_lr: f32,
+  best: f32,
+  num_bad_epochs: usize,
+  cooldown_counter: usize,
+}
+
+impl ReduceLROnPlateau {
+  /// `mode` is "min" when lower metric values are better (e.g. a loss) and "max"
+  /// otherwise. `threshold` is the relative improvement needed to count as better.
+  pub fn new(mode: &str, factor: f32, patience: usize, threshold: f32, cooldown: usize, min_lr: f32) -> Self {
+    let is_min_mode = match mode {
+      "min" => true,
+      "max" => false,
+      _ => panic!("Mode must be either 'min' or 'max'"),
+    };
+
+    if factor >= 1. {
+      panic!("Factor must be smaller than 1");
+    }
+
+    Self {
+      is_min_mode,
+      factor,
+      patience,
+      threshold,
+      cooldown,
+      min_lr,
+      best: if is_min_mode { f32::INFINITY } else { f32::NEG_INFINITY },
+      num_bad_epochs: 0,
+      cooldown_counter: 0,
+    }
+  }
+
+  fn is_better(&self, metric: f32) -> bool {
+    if self.is_min_mode {
+      metric < self.best * (1. - self.threshold)
+    } else {
+      metric > self.best * (1. + self.threshold)
+    }
+  }
+
+  pub fn step(&mut self, optimizer: &mut dyn OptimizerTrait, metric: f32) {
+    if self.is_better(metric) {
+      self.best = metric;
+      self.num_bad_epochs = 0;
+    } else {
+      self.num_bad_epochs += 1;
+    }
+
+    if self.cooldown_counter > 0 {
+      self.cooldown_counter -= 1;
+      self.num_bad_epochs = 0;
+    }
+
+    if self.num_bad_epochs > self.patience {
+      for group in optimizer.param_groups_mut() {
+        if let Some(lr) = group.lr {
+          group.lr = Some(f32::max(lr * self.factor, self.min_lr));
+        }
+      }
+
+      self.cooldown_counter = self.cooldown;
+      self.num_bad_epochs = 0;
+    }
+  }
+}
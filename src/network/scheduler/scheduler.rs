@@ -0,0 +1,55 @@
+use crate::network::optimizer::OptimizerTrait;
+
+/// A learning-rate schedule expressed in closed form: the learning rate for a given
+/// epoch as a function of the optimizer's initial learning rate.
+pub trait SchedulerTrait {
+  fn lr_at(&self, base_lr: f32, epoch: usize) -> f32;
+}
+
+/// Drives any `OptimizerTrait` implementation with a `SchedulerTrait` schedule.
+/// Call `step` once per epoch (or per batch for schedules such as `OneCycleLR`).
+pub struct LRScheduler {
+  schedule: Box<dyn SchedulerTrait>,
+  base_lrs: Vec<f32>,
+  last_epoch: usize,
+}
+
+impl LRScheduler {
+  /// Captures the current learning rate of every parameter group as its base and
+  /// applies the schedule's value for epoch 0.
+  pub fn new<S: SchedulerTrait + 'static>(optimizer: &mut dyn OptimizerTrait, schedule: S) -> Self {
+    let base_lrs = optimizer.param_groups().iter()
+      .map(|group| group.lr.expect("Parameter group has no learning rate"))
+      .collect();
+
+    let scheduler = Self {
+      schedule: Box::new(schedule),
+      base_lrs,
+      last_epoch: 0,
+    };
+    scheduler.apply(optimizer);
+    scheduler
+  }
+
+  pub fn step(&mut self, optimizer: &mut dyn OptimizerTrait) {
+    self.last_epoch += 1;
+    self.apply(optimizer);
+  }
+
+  fn apply(&self, optimizer: &mut dyn OptimizerTrait) {
+    for (group, lr) in optimizer.param_groups_mut().iter_mut().zip(self.last_lr()) {
+      group.lr = Some(lr);
+    }
+  }
+
+  pub fn last_epoch(&self) -> usize {
+    self.last_epoch
+  }
+
+  /// Learning rate of each parameter group for the current epoch
+  pub fn last_lr(&self) -> Vec<f32> {
+    self.base_lrs.iter()
+      .map(|&base_lr| self.schedule.lr_at(base_lr, self.last_epoch))
+      .collect()
+  }
+}
@@ -0,0 +1,61 @@
+use super::scheduler::*;
+
+/// Decays the learning rate by `gamma` every `step_size` epochs.
+pub struct StepLR {
+  step_size: usize,
+  gamma: f32,
+}
+
+impl StepLR {
+  pub fn new(step_size: usize, gamma: f32) -> Self {
+    if step_size == 0 {
+      panic!("Step size must be positive");
+    }
+
+    Self { step_size, gamma }
+  }
+}
+
+impl SchedulerTrait for StepLR {
+  fn lr_at(&self, base_lr: f32, epoch: usize) -> f32 {
+    base_lr * self.gamma.powi((epoch / self.step_size) as i32)
+  }
+}
+
+
+/// Decays the learning rate by `gamma` once each milestone epoch is reached.
+pub struct MultiStepLR {
+  milestones: Vec<usize>,
+  gamma: f32,
+}
+
+impl MultiStepLR {
+  pub fn new(milestones: Vec<usize>, gamma: f32) -> Self {
+    Self { milestones, gamma }
+  }
+}
+
+impl SchedulerTrait for MultiStepLR {
+  fn lr_at(&self, base_lr: f32, epoch: usize) -> f32 {
+    let passed = self.milestones.iter().filter(|&&milestone| milestone <= epoch).count();
+    base_lr * self.gamma.powi(passed as i32)
+  }
+}
+
+
+/// Decays the learning rate by `gamma` every epoch.
+pub struct ExponentialLR {
+  gamma: f32,
+}
+
+impl ExponentialLR {
+  pub fn new(gamma: f32) -> Self {
+    Self { gamma }
+  }
+}
+
+impl SchedulerTrait for ExponentialLR {
+  fn lr_at(&self, base_lr: f32, epoch: usize) -> f32 {
+    base_lr * self.gamma.powi(epoch as i32)
+  }
+}
@@ -0,0 +1,55 @@
+use std::cell::Cell;
+use crate::*;
+
+
+thread_local! {
+  static AUTOCAST: Cell<Option<DType>> = const { Cell::new(None) };
+}
+
+/// Enables mixed precision on the current thread until the returned guard is
+/// dropped. Inside the scope `matmul` (and so `Linear`) casts its float inputs
+/// to `dtype`, while `softmax`, `logsumexp` and the loss functions run in `f32`.
+/// Casts are recorded by autograd, so gradients reach `f32` parameters in `f32`.
+///
+/// ```ignore
+/// let _autocast = autocast(DType::BF16);
+/// let loss = criterion.loss(&model.forward(&x), &y);
+/// ```
+pub fn autocast(dtype: DType) -> AutocastGuard {
+  if !dtype.is_half() {
+    panic!("{}", FerriteError::invalid("autocast", format!("expected f16 or bf16, got {}", dtype)));
+  }
+  AutocastGuard { previous: AUTOCAST.with(|state| state.replace(Some(dtype))) }
+}
+
+/// The reduced-precision dtype of the innermost active autocast scope.
+pub fn autocast_dtype() -> Option<DType> {
+  AUTOCAST.with(|state| state.get())
+}
+
+/// Restores the previous autocast state when dropped.
+#[must_use = "autocast is disabled again as soon as the guard is dropped"]
+pub struct AutocastGuard {
+  previous: Option<DType>,
+}
+
+impl Drop for AutocastGuard {
+  fn drop(&mut self) {
+    AUTOCAST.with(|state| state.set(self.previous));
+  }
+}
+
+/// `tensor` cast to the autocast dtype, for ops that run in reduced precision.
+/// `None` when autocast is off or there is nothing to cast.
+pub(crate) fn autocast_lower(tensor: &Tensor) -> Option<Tensor> {
+  let dtype = autocast_dtype()?;
+  let current = tensor.dtype();
+  (current.is_floating_point() && current != dtype).then(|| tensor.to_dtype(dtype))
+}
+
+/// `tensor` cast to `f32`, for ops that need its range or precision. `None` when
+/// autocast is off or the tensor isn't half precision.
+pub(crate) fn autocast_full(tensor: &Tensor) -> Option<Tensor> {
+  autocast_dtype()?;
+  tensor.dtype().is_half().then(|| tensor.to_dtype(DType::F32))
+}
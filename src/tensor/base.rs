@@ -1,25 +1,33 @@
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use super::storage::*;
-use crate::{grad_storage, GradientFunction, CpuStorage};
-use std::collections::HashSet;
+use crate::{check_backward, grad_storage, is_grad_enabled, is_inference_mode_enabled, run_backward, CastGrad, DType, FerriteError, GradientFunction, GraphNode, ViewGrad, CpuStorage};
 
 
-pub type GradientStorage = Rc<RefCell<Storage>>;
+/// Shared gradient slot. `None` means the gradient was released by `zero_grad(true)`
+/// and will be materialized again by the next backward pass.
+pub type GradientStorage = Rc<RefCell<Option<Storage>>>;
 
 #[derive(Clone)]
 pub struct Tensor {
   pub storage: Storage,
   device: Device,
   requires_grad: bool,
-  grad_fn: Option<Rc<dyn GradientFunction>>,
+  node: Option<Rc<GraphNode>>,
   grad: Option<GradientStorage>,
+  // Shared by every clone, so `retain_grad` also reaches the copies held by the graph
+  retains_grad: Rc<Cell<bool>>,
+  // Recorded copy of `.grad` left by a `create_graph` backward, shared by every clone
+  graph_grad: Rc<RefCell<Option<Tensor>>>,
 }
 
 impl Tensor {
+  /// Inside `inference_mode` the tensor never requires grad, whatever
+  /// `requires_grad` says, and no gradient buffer is allocated.
   pub fn new(storage: Storage, device: Device, requires_grad: bool) -> Self {
+    let requires_grad = requires_grad && !is_inference_mode_enabled();
     let grad = if requires_grad {
-      Some(Rc::new(RefCell::new(Storage::zeros(storage.shape().clone(), Some(device), None))))
+      Some(Rc::new(RefCell::new(Some(Storage::zeros_like(&storage)))))
     } else {
       None
     };
@@ -28,19 +36,81 @@ impl Tensor {
       storage: storage,
       device: device,
       requires_grad: requires_grad,
-      grad_fn: None,
+      node: None,
       grad: grad,
+      retains_grad: Rc::new(Cell::new(false)),
+      graph_grad: Rc::new(RefCell::new(None)),
     }
   }
 
-  pub fn view(&self, tensor: Storage) -> Self {
-    Tensor {
-      storage: tensor,
-      device: self.device,
-      requires_grad: self.requires_grad,
-      grad_fn: self.grad_fn.clone(),
-      grad: self.grad.clone(),
+  /// The same elements reinterpreted with `new_shape` (row-major). Shares the
+  /// data when the tensor is contiguous; gradients are reshaped back.
+  pub fn view(&self, new_shape: Vec<usize>) -> Self {
+    let tensor = self.tensor().view(new_shape);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(ViewGrad::new(self, &result))));
+    }
+    result
+  }
+
+  /// A tensor sharing this tensor's data but cut out of the graph: it has no
+  /// `grad_fn`, doesn't require grad and is a leaf.
+  pub fn detach(&self) -> Self {
+    Tensor::new(self.tensor().clone(), self.device(), false)
+  }
+
+  /// Detaches this tensor from the graph in place, dropping its gradient.
+  pub fn detach_(&mut self) -> &mut Self {
+    self.node = None;
+    self.requires_grad = false;
+    self.grad = None;
+    self.retains_grad = Rc::new(Cell::new(false));
+    self.graph_grad = Rc::new(RefCell::new(None));
+    self
+  }
+
+  /// Turns gradient tracking on or off for a leaf tensor. Turning it on
+  /// allocates a zeroed gradient; turning it off drops it. Only float tensors
+  /// can require grad, and the flag of a non-leaf is fixed by its inputs.
+  pub fn requires_grad_(&mut self, requires_grad: bool) -> &mut Self {
+    if !self.is_leaf() {
+      panic!("{}", FerriteError::invalid("requires_grad_", "can only change the flag of leaf tensors; use detach() instead"));
+    }
+    if requires_grad && !self.dtype().is_floating_point() {
+      panic!("{}", FerriteError::invalid("requires_grad_", format!("only float tensors can require grad, got {}", self.dtype())));
+    }
+
+    if requires_grad && self.grad.is_none() {
+      self.grad = Some(Rc::new(RefCell::new(Some(Storage::zeros_like(self.tensor())))));
+    } else if !requires_grad {
+      self.grad = None;
+      self.graph_grad.borrow_mut().take();
     }
+    self.requires_grad = requires_grad;
+    self
+  }
+
+  /// Leaves are tensors created by the user (or that don't require grad)
+  /// rather than by a recorded op. After `backward()` only leaves that require
+  /// grad, and non-leaves marked with `retain_grad()`, keep a `.grad`.
+  pub fn is_leaf(&self) -> bool {
+    !self.requires_grad || self.node.is_none()
+  }
+
+  /// Keeps the gradient of this non-leaf tensor after `backward()`. Leaves
+  /// that require grad always keep theirs.
+  pub fn retain_grad(&self) {
+    if !self.requires_grad {
+      panic!("{}", FerriteError::GradNotEnabled { op: "retain_grad" });
+    }
+    self.retains_grad.set(true);
+  }
+
+  /// Whether `.grad` survives `backward()` for this tensor.
+  pub fn retains_grad(&self) -> bool {
+    self.requires_grad && (self.node.is_none() || self.retains_grad.get())
   }
 
   pub fn tensor(&self) -> &Storage {
@@ -55,16 +125,47 @@ impl Tensor {
     self.device
   }
 
+  pub fn dtype(&self) -> DType {
+    self.tensor().dtype()
+  }
+
+  /// Copy of the tensor converted to `dtype`. Gradients flow back through casts
+  /// between float dtypes; integer and bool results never require grad.
+  pub fn to_dtype(&self, dtype: DType) -> Self {
+    if dtype == self.dtype() {
+      return self.clone();
+    }
+    let tensor = self.tensor().to_dtype(dtype);
+    let requires_grad = self.tracks_grad() && dtype.is_floating_point();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(CastGrad::new(self, &result))));
+    }
+    result
+  }
+
   pub fn requires_grad(&self) -> &bool {
     &self.requires_grad
   }
 
+  /// Whether ops on this tensor record a backward node: it requires grad and
+  /// graph construction isn't disabled by `no_grad` or `inference_mode`.
+  pub(crate) fn tracks_grad(&self) -> bool {
+    self.requires_grad && is_grad_enabled()
+  }
+
+  /// Backward function of the op that produced this tensor. `None` for leaves
+  /// and once the graph has been freed by `backward`.
   pub fn grad_fn(&self) -> Option<Rc<dyn GradientFunction>> {
-    self.grad_fn.clone()
+    self.node.as_ref().and_then(|node| node.grad_fn())
   }
 
   pub fn set_grad_fn(&mut self, grad_fn: Option<Rc<dyn GradientFunction>>) {
-    self.grad_fn = grad_fn;
+    self.node = grad_fn.map(|grad_fn| Rc::new(GraphNode::new(grad_fn)));
+  }
+
+  pub(crate) fn graph_node(&self) -> Option<&Rc<GraphNode>> {
+    self.node.as_ref()
   }
 
   pub fn grad(&self) -> Option<GradientStorage> {
@@ -75,49 +176,63 @@ impl Tensor {
     self.grad.clone().expect("Grad can't be empty")
   }
 
-  pub fn shape(&self) -> &Vec<usize> {
-    &self.tensor().shape()
+  /// `.grad` as a tensor. After a `create_graph` backward it is recorded, so
+  /// it can be differentiated again; otherwise it is a constant.
+  pub fn grad_tensor(&self) -> Option<Tensor> {
+    if let Some(grad) = self.graph_grad.borrow().clone() {
+      return Some(grad);
+    }
+    let grad = self.grad.as_ref()?.borrow().clone()?;
+    Some(Tensor::new(grad, self.device(), false))
   }
 
-  pub fn backward(&mut self) {
-    // Verify we're starting with a scalar
-    if self.tensor().shape().len() != 1 || self.tensor().shape()[0] != 1 {
-      panic!("backward() can only be called on scalar tensors");
-    }
+  /// Adds a recorded gradient to `.grad`, keeping the recorded sum for `grad_tensor`.
+  pub(crate) fn accumulate_graph_grad(&self, grad: &Tensor) {
+    let Some(slot) = &self.grad else { return };
+    let mut graph_grad = self.graph_grad.borrow_mut();
+    let total = match (graph_grad.take(), slot.borrow().clone()) {
+      (Some(existing), _) => &existing + grad,
+      (None, Some(existing)) => &Tensor::new(existing, self.device(), false) + grad,
+      (None, None) => grad.clone(),
+    };
+    *slot.borrow_mut() = Some(total.tensor().clone());
+    *graph_grad = Some(total);
+  }
 
-    // Initialize gradient for final output (always 1.0 for scalar outputs)
+  /// Clears the accumulated gradient, either by zero-filling it or, when
+  /// `set_to_none` is set, by releasing the buffer entirely.
+  pub fn zero_grad(&self, set_to_none: bool) {
+    self.graph_grad.borrow_mut().take();
     if let Some(grad) = &self.grad {
-      grad.borrow_mut().set_data(vec![1.0]);
-    } else {
-      panic!("Called backward on tensor that doesn't require grad");
-    }
-
-    // Build computation graph in topological order
-    let mut topo = Vec::new();
-    let mut visited = HashSet::new();
-
-    fn build_topo(
-      node: &Tensor, 
-      topo: &mut Vec<Rc<dyn GradientFunction>>, 
-      visited: &mut HashSet<*const dyn GradientFunction>
-    ) {
-      if let Some(grad_fn) = &node.grad_fn {
-        let ptr = Rc::as_ptr(grad_fn) as *const dyn GradientFunction;
-        if !visited.contains(&ptr) {
-          visited.insert(ptr);
-          for parent in grad_fn.prev() {
-            build_topo(parent, topo, visited);
-          }
-          topo.push(grad_fn.clone());
-        }
+      let mut grad = grad.borrow_mut();
+      if set_to_none {
+        *grad = None;
+      } else if grad.is_some() {
+        *grad = Some(Storage::zeros_like(self.tensor()));
       }
     }
+  }
 
-    build_topo(self, &mut topo, &mut visited);
+  pub fn shape(&self) -> &Vec<usize> {
+    &self.tensor().shape()
+  }
 
-    // Execute backward passes in reverse order
-    for grad_fn in topo.iter().rev() {
-      grad_fn.backward();
+  /// Backpropagates from this single-element tensor and frees the graph.
+  pub fn backward(&self) {
+    self.backward_with(None, false, false);
+  }
+
+  /// Backpropagates `grad_output` (ones when `None`, which needs a
+  /// single-element tensor) into the `.grad` of every leaf. With
+  /// `retain_graph` the graph is kept, so it can be backpropagated again.
+  /// With `create_graph` the pass is recorded as well (and the graph kept):
+  /// `grad_tensor()` then returns gradients that can be differentiated again.
+  /// They reference the graph, so `zero_grad(true)` is what releases it.
+  pub fn backward_with(&self, grad_output: Option<&Tensor>, retain_graph: bool, create_graph: bool) {
+    let result = check_backward(self, grad_output)
+      .and_then(|seed| run_backward(&[self], &[seed], retain_graph, create_graph));
+    if let Err(error) = result {
+      panic!("{}", error);
     }
   }
-}
\ No newline at end of file
+}
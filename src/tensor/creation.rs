@@ -17,6 +17,13 @@ impl Tensor {
     Tensor::new(tensor, device, requires_grad)
   }
 
+  /// Creates a tensor holding `data` with its native dtype, e.g. `i64` class labels.
+  pub fn from_vec<T: Element>(data: Vec<T>, shape: Vec<usize>, device: Device, requires_grad: Option<bool>) -> Self {
+    let tensor = Storage::from_vec(data, shape, Some(device));
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
   pub fn from_ndarray<S, D, T>(data: &ndarray::ArrayBase<S, D>, device: Device, requires_grad: Option<bool>) -> Self
   where 
     S: ndarray::Data<Elem = T>,
@@ -28,9 +35,108 @@ impl Tensor {
     Tensor::new(tensor, device, requires_grad)
   }
 
-  pub fn uniform(l_bound: f32, r_bound: f32, shape: Vec<usize>, device: Device, requires_grad: Option<bool>) -> Self {
-    let tensor = Storage::uniform(l_bound, r_bound, shape, Some(device), None);
+  /// Samples from U(l_bound, r_bound) using `generator`, or the default generator
+  /// (see `manual_seed`) when `None`.
+  pub fn uniform(l_bound: f32, r_bound: f32, shape: Vec<usize>, device: Device, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let tensor = Storage::uniform(l_bound, r_bound, shape, Some(device), None, generator);
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
+  pub fn full(shape: Vec<usize>, value: f32, device: Device, requires_grad: Option<bool>) -> Self {
+    let tensor = Storage::full(shape, value, Some(device), None);
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
+  /// Values from `start` (inclusive) to `end` (exclusive), `step` apart.
+  pub fn arange(start: f32, end: f32, step: f32, device: Device, requires_grad: Option<bool>) -> Self {
+    let tensor = Storage::arange(start, end, step, Some(device), None);
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
+  /// `steps` evenly spaced values from `start` to `end`, both inclusive.
+  pub fn linspace(start: f32, end: f32, steps: usize, device: Device, requires_grad: Option<bool>) -> Self {
+    let tensor = Storage::linspace(start, end, steps, Some(device), None);
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
+  pub fn eye(n: usize, device: Device, requires_grad: Option<bool>) -> Self {
+    let tensor = Storage::eye(n, Some(device), None);
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
+  pub fn normal(mean: f32, std: f32, shape: Vec<usize>, device: Device, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let tensor = Storage::normal(mean, std, shape, Some(device), None, generator);
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
+  /// Samples from the standard normal distribution.
+  pub fn randn(shape: Vec<usize>, device: Device, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let tensor = Storage::randn(shape, Some(device), None, generator);
     let requires_grad = requires_grad.unwrap_or(false);
     Tensor::new(tensor, device, requires_grad)
   }
-}
\ No newline at end of file
+
+  /// Ones with probability `p`, zeros otherwise.
+  pub fn bernoulli(p: f32, shape: Vec<usize>, device: Device, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let tensor = Storage::bernoulli(p, shape, Some(device), None, generator);
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
+  /// Integers drawn uniformly from [low, high).
+  pub fn randint(low: i64, high: i64, shape: Vec<usize>, device: Device, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let tensor = Storage::randint(low, high, shape, Some(device), None, generator);
+    let requires_grad = requires_grad.unwrap_or(false);
+    Tensor::new(tensor, device, requires_grad)
+  }
+
+  /// A random permutation of 0..n.
+  pub fn randperm(n: usize, device: Device, generator: Option<&Generator>) -> Self {
+    let tensor = Storage::randperm(n, Some(device), None, generator);
+    Tensor::new(tensor, device, false)
+  }
+
+  /// Draws `num_samples` category indices from each row of `probs` ([C] or [N, C]).
+  pub fn multinomial(probs: &Tensor, num_samples: usize, replacement: bool, generator: Option<&Generator>) -> Self {
+    let tensor = Storage::multinomial(probs.tensor(), num_samples, replacement, generator);
+    Tensor::new(tensor, probs.device(), false)
+  }
+
+  pub fn zeros_like(other: &Tensor, requires_grad: Option<bool>) -> Self {
+    Tensor::zeros(other.shape().clone(), other.device(), requires_grad)
+  }
+
+  pub fn ones_like(other: &Tensor, requires_grad: Option<bool>) -> Self {
+    Tensor::ones(other.shape().clone(), other.device(), requires_grad)
+  }
+
+  pub fn full_like(other: &Tensor, value: f32, requires_grad: Option<bool>) -> Self {
+    Tensor::full(other.shape().clone(), value, other.device(), requires_grad)
+  }
+
+  pub fn uniform_like(other: &Tensor, l_bound: f32, r_bound: f32, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    Tensor::uniform(l_bound, r_bound, other.shape().clone(), other.device(), requires_grad, generator)
+  }
+
+  pub fn normal_like(other: &Tensor, mean: f32, std: f32, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    Tensor::normal(mean, std, other.shape().clone(), other.device(), requires_grad, generator)
+  }
+
+  pub fn randn_like(other: &Tensor, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    Tensor::randn(other.shape().clone(), other.device(), requires_grad, generator)
+  }
+
+  pub fn bernoulli_like(other: &Tensor, p: f32, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    Tensor::bernoulli(p, other.shape().clone(), other.device(), requires_grad, generator)
+  }
+
+  pub fn randint_like(other: &Tensor, low: i64, high: i64, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    Tensor::randint(low, high, other.shape().clone(), other.device(), requires_grad, generator)
+  }
+}
@@ -1,104 +1,110 @@
 use crate::*;
+use num_traits::Float;
 use rayon::prelude::*;
 
 
+/// Maps every element through `$body` in the compute dtype of the storage and
+/// returns its float dtype: integer and bool storages produce `f32`, half
+/// precision is evaluated in `f32` and rounded back.
+macro_rules! map_float {
+  ($storage:expr, |$x:ident| $body:expr) => {{
+    let storage: &CpuStorage = $storage;
+    with_float!(storage.dtype().compute_dtype(), F => {
+      let result: Vec<F> = strided_values::<F>(storage).into_iter().map(|$x| $body).collect();
+      CpuStorage::from_vec(result, storage.shape().clone()).to_dtype(storage.dtype().to_float())
+    })
+  }};
+}
+
+
 impl ActivationOps for CpuStorage {
   fn binary_step(&self) -> Self {
-    self.apply(|x| if x < 0. { 0. } else { 1. })
+    map_float!(self, |x| if x < 0. { 0. } else { 1. })
   }
 
   fn sigmoid(&self) -> Self {
-    self.apply(|x| 1./(1. + f32::exp(-x)))
+    map_float!(self, |x| 1./(1. + (-x).exp()))
   }
 
   fn tanh(&self) -> Self {
-    self.apply(|x| (f32::exp(x) - f32::exp(-x))/(f32::exp(x) + f32::exp(-x)))
+    map_float!(self, |x| (x.exp() - (-x).exp())/(x.exp() + (-x).exp()))
   }
 
   fn relu(&self) -> Self {
-    self.apply(|x| f32::max(0., x))
+    map_float!(self, |x| x.max(0.))
   }
 
   fn leaky_relu(&self) -> Self {
-    self.apply(|x| f32::max(0.1*x, x))
+    map_float!(self, |x| (0.1*x).max(x))
   }
 
   fn parametric_relu(&self, a: f32) -> Self {
-    self.apply(|x| f32::max(a*x, a))
+    map_float!(self, |x| if x > 0. {x} else {a as F * x})
   }
 
   fn elu(&self, alpha: f32) -> Self {
-    self.apply(|x| if x >= 0. {x} else {alpha * (f32::exp(x) - 1.)})
+    map_float!(self, |x| if x >= 0. {x} else {alpha as F * (x.exp() - 1.)})
   }
 
   fn softmax(&self, dim: usize) -> Self {
     // Compute dimensions:
-    // - outer: product of dimensions before `dim`
     // - axis_len: size of the softmax dimension (i.e. at `dim`)
     // - inner: product of dimensions after `dim`
-    let outer: usize = self.shape()[..dim].iter().product();
     let axis_len: usize = self.shape()[dim];
     let inner: usize = self.shape()[dim + 1..].iter().product();
-    let total_elements = self.shape().iter().product();
-
-    // Acquire a read lock and clone the input data.
-    let input: Vec<f32> = {
-      let binding = self.data();
-      let guard = binding.read().unwrap();
-      guard.clone()
-    };
-
-    // Allocate an output vector of the same size.
-    let mut new_data = vec![0.0; total_elements];
-    let base_offset = self.offset();
-
-    // We assume the tensor is contiguous, so the region from base_offset to
-    // base_offset + outer*(axis_len*inner) contains the relevant data.
-    // Split this region into `outer` mutable chunks, each of length axis_len*inner.
-    new_data[base_offset..base_offset + outer * (axis_len * inner)]
-      .par_chunks_mut(axis_len * inner)
-      .enumerate()
-      .for_each(|(i, out_slice)| {
-        // Compute the corresponding slice from the input data.
-        let in_start = base_offset + i * (axis_len * inner);
-        let in_end = in_start + (axis_len * inner);
-        let in_slice = &input[in_start..in_end];
-
-        // For each inner index (each column within the slice)
-        for k in 0..inner {
-          // Find the maximum value along the softmax axis for numerical stability.
-          let mut max_val = f32::NEG_INFINITY;
-          for j in 0..axis_len {
-            let idx = j * inner + k;
-            let v = in_slice[idx];
-            if v > max_val {
-              max_val = v;
-            }
-          }
 
-          // Compute the exponentials and their sum.
-          let mut sum_exp = 0.0;
-          let mut exps = vec![0.0; axis_len];
-          for j in 0..axis_len {
-            let idx = j * inner + k;
-            let exp_val = f32::exp(in_slice[idx] - max_val);
-            exps[j] = exp_val;
-            sum_exp += exp_val;
-          }
+    with_float!(self.dtype().compute_dtype(), F => {
+      let input = strided_values::<F>(self);
+      let output = softmax_lanes(&input, axis_len, inner);
+      CpuStorage::from_vec(output, self.shape().clone()).to_dtype(self.dtype().to_float())
+    })
+  }
 
-          // Normalize the exponentials and write the results to out_slice.
-          for j in 0..axis_len {
-            let idx = j * inner + k;
-            out_slice[idx] = exps[j] / sum_exp;
+  fn swish(&self) -> Self {
+    map_float!(self, |x| x * (1./(1. + (-x).exp())))
+  }
+}
+
+/// Softmax over the middle axis of a contiguous `[outer, axis_len, inner]` buffer.
+fn softmax_lanes<F: Float + Send + Sync>(input: &[F], axis_len: usize, inner: usize) -> Vec<F> {
+  // Allocate an output vector of the same size.
+  let mut new_data = vec![F::zero(); input.len()];
+
+  // Split the output into one mutable chunk per outer index, each of length axis_len*inner.
+  new_data
+    .par_chunks_mut((axis_len * inner).max(1))
+    .enumerate()
+    .for_each(|(i, out_slice)| {
+      // Compute the corresponding slice from the input data.
+      let in_start = i * (axis_len * inner);
+      let in_slice = &input[in_start..in_start + out_slice.len()];
+
+      // For each inner index (each column within the slice)
+      for k in 0..inner {
+        // Find the maximum value along the softmax axis for numerical stability.
+        let mut max_val = F::neg_infinity();
+        for j in 0..axis_len {
+          let v = in_slice[j * inner + k];
+          if v > max_val {
+            max_val = v;
           }
         }
-      });
 
-    // Return a new CpuStorage with the same shape as the original.
-    CpuStorage::new(new_data, self.shape().clone())
-  }
+        // Compute the exponentials and their sum.
+        let mut sum_exp = F::zero();
+        let mut exps = vec![F::zero(); axis_len];
+        for j in 0..axis_len {
+          let exp_val = (in_slice[j * inner + k] - max_val).exp();
+          exps[j] = exp_val;
+          sum_exp = sum_exp + exp_val;
+        }
 
-  fn swish(&self) -> Self {
-    self.apply(|x| x * (1./(1. + f32::exp(-x))))
-  }
-}
\ No newline at end of file
+        // Normalize the exponentials and write the results to out_slice.
+        for j in 0..axis_len {
+          out_slice[j * inner + k] = exps[j] / sum_exp;
+        }
+      }
+    });
+
+  new_data
+}
@@ -1,115 +1,161 @@
 use crate::*;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign, Div, DivAssign};
 
 
+/// Broadcasts both operands against each other and returns them with their
+/// promoted dtype.
+fn promoted(lhs: &CpuStorage, rhs: &CpuStorage) -> (CpuStorage, CpuStorage, DType) {
+  let (lhs_b, rhs_b) = CpuStorage::broadcast_tensors(lhs, rhs);
+  (lhs_b, rhs_b, lhs.dtype().promote(rhs.dtype()))
+}
+
+/// Combines two same-shape storages element by element in the compute dtype of
+/// `$dtype`, returning a storage of dtype `$out` (defaults to `$dtype`).
+macro_rules! zip_op {
+  ($lhs:expr, $rhs:expr, $dtype:expr, |$a:ident, $b:ident| $body:expr) => {
+    zip_op!($lhs, $rhs, $dtype => $dtype, |$a, $b| $body)
+  };
+  ($lhs:expr, $rhs:expr, $dtype:expr => $out:expr, |$a:ident, $b:ident| $body:expr) => {{
+    let (lhs, rhs): (&CpuStorage, &CpuStorage) = ($lhs, $rhs);
+    with_float!($dtype.compute_dtype(), F => {
+      let result = zip_values::<F, _>(lhs, rhs, |$a, $b| $body);
+      CpuStorage::from_vec(result, lhs.shape().clone()).to_dtype($out)
+    })
+  }};
+}
+
+/// Maps every element together with the scalar `$b` in the compute dtype of
+/// `$dtype`, returning a storage of dtype `$out` (defaults to `$dtype`).
+macro_rules! scalar_op {
+  ($storage:expr, $scalar:expr, $dtype:expr, |$a:ident, $b:ident| $body:expr) => {
+    scalar_op!($storage, $scalar, $dtype => $dtype, |$a, $b| $body)
+  };
+  ($storage:expr, $scalar:expr, $dtype:expr => $out:expr, |$a:ident, $b:ident| $body:expr) => {{
+    let storage: &CpuStorage = $storage;
+    with_float!($dtype.compute_dtype(), F => {
+      let $b = $scalar as F;
+      let result: Vec<F> = strided_values::<F>(storage).into_iter().map(|$a| $body).collect();
+      CpuStorage::from_vec(result, storage.shape().clone()).to_dtype($out)
+    })
+  }};
+}
+
 
 impl ArithmeticOps for CpuStorage {
   fn add_tensor(&self, other: &Self) -> Self {
-    let (tensor_a, tensor_b) = CpuStorage::broadcast_tensors(self, other);
-    tensor_a.elementwise_op(&tensor_b, |a, b| a + b)
+    let (tensor_a, tensor_b, dtype) = promoted(self, other);
+    zip_op!(&tensor_a, &tensor_b, dtype, |a, b| a + b)
   }
 
   fn sub_tensor(&self, other: &Self) -> Self {
-    let (tensor_a, tensor_b) = CpuStorage::broadcast_tensors(self, other);
-    tensor_a.elementwise_op(&tensor_b, |a, b| a - b)
+    let (tensor_a, tensor_b, dtype) = promoted(self, other);
+    zip_op!(&tensor_a, &tensor_b, dtype, |a, b| a - b)
   }
 
   fn mul_tensor(&self, other: &Self) -> Self {
-    let (tensor_a, tensor_b) = CpuStorage::broadcast_tensors(self, other);
-    tensor_a.elementwise_op(&tensor_b, |a, b| a * b)
+    let (tensor_a, tensor_b, dtype) = promoted(self, other);
+    zip_op!(&tensor_a, &tensor_b, dtype, |a, b| a * b)
   }
 
   fn div_tensor(&self, other: &Self) -> Self {
-    let (tensor_a, tensor_b) = CpuStorage::broadcast_tensors(self, other);
-   tensor_a.elementwise_op(&tensor_b, |a, b| a / b)
+    // True division: integer operands produce a float result
+    let (tensor_a, tensor_b, dtype) = promoted(self, other);
+    zip_op!(&tensor_a, &tensor_b, dtype.to_float(), |a, b| a / b)
   }
 
+  // Scalars are floats, so integer and bool storages promote to f32
+
   fn add_f32(&self, other: f32) -> Self {
-    self.scalar_op(other, |a, b| a + b)
+    scalar_op!(self, other, self.dtype().to_float(), |a, b| a + b)
   }
 
   fn sub_f32(&self, other: f32) -> Self {
-    self.scalar_op(other, |a, b| a - b)
+    scalar_op!(self, other, self.dtype().to_float(), |a, b| a - b)
   }
 
   fn mul_f32(&self, other: f32) -> Self {
-    self.scalar_op(other, |a, b| a * b)
+    scalar_op!(self, other, self.dtype().to_float(), |a, b| a * b)
   }
 
   fn div_f32(&self, other: f32) -> Self {
-    self.scalar_op(other, |a, b| a / b)
+    scalar_op!(self, other, self.dtype().to_float(), |a, b| a / b)
   }
 
   fn pow_f32(&self, other: f32) -> Self {
-    self.scalar_op(other, |a, b| f32::powf(a, b))
+    scalar_op!(self, other, self.dtype().to_float(), |a, b| a.powf(b))
   }
 
+  // Comparisons produce f32 masks (1/0, or 1/-1 unless `make_binary`)
+
   fn greater_than(&self, other: &Self, make_binary: bool) -> Self {
-    let (tensor_a, tensor_b) = CpuStorage::broadcast_tensors(self, other);
-    tensor_a.elementwise_op(&tensor_b, |a, b| if a > b { 1.0 } else if make_binary { 0.0 } else {-1.0})
+    let (tensor_a, tensor_b, dtype) = promoted(self, other);
+    zip_op!(&tensor_a, &tensor_b, dtype => DType::F32, |a, b| if a > b { 1.0 } else if make_binary { 0.0 } else {-1.0})
   }
 
   fn greater_than_f32(&self, other: f32, make_binary: bool) -> Self {
-    self.scalar_op(other, |a, b| if a > b { 1.0 } else if make_binary { 0.0 } else {-1.0})
+    scalar_op!(self, other, self.dtype() => DType::F32, |a, b| if a > b { 1.0 } else if make_binary { 0.0 } else {-1.0})
   }
 
   fn less_than(&self, other: &Self, make_binary: bool) -> Self {
-    let (tensor_a, tensor_b) = CpuStorage::broadcast_tensors(self, other);
-    tensor_a.elementwise_op(&tensor_b, |a, b| if a < b { 1.0 } else if make_binary { 0.0 } else {-1.0})
+    let (tensor_a, tensor_b, dtype) = promoted(self, other);
+    zip_op!(&tensor_a, &tensor_b, dtype => DType::F32, |a, b| if a < b { 1.0 } else if make_binary { 0.0 } else {-1.0})
   }
 
   fn less_than_f32(&self, other: f32, make_binary: bool) -> Self {
-    self.scalar_op(other, |a, b| if a < b { 1.0 } else if make_binary { 0.0 } else {-1.0})
+    scalar_op!(self, other, self.dtype() => DType::F32, |a, b| if a < b { 1.0 } else if make_binary { 0.0 } else {-1.0})
   }
 
   fn sign(&self) -> Self {
-    self.apply(|a| if a > 0. { 1.0 } else if a < 0. { -1. } else { 0.0 })
+    scalar_op!(self, 0.0, self.dtype(), |a, _zero| if a > 0. { 1.0 } else if a < 0. { -1. } else { 0.0 })
   }
 
   fn abs(&self) -> Self {
-    self.apply(|a| f32::abs(a))
+    scalar_op!(self, 0.0, self.dtype(), |a, _zero| a.abs())
   }
 
+  // In-place ops broadcast `other` to `self` and keep `self`'s dtype
+
   fn add_tensor_assign(&mut self, other: &Self) {
-    // Only broadcast one side
-    let broadcast_b = other.broadcast(&self.shape());
-    self.elementwise_op_assign(&broadcast_b, |a, b| a + b)
+    let other = other.broadcast(self.shape());
+    *self = zip_op!(self, &other, self.dtype().promote(other.dtype()) => self.dtype(), |a, b| a + b);
   }
 
   fn sub_tensor_assign(&mut self, other: &Self) {
-    self.elementwise_op_assign(other, |a, b| a - b)
+    let other = other.broadcast(self.shape());
+    *self = zip_op!(self, &other, self.dtype().promote(other.dtype()) => self.dtype(), |a, b| a - b);
   }
 
   fn mul_tensor_assign(&mut self, other: &Self) {
-    self.elementwise_op_assign(other, |a, b| a * b)
+    let other = other.broadcast(self.shape());
+    *self = zip_op!(self, &other, self.dtype().promote(other.dtype()) => self.dtype(), |a, b| a * b);
   }
 
   fn div_tensor_assign(&mut self, other: &Self) {
-    self.elementwise_op_assign(other, |a, b| a / b)
+    let other = other.broadcast(self.shape());
+    *self = zip_op!(self, &other, self.dtype().promote(other.dtype()).to_float() => self.dtype(), |a, b| a / b);
   }
 
   fn add_f32_assign(&mut self, other: f32) {
-    self.scalar_op_assign(other, |a, b| a + b)
+    *self = scalar_op!(self, other, self.dtype().to_float() => self.dtype(), |a, b| a + b);
   }
 
   fn sub_f32_assign(&mut self, other: f32) {
-    self.scalar_op_assign(other, |a, b| a - b)
+    *self = scalar_op!(self, other, self.dtype().to_float() => self.dtype(), |a, b| a - b);
   }
 
   fn mul_f32_assign(&mut self, other: f32) {
-    self.scalar_op_assign(other, |a, b| a * b)
+    *self = scalar_op!(self, other, self.dtype().to_float() => self.dtype(), |a, b| a * b);
   }
 
   fn div_f32_assign(&mut self, other: f32) {
-    self.scalar_op_assign(other, |a, b| a / b)
+    *self = scalar_op!(self, other, self.dtype().to_float() => self.dtype(), |a, b| a / b);
   }
 
   fn pow_f32_assign(&mut self, other: f32) {
-    self.scalar_op_assign(other, |a, b| f32::powf(a, b))
+    *self = scalar_op!(self, other, self.dtype().to_float() => self.dtype(), |a, b| a.powf(b));
   }
 
 
   fn abs_assign(&mut self) {
-    self.apply_assign(|a| f32::abs(a))
+    *self = self.abs();
   }
 }
@@ -1,60 +1,168 @@
 use crate::*;
 
-// CBLAS_LAYOUT
-const CBLAS_ROW_MAJOR: u8 = 101;
-const CBLAS_COL_MAJOR: u8 = 102;
+#[cfg(all(feature = "openblas", not(feature = "pure-rust")))]
+mod cblas {
+  // CBLAS_LAYOUT
+  pub const CBLAS_ROW_MAJOR: u8 = 101;
+  pub const CBLAS_COL_MAJOR: u8 = 102;
 
-// CBLAS_TRANSPOSE
-const CBLAS_NO_TRANS: u8 = 111;
-const CBLAS_TRANS: u8 = 112;
-const CBLAS_CONJ_TRANS: u8 = 113;
+  // CBLAS_TRANSPOSE
+  pub const CBLAS_NO_TRANS: u8 = 111;
+  pub const CBLAS_TRANS: u8 = 112;
+  pub const CBLAS_CONJ_TRANS: u8 = 113;
 
-#[link(name = "openblas")] // Replace "openblas" with the library you installed if different
-extern "C" {
-  fn cblas_ddot(n: i32, x: *const f64, incx: i32, y: *const f64, incy: i32) -> f64;
-  fn cblas_dgemv(Layout: u8, trans: u8, m: i32, n: i32, alpha: f64, a: *const f64, lda: i32, x: *const f64, incx: i32, beta: f64, y: *mut f64, incy: i32);
-  fn cblas_sgemm(Layout: u8, transa: u8, transb: u8, m: i32, n: i32, k: i32, alpha: f32, a: *const f32, lda: i32, b: *const f32, ldb: i32, beta: f32, c: *mut f32, ldc: i32);
+  #[link(name = "openblas")] // Replace "openblas" with the library you installed if different
+  extern "C" {
+    pub fn cblas_ddot(n: i32, x: *const f64, incx: i32, y: *const f64, incy: i32) -> f64;
+    pub fn cblas_dgemv(Layout: u8, trans: u8, m: i32, n: i32, alpha: f64, a: *const f64, lda: i32, x: *const f64, incx: i32, beta: f64, y: *mut f64, incy: i32);
+    pub fn cblas_sgemm(Layout: u8, transa: u8, transb: u8, m: i32, n: i32, k: i32, alpha: f32, a: *const f32, lda: i32, b: *const f32, ldb: i32, beta: f32, c: *mut f32, ldc: i32);
+  }
+}
+
+/// Row-major `C = op(A) * op(B)` through the system BLAS.
+#[cfg(all(feature = "openblas", not(feature = "pure-rust")))]
+#[allow(clippy::too_many_arguments)]
+fn sgemm(trans_a: bool, trans_b: bool, m: usize, n: usize, k: usize, a: &[f32], lda: usize, b: &[f32], ldb: usize, c: &mut [f32], ldc: usize) {
+  use cblas::*;
+  let trans_a = if trans_a { CBLAS_TRANS } else { CBLAS_NO_TRANS };
+  let trans_b = if trans_b { CBLAS_TRANS } else { CBLAS_NO_TRANS };
+  unsafe {
+    cblas_sgemm(
+      CBLAS_ROW_MAJOR, trans_a, trans_b,
+      m as i32, n as i32, k as i32, 1.0,
+      a.as_ptr(), lda as i32,
+      b.as_ptr(), ldb as i32, 0.0,
+      c.as_mut_ptr(), ldc as i32
+    );
+  }
+}
+
+/// Row-major `C = op(A) * op(B)` through the built-in blocked GEMM.
+#[cfg(not(all(feature = "openblas", not(feature = "pure-rust"))))]
+#[allow(clippy::too_many_arguments)]
+fn sgemm(trans_a: bool, trans_b: bool, m: usize, n: usize, k: usize, a: &[f32], lda: usize, b: &[f32], ldb: usize, c: &mut [f32], ldc: usize) {
+  gemm::sgemm(trans_a, trans_b, m, n, k, 1.0, a, lda, b, ldb, 0.0, c, ldc);
+}
+
+/// Row-major `C = op(A) * op(B)` in double precision through the built-in blocked GEMM.
+#[allow(clippy::too_many_arguments)]
+fn dgemm(trans_a: bool, trans_b: bool, m: usize, n: usize, k: usize, a: &[f64], lda: usize, b: &[f64], ldb: usize, c: &mut [f64], ldc: usize) {
+  gemm::dgemm(trans_a, trans_b, m, n, k, 1.0, a, lda, b, ldb, 0.0, c, ldc);
+}
+
+/// Broadcasts two batch shapes (everything but the trailing matrix dims) NumPy-style.
+fn broadcast_batch(a: &[usize], b: &[usize]) -> Vec<usize> {
+  let rank = a.len().max(b.len());
+  (0..rank).map(|i| {
+    let da = if i + a.len() >= rank { a[i + a.len() - rank] } else { 1 };
+    let db = if i + b.len() >= rank { b[i + b.len() - rank] } else { 1 };
+    if da == db || db == 1 { da } else if da == 1 { db } else {
+      panic!("Matmul batch dimensions {:?} and {:?} cannot be broadcast", a, b);
+    }
+  }).collect()
+}
+
+/// Offset (in matrices) of the operand matrix used for each batch of the output,
+/// honouring broadcasting of size-1 and missing batch dimensions.
+fn batch_offsets(batch: &[usize], operand: &[usize]) -> Vec<usize> {
+  let count: usize = batch.iter().product();
+  let pad = batch.len() - operand.len();
+  let strides = CpuStorage::compute_strides(&operand.to_vec());
+  (0..count).map(|mut flat| {
+    let mut offset = 0;
+    for dim in (0..batch.len()).rev() {
+      let index = flat % batch[dim];
+      flat /= batch[dim];
+      if dim >= pad && operand[dim - pad] != 1 {
+        offset += index * strides[dim - pad];
+      }
+    }
+    offset
+  }).collect()
 }
 
 impl BlasOps for CpuStorage {
+  /// Matrix product with NumPy semantics: 1-D operands are promoted to a row
+  /// (lhs) or column (rhs) vector and the promoted dimension is dropped from the
+  /// result; leading dimensions are treated as a broadcast batch. The transpose
+  /// flags apply to the trailing two dimensions of each operand. `f32` and
+  /// half-precision operands accumulate in `f32`, everything else in `f64`, and
+  /// the product is cast to the promoted dtype.
   fn matmul(&self, other: &Self, transpose_self: bool, transpose_other: bool) -> Self {
-    if self.shape().len() != 2 { panic!("Can't Matmul on non-matrices"); }
-
-    // Check dimensions
-    if transpose_self && (self.shape()[0] != other.shape()[0]) { 
-      panic!("Matrix dimensions do not match for multiplication.");
-    } else if transpose_other && (self.shape()[1] != other.shape()[1]) { 
-      panic!("Matrix dimensions do not match for multiplication.");
-    } else if !transpose_other && !transpose_self && self.shape()[1] != other.shape()[0] { 
-      panic!("Matrix dimensions do not match for multiplication.");
+    let dtype = self.dtype().promote(other.dtype());
+    match dtype.compute_dtype() {
+      DType::F32 => batched_matmul(self, other, transpose_self, transpose_other, sgemm).to_dtype(dtype),
+      _ => batched_matmul(self, other, transpose_self, transpose_other, dgemm).to_dtype(dtype),
     }
+  }
+}
+
+/// Batched matmul over operands cast to `T`, with `gemm` computing each
+/// `C = op(A) * op(B)`.
+fn batched_matmul<T, G>(lhs: &CpuStorage, rhs: &CpuStorage, transpose_self: bool, transpose_other: bool, gemm: G) -> CpuStorage
+where
+  T: Element,
+  G: Fn(bool, bool, usize, usize, usize, &[T], usize, &[T], usize, &mut [T], usize),
+{
+  if lhs.shape().is_empty() || rhs.shape().is_empty() { panic!("Can't Matmul on scalars"); }
+
+  let vector_lhs = lhs.shape().len() == 1;
+  let vector_rhs = rhs.shape().len() == 1;
+  let a_shape = if vector_lhs { vec![1, lhs.shape()[0]] } else { lhs.shape().clone() };
+  let b_shape = if vector_rhs { vec![rhs.shape()[0], 1] } else { rhs.shape().clone() };
+  let transpose_self = transpose_self && !vector_lhs;
+  let transpose_other = transpose_other && !vector_rhs;
+
+  let (a_batch, a_mat) = a_shape.split_at(a_shape.len() - 2);
+  let (b_batch, b_mat) = b_shape.split_at(b_shape.len() - 2);
+
+  // Get dimensions
+  let (m, k) = if !transpose_self { (a_mat[0], a_mat[1]) } else { (a_mat[1], a_mat[0]) };
+  let (k_other, n) = if !transpose_other { (b_mat[0], b_mat[1]) } else { (b_mat[1], b_mat[0]) };
+  if k != k_other {
+    panic!("Matrix dimensions do not match for multiplication: {:?} and {:?}", lhs.shape(), rhs.shape());
+  }
 
-    let layout = CBLAS_ROW_MAJOR;
-    let trans_a = if transpose_self { CBLAS_TRANS } else { CBLAS_NO_TRANS };
-    let trans_b = if transpose_other { CBLAS_TRANS } else { CBLAS_NO_TRANS };
-    
-    // Get dimensions
-    let m = if !transpose_self { self.shape()[0] } else { self.shape()[1] };
-    let k = if !transpose_self { self.shape()[1] } else { self.shape()[0] };
-    let n = if !transpose_other { other.shape()[1] } else { other.shape()[0] };
-
-    // Get contiguous data
-    let (a_data, lda) = self.make_contiguous();
-    let (b_data, ldb) = other.make_contiguous();
-
-    let mut c = vec![0.0; (m * n) as usize];
-    let ldc = n as i32;
-
-    unsafe {
-      cblas_sgemm(
-        layout, trans_a, trans_b,
-        m as i32, n as i32, k as i32, 1.0,
-        a_data.as_ptr(), lda,
-        b_data.as_ptr(), ldb, 0.0,
-        c.as_mut_ptr(), ldc
+  let batch = broadcast_batch(a_batch, b_batch);
+  let batch_count: usize = batch.iter().product();
+
+  // Get contiguous data
+  let a_data = strided_values::<T>(lhs);
+  let b_data = strided_values::<T>(rhs);
+  let lda = if vector_lhs { k } else { a_shape[a_shape.len() - 1] };
+  let ldb = if vector_rhs { 1 } else { b_shape[b_shape.len() - 1] };
+
+  let mut c = vec![T::default(); batch_count * m * n];
+  let ldc = n;
+
+  let a_offsets = batch_offsets(&batch, a_batch);
+  let b_offsets = batch_offsets(&batch, b_batch);
+
+  if batch_count * m * n * k > 0 {
+    if !transpose_self && b_offsets.iter().all(|&offset| offset == 0) && a_offsets.windows(2).all(|w| w[1] == w[0] + 1) {
+      // A shared rhs against stacked lhs matrices is a single tall GEMM
+      gemm(
+        transpose_self, transpose_other, batch_count * m, n, k,
+        &a_data[a_offsets[0] * m * k..], lda,
+        &b_data, ldb,
+        &mut c, ldc
       );
+    } else {
+      // Strided batch: one GEMM per output matrix, stepping through each operand
+      for (index, (a_offset, b_offset)) in a_offsets.iter().zip(&b_offsets).enumerate() {
+        gemm(
+          transpose_self, transpose_other, m, n, k,
+          &a_data[a_offset * m * k..], lda,
+          &b_data[b_offset * k * n..], ldb,
+          &mut c[index * m * n..], ldc
+        );
+      }
     }
-
-    CpuStorage::new(c, vec![m as usize, n as usize])
   }
-}
\ No newline at end of file
+
+  let mut shape = batch;
+  if !vector_lhs { shape.push(m); }
+  if !vector_rhs { shape.push(n); }
+  if shape.is_empty() { shape.push(1); }
+  CpuStorage::from_vec(c, shape)
+}
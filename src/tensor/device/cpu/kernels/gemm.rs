@@ -0,0 +1,142 @@
+//! Pure-Rust general matrix multiplication.
+//!
+//! A cache-blocked GEMM in the style of GotoBLAS: `B` is packed into `KC x NC`
+//! panels shared by all threads, each rayon task packs an `MC x KC` block of
+//! `A`, and a fixed-size `MR x NR` micro-kernel accumulates in registers. The
+//! micro-kernel works on plain arrays so LLVM can vectorize it.
+//!
+//! All matrices are row-major; `lda`, `ldb` and `ldc` are row strides of the
+//! matrices as stored (i.e. before any transpose is applied).
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+const MR: usize = 4;
+const NR: usize = 16;
+const MC: usize = 64;
+const KC: usize = 256;
+const NC: usize = 4096;
+
+/// `C = alpha * op(A) * op(B) + beta * C` in single precision.
+#[allow(clippy::too_many_arguments)]
+pub fn sgemm(
+  trans_a: bool, trans_b: bool,
+  m: usize, n: usize, k: usize, alpha: f32,
+  a: &[f32], lda: usize,
+  b: &[f32], ldb: usize, beta: f32,
+  c: &mut [f32], ldc: usize,
+) {
+  gemm(trans_a, trans_b, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc);
+}
+
+/// `C = alpha * op(A) * op(B) + beta * C` in double precision.
+#[allow(clippy::too_many_arguments)]
+pub fn dgemm(
+  trans_a: bool, trans_b: bool,
+  m: usize, n: usize, k: usize, alpha: f64,
+  a: &[f64], lda: usize,
+  b: &[f64], ldb: usize, beta: f64,
+  c: &mut [f64], ldc: usize,
+) {
+  gemm(trans_a, trans_b, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn gemm<T: Float + Send + Sync>(
+  trans_a: bool, trans_b: bool,
+  m: usize, n: usize, k: usize, alpha: T,
+  a: &[T], lda: usize,
+  b: &[T], ldb: usize, beta: T,
+  c: &mut [T], ldc: usize,
+) {
+  if m == 0 || n == 0 {
+    return;
+  }
+
+  // Scale C up front; beta == 0 must overwrite (C may hold NaNs)
+  for row in c.chunks_mut(ldc).take(m) {
+    for value in &mut row[..n] {
+      *value = if beta == T::zero() { T::zero() } else { *value * beta };
+    }
+  }
+  if k == 0 || alpha == T::zero() {
+    return;
+  }
+
+  let a_at = |i: usize, p: usize| if trans_a { a[p * lda + i] } else { a[i * lda + p] };
+  let b_at = |p: usize, j: usize| if trans_b { b[j * ldb + p] } else { b[p * ldb + j] };
+
+  for pc in (0..k).step_by(KC) {
+    let kc = KC.min(k - pc);
+    for jc in (0..n).step_by(NC) {
+      let nc = NC.min(n - jc);
+      let b_panel = pack_b(kc, nc, |p, j| b_at(pc + p, jc + j));
+
+      // Row blocks of C are disjoint, so they can be filled in parallel
+      c.par_chunks_mut(MC * ldc).take(m.div_ceil(MC)).enumerate().for_each(|(block, c_block)| {
+        let ic = block * MC;
+        let mc = MC.min(m - ic);
+        let a_panel = pack_a(mc, kc, |i, p| alpha * a_at(ic + i, pc + p));
+
+        for (jr, b_sliver) in b_panel.chunks(kc * NR).enumerate() {
+          let nr = NR.min(nc - jr * NR);
+          for (ir, a_sliver) in a_panel.chunks(kc * MR).enumerate() {
+            let mr = MR.min(mc - ir * MR);
+            let acc = micro_kernel(kc, a_sliver, b_sliver);
+            for (r, acc_row) in acc.iter().enumerate().take(mr) {
+              let row = &mut c_block[(ir * MR + r) * ldc + jc + jr * NR..];
+              for (value, &sum) in row.iter_mut().zip(acc_row).take(nr) {
+                *value = *value + sum;
+              }
+            }
+          }
+        }
+      });
+    }
+  }
+}
+
+/// Packs an `mc x kc` block of A into `MR`-row slivers laid out column by column,
+/// zero-padding the last sliver.
+fn pack_a<T: Float>(mc: usize, kc: usize, at: impl Fn(usize, usize) -> T) -> Vec<T> {
+  let slivers = mc.div_ceil(MR);
+  let mut packed = vec![T::zero(); slivers * MR * kc];
+  for s in 0..slivers {
+    for p in 0..kc {
+      for r in 0..MR.min(mc - s * MR) {
+        packed[s * MR * kc + p * MR + r] = at(s * MR + r, p);
+      }
+    }
+  }
+  packed
+}
+
+/// Packs a `kc x nc` block of B into `NR`-column slivers laid out row by row,
+/// zero-padding the last sliver.
+fn pack_b<T: Float>(kc: usize, nc: usize, at: impl Fn(usize, usize) -> T) -> Vec<T> {
+  let slivers = nc.div_ceil(NR);
+  let mut packed = vec![T::zero(); slivers * NR * kc];
+  for s in 0..slivers {
+    for p in 0..kc {
+      for col in 0..NR.min(nc - s * NR) {
+        packed[s * NR * kc + p * NR + col] = at(p, s * NR + col);
+      }
+    }
+  }
+  packed
+}
+
+/// Multiplies one packed `MR x kc` sliver of A by one packed `kc x NR` sliver of B.
+#[inline(always)]
+fn micro_kernel<T: Float>(kc: usize, a: &[T], b: &[T]) -> [[T; NR]; MR] {
+  let mut acc = [[T::zero(); NR]; MR];
+  for (a_col, b_row) in a.chunks_exact(MR).zip(b.chunks_exact(NR)).take(kc) {
+    for r in 0..MR {
+      let a_value = a_col[r];
+      for col in 0..NR {
+        acc[r][col] = acc[r][col] + a_value * b_row[col];
+      }
+    }
+  }
+  acc
+}
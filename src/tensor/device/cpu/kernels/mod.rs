@@ -4,6 +4,7 @@ mod blas;         // Internal module
 mod reduction;    // Internal module
 mod transform;       // Internal module
 mod activation;
+pub mod gemm;        // Pure-Rust GEMM, public for benchmarking
 
 // Re-export what you want public
 pub use arithmetic::*;
@@ -1,59 +1,183 @@
 use crate::*;
-use ndarray::prelude::*;
+use num_traits::Float;
+use std::iter::Sum;
+
+
+/// Marks the dimensions to reduce over; an empty `dims` selects every dimension.
+fn reduced_mask(shape: &[usize], dims: &[usize]) -> Vec<bool> {
+  if dims.is_empty() {
+    return vec![true; shape.len()];
+  }
+  let mut mask = vec![false; shape.len()];
+  for &dim in dims {
+    if dim >= shape.len() {
+      panic!("Reduction dimension {} out of range for tensor of rank {}", dim, shape.len());
+    }
+    mask[dim] = true;
+  }
+  mask
+}
+
+/// Reduces every lane spanned by `dims` with `op`, which receives the lane's
+/// values (cast to `T`) in row-major order over the reduced dimensions.
+fn reduce_lanes<T: Element, U: Element, F>(storage: &CpuStorage, dims: &[usize], keepdim: bool, op: F) -> CpuStorage
+where
+  F: Fn(&[T]) -> U,
+{
+  let shape = storage.shape().clone();
+  let mask = reduced_mask(&shape, dims);
+
+  // Move the reduced dimensions to the back so each lane is a contiguous run
+  let order: Vec<usize> = (0..shape.len()).filter(|&d| !mask[d])
+    .chain((0..shape.len()).filter(|&d| mask[d]))
+    .collect();
+  let mut permuted = storage.clone();
+  permuted.permute(&order);
+  let data = strided_values::<T>(&permuted);
+
+  let lanes: usize = (0..shape.len()).filter(|&d| !mask[d]).map(|d| shape[d]).product();
+  let lane_len: usize = (0..shape.len()).filter(|&d| mask[d]).map(|d| shape[d]).product();
+  let result: Vec<U> = (0..lanes).map(|i| op(&data[i * lane_len..(i + 1) * lane_len])).collect();
+
+  let mut new_shape: Vec<usize> = if keepdim {
+    shape.iter().zip(&mask).map(|(&size, &reduced)| if reduced { 1 } else { size }).collect()
+  } else {
+    shape.iter().zip(&mask).filter(|(_, &reduced)| !reduced).map(|(&size, _)| size).collect()
+  };
+  if new_shape.is_empty() {
+    new_shape.push(1);
+  }
+  CpuStorage::from_vec(result, new_shape)
+}
+
+/// Reduces in the compute dtype of the storage and casts the result to `$out`.
+macro_rules! reduce_float {
+  ($storage:expr, $dims:expr, $keepdim:expr => $out:expr, |$lane:ident| $body:expr) => {{
+    let storage: &CpuStorage = $storage;
+    with_float!(storage.dtype().compute_dtype(), F => {
+      reduce_lanes(storage, $dims, $keepdim, |$lane: &[F]| -> F { $body }).to_dtype($out)
+    })
+  }};
+}
+
+/// Sums and products of integers and bools accumulate into `i64`.
+fn accumulated(dtype: DType) -> DType {
+  if dtype.is_floating_point() { dtype } else { DType::I64 }
+}
+
+fn lane_mean<F: Float + Sum>(lane: &[F]) -> F {
+  lane.iter().copied().sum::<F>() / F::from(lane.len()).unwrap()
+}
+
+fn lane_var<F: Float + Sum>(lane: &[F], unbiased: bool) -> F {
+  let mean = lane_mean(lane);
+  let correction = if unbiased { F::one() } else { F::zero() };
+  lane.iter().map(|&x| (x - mean) * (x - mean)).sum::<F>() / (F::from(lane.len()).unwrap() - correction)
+}
+
+/// Position of the first extreme element of the lane under `better`.
+fn lane_arg<F: Float, B: Fn(F, F) -> bool>(lane: &[F], better: B) -> i64 {
+  let mut best = 0;
+  for (i, &x) in lane.iter().enumerate() {
+    if x.is_nan() {
+      return i as i64;
+    }
+    if better(x, lane[best]) {
+      best = i;
+    }
+  }
+  best as i64
+}
 
 
 impl ReductionOps for CpuStorage {
   fn sum(&self) -> Self {
-    let data: f32 = self.data().read().unwrap().iter().sum();
-    CpuStorage::from_ndarray(&array![data], None, None)
+    self.sum_dims(&[], false)
   }
 
   fn sum_axis(&self, axis: usize) -> Self {
-    // Compute the sizes of the "outer" dimensions, the dimension to sum over,
-    // and the "inner" (trailing) dimensions.
-    let outer: usize = self.shape()[..axis].iter().product();
-    let axis_len: usize = self.shape()[axis];
-    let trailing: usize = self.shape()[axis+1..].iter().product();
-    
-    // The new shape is the original shape with the summing axis removed.
-    let mut new_shape = self.shape().clone();
-    new_shape.remove(axis);
-
-    // Prepare a vector for the summed data.
-    let mut new_data = vec![0.0; outer * trailing];
-    
-    // Borrow the underlying data.
-    let binding = self.data();
-    let data_ref = binding.read().unwrap();
-    
-    // Iterate over the "outer" blocks and the "inner" trailing dimensions.
-    // For each such location, sum over the elements along the `axis`.
-    for i in 0..outer {
-      for k in 0..trailing {
-        let mut sum = 0.0;
-        for j in 0..axis_len {
-          // In contiguous (row-major) layout, the index is computed as:
-          //   index = offset + i * (axis_len * trailing) + j * trailing + k
-          let index = self.offset() + i * (axis_len * trailing) + j * trailing + k;
-          sum += data_ref[index];
-        }
-        new_data[i * trailing + k] = sum;
-      }
-    }
-    
-    // Construct a new CpuStorage with the summed data and the new shape.
-    CpuStorage::new(new_data, new_shape)
+    self.sum_dims(&[axis], false)
   }
 
   fn product(&self) -> Self {
-    let data: f32 = self.data().read().unwrap().iter().sum();
-    CpuStorage::from_ndarray(&array![data], None, None)
+    self.prod_dims(&[], false)
   }
 
   fn mean(&self) -> Self {
-    let data: f32 = self.data().read().unwrap().iter().sum::<f32>() / self.data().read().unwrap().len() as f32;
-    CpuStorage::from_ndarray(&array![data], None, None)
+    self.mean_dims(&[], false)
+  }
+
+  fn sum_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => accumulated(self.dtype()), |lane| lane.iter().copied().sum())
+  }
+
+  fn mean_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => self.dtype().to_float(), |lane| lane_mean(lane))
   }
 
-  
-}
\ No newline at end of file
+  fn prod_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => accumulated(self.dtype()), |lane| lane.iter().copied().product())
+  }
+
+  fn max(&self, dims: &[usize], keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => self.dtype(), |lane| {
+      lane.iter().fold(F::NEG_INFINITY, |acc, &x| if x.is_nan() || acc.is_nan() { F::NAN } else { acc.max(x) })
+    })
+  }
+
+  fn min(&self, dims: &[usize], keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => self.dtype(), |lane| {
+      lane.iter().fold(F::INFINITY, |acc, &x| if x.is_nan() || acc.is_nan() { F::NAN } else { acc.min(x) })
+    })
+  }
+
+  fn argmax(&self, dims: &[usize], keepdim: bool) -> Self {
+    with_float!(self.dtype().compute_dtype(), F => {
+      reduce_lanes(self, dims, keepdim, |lane: &[F]| lane_arg(lane, |x, best| x > best))
+    })
+  }
+
+  fn argmin(&self, dims: &[usize], keepdim: bool) -> Self {
+    with_float!(self.dtype().compute_dtype(), F => {
+      reduce_lanes(self, dims, keepdim, |lane: &[F]| lane_arg(lane, |x, best| x < best))
+    })
+  }
+
+  fn var(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => self.dtype().to_float(), |lane| lane_var(lane, unbiased))
+  }
+
+  fn std(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => self.dtype().to_float(), |lane| lane_var(lane, unbiased).sqrt())
+  }
+
+  fn norm(&self, p: f32, dims: &[usize], keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => self.dtype().to_float(), |lane| {
+      if p == f32::INFINITY {
+        lane.iter().fold(0.0, |acc: F, &x| acc.max(x.abs()))
+      } else {
+        let p = p as F;
+        lane.iter().map(|&x| x.abs().powf(p)).sum::<F>().powf(1.0 / p)
+      }
+    })
+  }
+
+  fn logsumexp(&self, dims: &[usize], keepdim: bool) -> Self {
+    reduce_float!(self, dims, keepdim => self.dtype().to_float(), |lane| {
+      // Shift by the maximum so the exponentials cannot overflow
+      let max = lane.iter().fold(F::NEG_INFINITY, |acc, &x| acc.max(x));
+      if max.is_infinite() {
+        return max;
+      }
+      max + lane.iter().map(|&x| (x - max).exp()).sum::<F>().ln()
+    })
+  }
+
+  fn all(&self, dims: &[usize], keepdim: bool) -> Self {
+    reduce_lanes(self, dims, keepdim, |lane: &[bool]| lane.iter().all(|&x| x))
+  }
+
+  fn any(&self, dims: &[usize], keepdim: bool) -> Self {
+    reduce_lanes(self, dims, keepdim, |lane: &[bool]| lane.iter().any(|&x| x))
+  }
+}
@@ -5,93 +5,34 @@ impl TransformOps for CpuStorage {
   where
     F: Fn(f32) -> f32,
   {
-    let data = self.data().read().unwrap().iter()
-      .map(|a| op(*a))
+    let data: Vec<f32> = strided_values(self).into_iter()
+      .map(op)
       .collect();
 
-    self.set_data(data);
+    self.set_contiguous_data(data);
   }
 
   fn elementwise_op_assign<F>(&mut self, other: &Self, op: F)
   where
     F: Fn(f32, f32) -> f32,
   {
-    let total_elements = self.shape().iter().product();
-    let mut result = vec![0.0; total_elements];
-    
-    // Get data once to avoid multiple borrows
-    let self_binding = self.data();
-    let self_data = self_binding.read().unwrap();
-    let other_binding = other.data();
-    let other_data = other_binding.read().unwrap();
-    
-    // Pre-calculate dimensions for faster access
-    let rank = self.shape().len();
-    let shape = self.shape();
-    let self_strides = self.stride();
-    let other_strides = other.stride();
-    
-    // Use chunk size optimization for contiguous dimensions
-    let mut chunk_size = 1;
-    let mut contiguous_dims = 0;
-    for dim in (0..rank).rev() {
-      if self_strides[dim] == chunk_size && other_strides[dim] == chunk_size {
-        chunk_size *= shape[dim];
-        contiguous_dims += 1;
-      } else {
-        break;
-      }
-    }
-    
-    let outer_dims = rank - contiguous_dims;
-    let mut indices = vec![0; outer_dims];
-    
-    // Process chunks
-    let chunks = total_elements / chunk_size;
-    for chunk_idx in 0..chunks {
-      // Calculate base indices for the chunk
-      let mut self_base_idx = 0;
-      let mut other_base_idx = 0;
-      
-      for (dim, &idx) in indices.iter().enumerate() {
-        self_base_idx += idx * self_strides[dim];
-        other_base_idx += idx * other_strides[dim];
-      }
-      
-      // Process the entire chunk
-      let result_start = chunk_idx * chunk_size;
-      for i in 0..chunk_size {
-        let self_val = self_data[self_base_idx + i];
-        let other_val = other_data[other_base_idx + i];
-        result[result_start + i] = op(self_val, other_val);
-      }
-      
-      // Update indices for outer dimensions
-      for dim in (0..outer_dims).rev() {
-        indices[dim] += 1;
-        if indices[dim] < shape[dim] {
-          break;
-        }
-        indices[dim] = 0;
-      }
-    }
-
-    self.set_data(result);
+    let result = zip_values::<f32, _>(self, other, op);
+    self.set_contiguous_data(result);
   }
 
   fn reshape(&mut self, new_shape: Vec<usize>) {
-    self.set_shape(new_shape);
+    *self = self.view(new_shape);
   }
 
   fn scalar_op_assign<F>(&mut self, scalar: f32, op: F)
   where
     F: Fn(f32, f32) -> f32,
   {
-    let data = self.data().read().unwrap().iter()
-      .map(|a| op(*a, scalar))
+    let data: Vec<f32> = strided_values(self).into_iter()
+      .map(|a| op(a, scalar))
       .collect();
 
-    self.set_data(data);
+    self.set_contiguous_data(data);
   }
 
   fn permute(&mut self, dims: &[usize]) {
@@ -106,6 +47,9 @@ impl TransformOps for CpuStorage {
   }
 
   fn flatten(&mut self) {
+    if !self.is_contiguous() {
+      *self = self.contiguous();
+    }
     let shape: Vec<usize> = vec![self.shape().iter().product()];
     let stride = vec![1];
 
@@ -114,6 +58,9 @@ impl TransformOps for CpuStorage {
   }
 
   fn squeeze(&mut self) {
+    if !self.is_contiguous() {
+      *self = self.contiguous();
+    }
     // Remove all 1 dimension from the shape
     let shape: Vec<usize> = self.shape().to_owned().iter().filter(|&&x| x != 1).cloned().collect();
     let stride = Self::compute_strides(&shape);
@@ -123,6 +70,9 @@ impl TransformOps for CpuStorage {
   } 
 
   fn unsqueeze(&mut self, dim: usize) {
+    if !self.is_contiguous() {
+      *self = self.contiguous();
+    }
     let mut shape: Vec<usize> = self.shape().to_owned();
     shape.insert(dim, 1);
     let stride = Self::compute_strides(&shape);
@@ -135,89 +85,30 @@ impl TransformOps for CpuStorage {
   where
     F: Fn(f32) -> f32,
   {
-    let data = self.data().read().unwrap().iter()
-      .map(|a| op(*a))
+    let data = strided_values(self).into_iter()
+      .map(op)
       .collect();
 
-    Self::new(data, self.shape().clone())
+    Self::new(data, self.shape().clone()).to_dtype(self.dtype())
   }
 
   fn elementwise_op<F>(&self, other: &Self, op: F) -> Self
   where
     F: Fn(f32, f32) -> f32,
   {
-    let total_elements = self.shape().iter().product();
-    let mut result = vec![0.0; total_elements];
-    
-    // Get data once to avoid multiple borrows
-    let self_binding = self.data();
-    let self_data = self_binding.read().unwrap();
-    let other_binding = other.data();
-    let other_data = other_binding.read().unwrap();
-    
-    // Pre-calculate dimensions for faster access
-    let rank = self.shape().len();
-    let shape = self.shape();
-    let self_strides = self.stride();
-    let other_strides = other.stride();
-    
-    // Use chunk size optimization for contiguous dimensions
-    let mut chunk_size = 1;
-    let mut contiguous_dims = 0;
-    for dim in (0..rank).rev() {
-      if self_strides[dim] == chunk_size && other_strides[dim] == chunk_size {
-        chunk_size *= shape[dim];
-        contiguous_dims += 1;
-      } else {
-        break;
-      }
-    }
-    
-    let outer_dims = rank - contiguous_dims;
-    let mut indices = vec![0; outer_dims];
-    
-    // Process chunks
-    let chunks = total_elements / chunk_size;
-    for chunk_idx in 0..chunks {
-      // Calculate base indices for the chunk
-      let mut self_base_idx = 0;
-      let mut other_base_idx = 0;
-      
-      for (dim, &idx) in indices.iter().enumerate() {
-        self_base_idx += idx * self_strides[dim];
-        other_base_idx += idx * other_strides[dim];
-      }
-      
-      // Process the entire chunk
-      let result_start = chunk_idx * chunk_size;
-      for i in 0..chunk_size {
-        let self_val = self_data[self_base_idx + i];
-        let other_val = other_data[other_base_idx + i];
-        result[result_start + i] = op(self_val, other_val);
-      }
-      
-      // Update indices for outer dimensions
-      for dim in (0..outer_dims).rev() {
-        indices[dim] += 1;
-        if indices[dim] < shape[dim] {
-          break;
-        }
-        indices[dim] = 0;
-      }
-    }
-
-    Self::new(result, self.shape().clone())
+    let result = zip_values::<f32, _>(self, other, op);
+    Self::new(result, self.shape().clone()).to_dtype(self.dtype())
   }
 
   fn scalar_op<F>(&self, scalar: f32, op: F) -> Self
   where
     F: Fn(f32, f32) -> f32,
   {
-    let data = self.data().read().unwrap().iter()
-      .map(|a| op(*a, scalar))
+    let data = strided_values(self).into_iter()
+      .map(|a| op(a, scalar))
       .collect();
 
-    Self::new(data, self.shape().clone())
+    Self::new(data, self.shape().clone()).to_dtype(self.dtype())
   }
 
   fn sum_dim(&self, dims: &[bool]) -> Self {
@@ -234,7 +125,7 @@ impl TransformOps for CpuStorage {
 
     // If all dimensions are summed, return scalar
     if new_shape.is_empty() {
-        let sum: f32 = self.data().read().unwrap().iter().sum();
+        let sum: f32 = strided_values::<f32>(self).iter().sum();
         return Self::new(vec![sum], vec![1]);
     }
 
@@ -247,10 +138,8 @@ impl TransformOps for CpuStorage {
     
     // Sum values maintaining non-summed dimensions
     let mut sum = 0.0;
-    let binding = self.data();
-    let data = binding.read().unwrap();
-    for i in 0..data.len() {
-        sum += data[i];
+    for value in strided_values::<f32>(self) {
+        sum += value;
     }
     result[0] = sum;
 
@@ -267,7 +156,7 @@ impl TransformOps for CpuStorage {
     let mut stride = self.stride().to_owned();
     stride.reverse();
 
-    Self::create(self.data(), shape, stride)
+    self.restride(shape, stride, self.offset())
   }
 
   fn broadcast(&self, new_shape: &[usize]) -> Self {
@@ -277,7 +166,7 @@ impl TransformOps for CpuStorage {
     // Calculate new strides for broadcasting
     let broadcast_strides = self.compute_broadcast_strides(&broadcast_shape);
 
-    Self::create(self.data(), broadcast_shape, broadcast_strides)
+    self.restride(broadcast_shape, broadcast_strides, self.offset())
   }
 
   /// Compute broadcast shape between two shapes
@@ -353,4 +242,356 @@ impl TransformOps for CpuStorage {
     let broadcast_b = b.broadcast(&broadcast_shape);
     (broadcast_a, broadcast_b)
   }
+
+  fn index_select(&self, dim: usize, indices: &[usize]) -> Self {
+    check_dim(self.shape(), dim);
+    let (outer, axis_len, inner) = split_at_dim(self.shape(), dim);
+    let mut shape = self.shape().clone();
+    shape[dim] = indices.len();
+
+    with_dtype!(self.dtype(), T => {
+      let data = strided_values::<T>(self);
+      let mut result = Vec::with_capacity(outer * indices.len() * inner);
+      for o in 0..outer {
+        for &idx in indices {
+          if idx >= axis_len {
+            panic!("index_select: index {} out of bounds for dimension {} of size {}", idx, dim, axis_len);
+          }
+          let start = (o * axis_len + idx) * inner;
+          result.extend_from_slice(&data[start..start + inner]);
+        }
+      }
+      Self::from_vec(result, shape)
+    })
+  }
+
+  fn index_add(&self, dim: usize, indices: &[usize], source: &Self) -> Self {
+    check_dim(self.shape(), dim);
+    let mut expected = self.shape().clone();
+    expected[dim] = indices.len();
+    if source.shape() != &expected {
+      panic!("index_add: source must have shape {:?}, got {:?}", expected, source.shape());
+    }
+
+    let (outer, axis_len, inner) = split_at_dim(self.shape(), dim);
+    let mut result = strided_values::<f64>(self);
+    let source = strided_values::<f64>(source);
+
+    for o in 0..outer {
+      for (k, &idx) in indices.iter().enumerate() {
+        if idx >= axis_len {
+          panic!("index_add: index {} out of bounds for dimension {} of size {}", idx, dim, axis_len);
+        }
+        let dst = (o * axis_len + idx) * inner;
+        let src = (o * indices.len() + k) * inner;
+        for i in 0..inner {
+          result[dst + i] += source[src + i];
+        }
+      }
+    }
+
+    Self::from_vec(result, self.shape().clone()).to_dtype(self.dtype())
+  }
+
+  fn gather(&self, dim: usize, index: &Self) -> Self {
+    check_index_shape("gather", self.shape(), index.shape(), dim);
+    let mut indices = strided_values::<f64>(index).into_iter();
+
+    with_dtype!(self.dtype(), T => {
+      let data = T::unwrap(self.buffer()).unwrap().read().unwrap();
+      let mut result = Vec::with_capacity(index.shape().iter().product());
+      for_each_index(index.shape(), |position| {
+        let idx = checked_index("gather", indices.next().unwrap(), self.shape()[dim]);
+        let mut flat = self.offset();
+        for (d, &p) in position.iter().enumerate() {
+          flat += if d == dim { idx } else { p } * self.stride()[d];
+        }
+        result.push(data[flat]);
+      });
+      Self::from_vec(result, index.shape().clone())
+    })
+  }
+
+  fn scatter(&self, dim: usize, index: &Self, src: &Self) -> Self {
+    with_dtype!(self.dtype(), T => scatter_with::<T, _>(self, dim, index, src, "scatter", |slot, value| *slot = value))
+  }
+
+  fn scatter_add(&self, dim: usize, index: &Self, src: &Self) -> Self {
+    scatter_with::<f64, _>(self, dim, index, src, "scatter_add", |slot, value| *slot += value).to_dtype(self.dtype())
+  }
+
+  fn contiguous(&self) -> Self {
+    let len: usize = self.shape().iter().product();
+    if self.is_contiguous() && self.offset() == 0 && self.buffer().len() == len {
+      return self.clone();
+    }
+    with_dtype!(self.dtype(), T => Self::from_vec(strided_values::<T>(self), self.shape().clone()))
+  }
+
+  fn slice(&self, args: &[SliceArg]) -> Self {
+    if args.len() > self.shape().len() {
+      panic!("Too many slice arguments ({}) for tensor of rank {}", args.len(), self.shape().len());
+    }
+
+    let mut shape = Vec::with_capacity(self.shape().len());
+    let mut stride = Vec::with_capacity(self.shape().len());
+    let mut offset = self.offset();
+
+    for (dim, (&size, &dim_stride)) in self.shape().iter().zip(self.stride()).enumerate() {
+      let arg = args.get(dim).copied().unwrap_or_else(SliceArg::full);
+      let (start, len, step) = arg.resolve(size);
+      offset += start * dim_stride;
+      if let SliceArg::Range { .. } = arg {
+        shape.push(len);
+        stride.push(dim_stride * step);
+      }
+    }
+
+    // Selecting every dimension yields a single element, kept as shape [1]
+    if shape.is_empty() {
+      shape.push(1);
+      stride.push(1);
+    }
+
+    self.restride(shape, stride, offset)
+  }
+
+  fn narrow(&self, dim: usize, start: usize, length: usize) -> Self {
+    check_dim(self.shape(), dim);
+    if start + length > self.shape()[dim] {
+      panic!("narrow: range {}..{} out of bounds for dimension {} of size {}", start, start + length, dim, self.shape()[dim]);
+    }
+    self.slice(&SliceArg::narrow(dim, start, length))
+  }
+
+  fn select(&self, dim: usize, index: usize) -> Self {
+    check_dim(self.shape(), dim);
+    self.slice(&SliceArg::select(dim, index))
+  }
+
+  fn slice_scatter(&self, args: &[SliceArg], src: &Self) -> Self {
+    with_dtype!(self.dtype(), T => {
+      let result = Self::from_vec(strided_values::<T>(self), self.shape().clone());
+      let region = result.slice(args);
+      if region.shape() != src.shape() {
+        panic!("slice_scatter: src shape {:?} does not match slice shape {:?}", src.shape(), region.shape());
+      }
+
+      {
+        let mut data = T::unwrap(result.buffer()).unwrap().write().unwrap();
+        let mut values = strided_values::<T>(src).into_iter();
+        for_each_index(region.shape(), |position| {
+          let flat: usize = position.iter().zip(region.stride()).map(|(p, s)| p * s).sum();
+          data[region.offset() + flat] = values.next().unwrap();
+        });
+      }
+
+      result
+    })
+  }
+
+  fn masked_select(&self, mask: &Self) -> Self {
+    let mask = strided_values::<bool>(&mask.broadcast(self.shape()));
+    with_dtype!(self.dtype(), T => {
+      let values: Vec<T> = strided_values::<T>(self).into_iter()
+        .zip(mask)
+        .filter(|&(_, keep)| keep)
+        .map(|(value, _)| value)
+        .collect();
+
+      let len = values.len();
+      Self::from_vec(values, vec![len])
+    })
+  }
+
+  fn masked_scatter(&self, mask: &Self, source: &Self) -> Self {
+    let mask = strided_values::<bool>(&mask.broadcast(self.shape()));
+    with_dtype!(self.dtype(), T => {
+      let source = strided_values::<T>(source);
+      let mut result = strided_values::<T>(self);
+
+      let mut next = 0;
+      for (value, &keep) in result.iter_mut().zip(mask.iter()) {
+        if keep {
+          if next >= source.len() {
+            panic!("masked_scatter: source has fewer elements than the mask selects");
+          }
+          *value = source[next];
+          next += 1;
+        }
+      }
+
+      Self::from_vec(result, self.shape().clone())
+    })
+  }
+
+  fn take(&self, dim: usize, indices: &Self) -> Self {
+    check_dim(self.shape(), dim);
+    let indices = take_indices(indices, self.shape()[dim]);
+
+    let selected = self.index_select(dim, &indices.0);
+    let mut shape = self.shape()[..dim].to_vec();
+    shape.extend_from_slice(&indices.1);
+    shape.extend_from_slice(&self.shape()[dim + 1..]);
+    if shape.is_empty() {
+      shape.push(1);
+    }
+    selected.view(shape)
+  }
+
+  fn cat(tensors: &[&Self], dim: usize) -> Self {
+    let first = tensors.first().expect("cat: expected at least one tensor");
+    check_dim(first.shape(), dim);
+    for tensor in tensors {
+      let compatible = tensor.shape().len() == first.shape().len()
+        && tensor.shape().iter().zip(first.shape()).enumerate().all(|(d, (a, b))| d == dim || a == b);
+      if !compatible {
+        panic!("cat: shapes {:?} and {:?} differ outside of dimension {}", first.shape(), tensor.shape(), dim);
+      }
+    }
+
+    let (outer, _, inner) = split_at_dim(first.shape(), dim);
+    let mut shape = first.shape().clone();
+    shape[dim] = tensors.iter().map(|tensor| tensor.shape()[dim]).sum();
+
+    // Mixed inputs are promoted to a common dtype
+    let dtype = tensors.iter().map(|tensor| tensor.dtype()).fold(first.dtype(), DType::promote);
+    with_dtype!(dtype, T => {
+      let values: Vec<Vec<T>> = tensors.iter().map(|tensor| strided_values::<T>(tensor)).collect();
+
+      let mut result = Vec::with_capacity(values.iter().map(|v| v.len()).sum());
+      for o in 0..outer {
+        for (tensor, data) in tensors.iter().zip(values.iter()) {
+          let block = tensor.shape()[dim] * inner;
+          result.extend_from_slice(&data[o * block..(o + 1) * block]);
+        }
+      }
+      Self::from_vec(result, shape)
+    })
+  }
+
+  fn stack(tensors: &[&Self], dim: usize) -> Self {
+    let first = tensors.first().expect("stack: expected at least one tensor");
+    if dim > first.shape().len() {
+      panic!("stack: dimension {} out of range for tensors of rank {}", dim, first.shape().len());
+    }
+    if tensors.iter().any(|tensor| tensor.shape() != first.shape()) {
+      panic!("stack: all tensors must have the same shape");
+    }
+
+    let expanded: Vec<Self> = tensors.iter().map(|tensor| {
+      let mut expanded = (*tensor).clone();
+      expanded.unsqueeze(dim);
+      expanded
+    }).collect();
+    let expanded: Vec<&Self> = expanded.iter().collect();
+    Self::cat(&expanded, dim)
+  }
+
+  fn split(&self, split_size: usize, dim: usize) -> Vec<Self> {
+    check_dim(self.shape(), dim);
+    if split_size == 0 {
+      panic!("split: split size must be positive");
+    }
+    let len = self.shape()[dim];
+    let sizes: Vec<usize> = (0..len).step_by(split_size).map(|start| split_size.min(len - start)).collect();
+    self.split_with_sizes(&sizes, dim)
+  }
+
+  fn split_with_sizes(&self, sizes: &[usize], dim: usize) -> Vec<Self> {
+    check_dim(self.shape(), dim);
+    if sizes.iter().sum::<usize>() != self.shape()[dim] {
+      panic!("split_with_sizes: sizes {:?} don't add up to dimension {} of size {}", sizes, dim, self.shape()[dim]);
+    }
+    let mut start = 0;
+    sizes.iter().map(|&size| {
+      let piece = self.narrow(dim, start, size);
+      start += size;
+      piece
+    }).collect()
+  }
+
+  fn chunk(&self, chunks: usize, dim: usize) -> Vec<Self> {
+    check_dim(self.shape(), dim);
+    if chunks == 0 {
+      panic!("chunk: number of chunks must be positive");
+    }
+    let len = self.shape()[dim];
+    self.split(len.div_ceil(chunks).max(1), dim)
+  }
+
+  fn unbind(&self, dim: usize) -> Vec<Self> {
+    check_dim(self.shape(), dim);
+    (0..self.shape()[dim]).map(|index| self.select(dim, index)).collect()
+  }
+}
+
+/// Flattens an index tensor into positions (negative values count from the end),
+/// returning them with the index tensor's shape.
+fn take_indices(indices: &CpuStorage, size: usize) -> (Vec<usize>, Vec<usize>) {
+  let positions = strided_values::<f64>(indices).into_iter()
+    .map(|value| SliceArg::Index(value as isize).resolve(size).0)
+    .collect();
+  (positions, indices.shape().clone())
+}
+
+/// Sizes of the dimensions before, at and after `dim`.
+fn split_at_dim(shape: &[usize], dim: usize) -> (usize, usize, usize) {
+  let outer = shape[..dim].iter().product();
+  let inner = shape[dim + 1..].iter().product();
+  (outer, shape[dim], inner)
+}
+
+fn check_dim(shape: &[usize], dim: usize) {
+  if dim >= shape.len() {
+    panic!("Dimension {} out of range for tensor of rank {}", dim, shape.len());
+  }
+}
+
+/// `index` must have the same rank as `shape` and may not exceed it outside of `dim`.
+fn check_index_shape(op: &str, shape: &[usize], index_shape: &[usize], dim: usize) {
+  check_dim(shape, dim);
+  if index_shape.len() != shape.len() {
+    panic!("{}: index must have the same number of dimensions as the input", op);
+  }
+  for d in 0..shape.len() {
+    if d != dim && index_shape[d] > shape[d] {
+      panic!("{}: index shape {:?} exceeds input shape {:?} outside of dimension {}", op, index_shape, shape, dim);
+    }
+  }
+}
+
+fn checked_index(op: &str, value: f64, size: usize) -> usize {
+  if value < 0. || value as usize >= size || value.fract() != 0. {
+    panic!("{}: index {} out of bounds for dimension of size {}", op, value, size);
+  }
+  value as usize
+}
+
+fn scatter_with<T: Element, F>(input: &CpuStorage, dim: usize, index: &CpuStorage, src: &CpuStorage, op: &str, combine: F) -> CpuStorage
+where
+  F: Fn(&mut T, T),
+{
+  check_index_shape(op, input.shape(), index.shape(), dim);
+  if src.shape().len() != index.shape().len() || src.shape().iter().zip(index.shape()).any(|(s, i)| s < i) {
+    panic!("{}: src shape {:?} must cover index shape {:?}", op, src.shape(), index.shape());
+  }
+
+  let mut result = strided_values::<T>(input);
+  let stride = CpuStorage::compute_strides(input.shape());
+  let src = src.to_dtype(T::DTYPE);
+  let src_data = T::unwrap(src.buffer()).unwrap().read().unwrap();
+  let mut indices = strided_values::<f64>(index).into_iter();
+
+  for_each_index(index.shape(), |position| {
+    let idx = checked_index(op, indices.next().unwrap(), input.shape()[dim]);
+    let mut flat = 0;
+    for (d, &p) in position.iter().enumerate() {
+      flat += if d == dim { idx } else { p } * stride[d];
+    }
+    let src_flat: usize = src.offset() + position.iter().zip(src.stride()).map(|(p, s)| p * s).sum::<usize>();
+    combine(&mut result[flat], src_data[src_flat]);
+  });
+
+  CpuStorage::from_vec(result, input.shape().clone())
 }
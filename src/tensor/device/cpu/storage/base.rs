@@ -2,11 +2,13 @@ use std::sync::{Arc, RwLock};
 use crate::*;
 use ndarray::{ArrayBase, Dimension};
 use num_traits::cast::AsPrimitive;
-use rand::distributions::{Distribution, Uniform};
+use rand::distributions::{Bernoulli, Distribution, Uniform, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand_distr::Normal;
 
 #[derive(Clone)]
 pub struct CpuStorage {
-    data: Arc<RwLock<Vec<f32>>>,
+    data: CpuBuffer,
     shape: Vec<usize>,
     stride: Vec<usize>,
     offset: usize,
@@ -15,15 +17,12 @@ pub struct CpuStorage {
 impl DeviceStorageStatic for CpuStorage {
     fn new(data: Vec<f32>, shape: Vec<usize>) -> Self {
         // Check that the data length matches the product of shape dimensions.
-        if data.len() != shape.iter().product::<usize>() {
-            let x: usize = shape.iter().product::<usize>();
-            println!("Data Len: {}. Shape iter prod {}", data.len(), x);
-            println!("Data: {:?}", data);
-            panic!("Data does not match shape!");
+        if let Err(error) = check_data_len("new", data.len(), &shape) {
+            panic!("{}", error);
         }
         let stride = CpuStorage::compute_strides(&shape);
         CpuStorage {
-            data: Arc::new(RwLock::new(data)),
+            data: CpuBuffer::new(data),
             shape: shape,
             stride: stride,
             offset: 0,
@@ -31,11 +30,11 @@ impl DeviceStorageStatic for CpuStorage {
     }
 
     fn new_with_stride(data: Vec<f32>, shape: Vec<usize>, stride: Vec<usize>) -> Self {
-        if data.len() != shape.iter().product::<usize>() {
-            panic!("Data does not match shape!");
+        if let Err(error) = check_data_len("new_with_stride", data.len(), &shape) {
+            panic!("{}", error);
         }
         CpuStorage {
-            data: Arc::new(RwLock::new(data)),
+            data: CpuBuffer::new(data),
             shape: shape,
             stride: stride,
             offset: 0,
@@ -44,7 +43,7 @@ impl DeviceStorageStatic for CpuStorage {
 
     fn create(data: Arc<RwLock<Vec<f32>>>, shape: Vec<usize>, stride: Vec<usize>) -> Self {
         CpuStorage {
-            data: data,
+            data: CpuBuffer::F32(data),
             shape: shape,
             stride: stride,
             offset: 0,
@@ -53,7 +52,7 @@ impl DeviceStorageStatic for CpuStorage {
 
     fn compute_strides(shape: &Vec<usize>) -> Vec<usize> {
         let mut stride = vec![1; shape.len()];
-        for i in (0..shape.len() - 1).rev() {
+        for i in (0..shape.len().saturating_sub(1)).rev() {
             stride[i] = stride[i + 1] * shape[i + 1];
         }
         stride
@@ -95,14 +94,194 @@ impl DeviceStorageCreation for CpuStorage {
         shape: Vec<usize>,
         _device: Option<Device>,
         _requires_grad: Option<bool>,
+        generator: Option<&Generator>,
     ) -> Self {
         let uniform = Uniform::from(l_bound..r_bound); // Create a uniform distribution
-        let mut rng = rand::thread_rng(); // Random number generator
-        let data = (0..shape.iter().product())
-            .map(|_| uniform.sample(&mut rng)) // Sample from the uniform distribution
-            .collect();
+        let generator = generator_or_default(generator);
+        let data = generator.with_rng(|rng| {
+            (0..shape.iter().product())
+                .map(|_| uniform.sample(rng)) // Sample from the uniform distribution
+                .collect()
+        });
         CpuStorage::new(data, shape)
     }
+
+    fn full(shape: Vec<usize>, value: f32, _device: Option<Device>, _requires_grad: Option<bool>) -> Self {
+        let size = shape.iter().product();
+        CpuStorage::new(vec![value; size], shape)
+    }
+
+    fn arange(start: f32, end: f32, step: f32, _device: Option<Device>, _requires_grad: Option<bool>) -> Self {
+        if let Err(error) = check_arange(start, end, step) {
+            panic!("{}", error);
+        }
+        let len = ((end - start) / step).ceil().max(0.) as usize;
+        let data: Vec<f32> = (0..len).map(|i| start + i as f32 * step).collect();
+        CpuStorage::new(data, vec![len])
+    }
+
+    fn linspace(start: f32, end: f32, steps: usize, _device: Option<Device>, _requires_grad: Option<bool>) -> Self {
+        let data = match steps {
+            0 => panic!("{}", FerriteError::invalid("linspace", "number of steps must be positive")),
+            1 => vec![start],
+            _ => {
+                let step = (end - start) / (steps - 1) as f32;
+                (0..steps).map(|i| start + i as f32 * step).collect()
+            }
+        };
+        CpuStorage::new(data, vec![steps])
+    }
+
+    fn eye(n: usize, _device: Option<Device>, _requires_grad: Option<bool>) -> Self {
+        let mut data = vec![0.0; n * n];
+        for i in 0..n {
+            data[i * n + i] = 1.0;
+        }
+        CpuStorage::new(data, vec![n, n])
+    }
+
+    fn normal(
+        mean: f32,
+        std: f32,
+        shape: Vec<usize>,
+        _device: Option<Device>,
+        _requires_grad: Option<bool>,
+        generator: Option<&Generator>,
+    ) -> Self {
+        let normal = Normal::new(mean, std).expect("normal: standard deviation must be finite and non-negative");
+        let generator = generator_or_default(generator);
+        let data = generator.with_rng(|rng| {
+            (0..shape.iter().product())
+                .map(|_| normal.sample(rng))
+                .collect()
+        });
+        CpuStorage::new(data, shape)
+    }
+
+    fn bernoulli(
+        p: f32,
+        shape: Vec<usize>,
+        _device: Option<Device>,
+        _requires_grad: Option<bool>,
+        generator: Option<&Generator>,
+    ) -> Self {
+        if let Err(error) = check_probability("bernoulli", p) {
+            panic!("{}", error);
+        }
+        let bernoulli = Bernoulli::new(p as f64).unwrap();
+        let generator = generator_or_default(generator);
+        let data = generator.with_rng(|rng| {
+            (0..shape.iter().product())
+                .map(|_| if bernoulli.sample(rng) { 1.0 } else { 0.0 })
+                .collect()
+        });
+        CpuStorage::new(data, shape)
+    }
+
+    fn randint(
+        low: i64,
+        high: i64,
+        shape: Vec<usize>,
+        _device: Option<Device>,
+        _requires_grad: Option<bool>,
+        generator: Option<&Generator>,
+    ) -> Self {
+        if let Err(error) = check_randint(low, high) {
+            panic!("{}", error);
+        }
+        let uniform = Uniform::new(low, high);
+        let generator = generator_or_default(generator);
+        let data = generator.with_rng(|rng| {
+            (0..shape.iter().product())
+                .map(|_| uniform.sample(rng))
+                .collect()
+        });
+        CpuStorage::from_vec::<i64>(data, shape)
+    }
+
+    fn randperm(n: usize, _device: Option<Device>, _requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+        let mut data: Vec<i64> = (0..n as i64).collect();
+        let generator = generator_or_default(generator);
+        generator.with_rng(|rng| data.shuffle(rng));
+        CpuStorage::from_vec(data, vec![n])
+    }
+
+    fn multinomial(probs: &Self, num_samples: usize, replacement: bool, generator: Option<&Generator>) -> Self {
+        if let Err(error) = check_multinomial(probs, num_samples, replacement) {
+            panic!("{}", error);
+        }
+        let (rows, classes) = match probs.shape().len() {
+            1 => (1, probs.shape()[0]),
+            _ => (probs.shape()[0], probs.shape()[1]),
+        };
+        let generator = generator_or_default(generator);
+
+        let mut data = Vec::with_capacity(rows * num_samples);
+        for i in 0..rows {
+            let mut weights: Vec<f32> = (0..classes)
+                .map(|c| if rows == 1 && probs.shape().len() == 1 { probs.get(&[c]) } else { probs.get(&[i, c]) })
+                .collect();
+            let mut dist = WeightedIndex::new(&weights).expect("multinomial: probabilities must not sum to zero");
+            generator.with_rng(|rng| {
+                for _ in 0..num_samples {
+                    let class = dist.sample(rng);
+                    data.push(class as i64);
+                    if !replacement {
+                        // Remove the drawn category from the remaining draws
+                        weights[class] = 0.;
+                        if let Ok(updated) = WeightedIndex::new(&weights) {
+                            dist = updated;
+                        }
+                    }
+                }
+            });
+        }
+
+        let shape = if probs.shape().len() == 1 { vec![num_samples] } else { vec![rows, num_samples] };
+        CpuStorage::from_vec(data, shape)
+    }
+}
+
+impl CpuStorage {
+    /// Creates a contiguous storage holding `data` with its native dtype.
+    pub fn from_vec<T: Element>(data: Vec<T>, shape: Vec<usize>) -> Self {
+        if let Err(error) = check_data_len("from_vec", data.len(), &shape) {
+            panic!("{}", error);
+        }
+        let stride = CpuStorage::compute_strides(&shape);
+        CpuStorage {
+            data: CpuBuffer::new(data),
+            shape,
+            stride,
+            offset: 0,
+        }
+    }
+
+    pub fn buffer(&self) -> &CpuBuffer {
+        &self.data
+    }
+
+    /// A view of the same buffer with a different layout.
+    pub(crate) fn restride(&self, shape: Vec<usize>, stride: Vec<usize>, offset: usize) -> Self {
+        CpuStorage {
+            data: self.data.clone(),
+            shape,
+            stride,
+            offset,
+        }
+    }
+
+    /// Replaces the data with a freshly computed row-major buffer for the current
+    /// shape, dropping any view strides or offset. The storage keeps its dtype.
+    pub(crate) fn set_contiguous_data<T: Element>(&mut self, data: Vec<T>) {
+        let dtype = self.dtype();
+        self.stride = CpuStorage::compute_strides(&self.shape);
+        self.offset = 0;
+        self.data = CpuBuffer::new(data);
+        if T::DTYPE != dtype {
+            *self = self.to_dtype(dtype);
+        }
+    }
 }
 
 impl DeviceStorage for CpuStorage {
@@ -112,9 +291,13 @@ impl DeviceStorage for CpuStorage {
         if total_elements != self.shape.iter().product::<usize>() {
             panic!("New shape must have the same number of elements");
         }
+        // Strided views can't be reinterpreted in place, so copy them first
+        if !self.is_contiguous() {
+            return self.contiguous().view(new_shape);
+        }
         let stride = CpuStorage::compute_strides(&new_shape);
         CpuStorage {
-            data: Arc::clone(&self.data),
+            data: self.data.clone(),
             shape: new_shape,
             stride: stride,
             offset: self.offset,
@@ -122,15 +305,32 @@ impl DeviceStorage for CpuStorage {
     }
 
     fn data(&self) -> Arc<RwLock<Vec<f32>>> {
-        Arc::clone(&self.data)
+        match &self.data {
+            CpuBuffer::F32(data) => Arc::clone(data),
+            _ => panic!("{}", FerriteError::DTypeMismatch { op: "data", expected: DType::F32, found: self.dtype() }),
+        }
     }
 
     fn data_mut(&self) -> std::sync::RwLockWriteGuard<Vec<f32>> {
-        self.data.write().unwrap()
+        match &self.data {
+            CpuBuffer::F32(data) => data.write().unwrap(),
+            _ => panic!("{}", FerriteError::DTypeMismatch { op: "data_mut", expected: DType::F32, found: self.dtype() }),
+        }
     }
 
     fn set_data(&mut self, data: Vec<f32>) {
-        self.data = Arc::new(RwLock::new(data));
+        self.data = CpuBuffer::new(data);
+    }
+
+    fn dtype(&self) -> DType {
+        self.data.dtype()
+    }
+
+    fn to_dtype(&self, dtype: DType) -> Self {
+        if dtype == self.dtype() {
+            return self.clone();
+        }
+        with_dtype!(dtype, T => CpuStorage::from_vec(strided_values::<T>(self), self.shape.clone()))
     }
 
     fn shape(&self) -> &Vec<usize> {
@@ -153,57 +353,44 @@ impl DeviceStorage for CpuStorage {
         self.offset
     }
 
+    fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+
+    fn device(&self) -> Device {
+        Device::Cpu
+    }
+
     fn get(&self, indices: &[usize]) -> f32 {
-        // Ensure the number of indices matches the tensor's dimensions.
-        if indices.len() != self.shape.len() {
-            panic!("Tensor index does not match shape!");
+        // Ensure the indices match the tensor's dimensions.
+        if let Err(error) = check_index("get", &self.shape, indices) {
+            panic!("{}", error);
         }
         // Compute the flat index.
-        let mut flat_index = 0;
-        for (i, &idx) in indices.iter().enumerate() {
-            if idx >= self.shape[i] {
-                panic!("Tensor index out of bounds!");
-            }
-            flat_index += idx * self.stride[i];
-        }
+        let flat_index = self.offset + indices.iter().zip(&self.stride).map(|(idx, stride)| idx * stride).sum::<usize>();
         // Use a read lock for safe concurrent access.
-        let data = self.data.read().unwrap();
-        data[flat_index]
+        with_dtype!(self.dtype(), T => T::unwrap(&self.data).unwrap().read().unwrap()[flat_index].to_f64() as f32)
     }
 
     fn set(&mut self, indices: &[usize], value: f32) {
-        if indices.len() != self.shape.len() {
-            panic!("Tensor index does not match shape!");
-        }
-        let mut flat_index = 0;
-        for (i, &idx) in indices.iter().enumerate() {
-            if idx >= self.shape[i] {
-                panic!("Tensor index out of bounds!");
-            }
-            flat_index += idx * self.stride[i];
+        if let Err(error) = check_index("set", &self.shape, indices) {
+            panic!("{}", error);
         }
+        let flat_index = self.offset + indices.iter().zip(&self.stride).map(|(idx, stride)| idx * stride).sum::<usize>();
         // Acquire a write lock for mutation.
-        let mut data = self.data.write().unwrap();
-        data[flat_index] = value;
+        with_dtype!(self.dtype(), T => T::unwrap(&self.data).unwrap().write().unwrap()[flat_index] = T::from_f64(value as f64))
     }
 
     fn make_contiguous(&self) -> (Vec<f32>, i32) {
-        if self.is_contiguous() {
-            return (self.data.read().unwrap().clone(), self.shape[1] as i32);
-        }
-        let mut contiguous = vec![0.0; self.shape.iter().product()];
-        for i in 0..self.shape[0] {
-            for j in 0..self.shape[1] {
-                contiguous[i * self.shape[1] + j] = self.get(&[i, j]);
-            }
-        }
-        (contiguous, self.shape[1] as i32)
+        let leading_dim = *self.shape.last().unwrap_or(&1) as i32;
+        (strided_values(self), leading_dim)
     }
 
     fn is_contiguous(&self) -> bool {
         let mut expected_stride = 1;
         for i in (0..self.shape.len()).rev() {
-            if self.stride[i] != expected_stride {
+            // The stride of a size-1 dimension is never used to step
+            if self.shape[i] != 1 && self.stride[i] != expected_stride {
                 return false;
             }
             expected_stride *= self.shape[i];
@@ -0,0 +1,167 @@
+use std::sync::{Arc, RwLock};
+use crate::*;
+
+
+/// Type-erased element buffer behind a `CpuStorage`. Views share the same `Arc`.
+#[derive(Clone, Debug)]
+pub enum CpuBuffer {
+  Bool(Arc<RwLock<Vec<bool>>>),
+  U8(Arc<RwLock<Vec<u8>>>),
+  I32(Arc<RwLock<Vec<i32>>>),
+  I64(Arc<RwLock<Vec<i64>>>),
+  F16(Arc<RwLock<Vec<f16>>>),
+  BF16(Arc<RwLock<Vec<bf16>>>),
+  F32(Arc<RwLock<Vec<f32>>>),
+  F64(Arc<RwLock<Vec<f64>>>),
+}
+
+impl CpuBuffer {
+  pub fn new<T: Element>(data: Vec<T>) -> Self {
+    T::wrap(Arc::new(RwLock::new(data)))
+  }
+
+  pub fn dtype(&self) -> DType {
+    match self {
+      CpuBuffer::Bool(_) => DType::Bool,
+      CpuBuffer::U8(_) => DType::U8,
+      CpuBuffer::I32(_) => DType::I32,
+      CpuBuffer::I64(_) => DType::I64,
+      CpuBuffer::F16(_) => DType::F16,
+      CpuBuffer::BF16(_) => DType::BF16,
+      CpuBuffer::F32(_) => DType::F32,
+      CpuBuffer::F64(_) => DType::F64,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    with_dtype!(self.dtype(), T => T::unwrap(self).unwrap().read().unwrap().len())
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+
+/// Copies the elements of `storage` into a row-major buffer, honouring its
+/// strides and offset and casting to `T` if the storage holds another dtype.
+pub(crate) fn strided_values<T: Element>(storage: &CpuStorage) -> Vec<T> {
+  if let Some(data) = T::unwrap(storage.buffer()) {
+    return gather_strided(&data.read().unwrap(), storage, |value| value);
+  }
+  with_dtype!(storage.dtype(), S => {
+    let data = S::unwrap(storage.buffer()).unwrap().read().unwrap();
+    gather_strided(&data, storage, |value: S| T::from_f64(value.to_f64()))
+  })
+}
+
+fn gather_strided<S: Element, T>(data: &[S], storage: &CpuStorage, cast: impl Fn(S) -> T) -> Vec<T> {
+  let len: usize = storage.shape().iter().product();
+  if storage.is_contiguous() {
+    return data[storage.offset()..storage.offset() + len].iter().map(|&value| cast(value)).collect();
+  }
+
+  let mut values = Vec::with_capacity(len);
+  for_each_index(storage.shape(), |position| {
+    let flat: usize = position.iter().zip(storage.stride()).map(|(p, s)| p * s).sum();
+    values.push(cast(data[storage.offset() + flat]));
+  });
+  values
+}
+
+/// Combines two storages of the same shape element by element in `T`, casting
+/// operands of another dtype first.
+pub(crate) fn zip_values<T: Element, F>(lhs: &CpuStorage, rhs: &CpuStorage, op: F) -> Vec<T>
+where
+  F: Fn(T, T) -> T,
+{
+  if lhs.dtype() != T::DTYPE {
+    return zip_values(&lhs.to_dtype(T::DTYPE), rhs, op);
+  }
+  if rhs.dtype() != T::DTYPE {
+    return zip_values(lhs, &rhs.to_dtype(T::DTYPE), op);
+  }
+
+  let total_elements = lhs.shape().iter().product();
+  let mut result = vec![T::default(); total_elements];
+
+  // Get data once to avoid multiple borrows
+  let lhs_data = T::unwrap(lhs.buffer()).unwrap().read().unwrap();
+  let rhs_data = T::unwrap(rhs.buffer()).unwrap().read().unwrap();
+
+  // Pre-calculate dimensions for faster access
+  let rank = lhs.shape().len();
+  let shape = lhs.shape();
+  let lhs_strides = lhs.stride();
+  let rhs_strides = rhs.stride();
+
+  // Use chunk size optimization for contiguous dimensions
+  let mut chunk_size = 1;
+  let mut contiguous_dims = 0;
+  for dim in (0..rank).rev() {
+    if lhs_strides[dim] == chunk_size && rhs_strides[dim] == chunk_size {
+      chunk_size *= shape[dim];
+      contiguous_dims += 1;
+    } else {
+      break;
+    }
+  }
+
+  let outer_dims = rank - contiguous_dims;
+  let mut indices = vec![0; outer_dims];
+
+  // Process chunks
+  let chunks = total_elements.checked_div(chunk_size).unwrap_or(0);
+  for chunk_idx in 0..chunks {
+    // Calculate base indices for the chunk
+    let mut lhs_base_idx = lhs.offset();
+    let mut rhs_base_idx = rhs.offset();
+
+    for (dim, &idx) in indices.iter().enumerate() {
+      lhs_base_idx += idx * lhs_strides[dim];
+      rhs_base_idx += idx * rhs_strides[dim];
+    }
+
+    // Process the entire chunk
+    let result_start = chunk_idx * chunk_size;
+    for i in 0..chunk_size {
+      result[result_start + i] = op(lhs_data[lhs_base_idx + i], rhs_data[rhs_base_idx + i]);
+    }
+
+    // Update indices for outer dimensions
+    for dim in (0..outer_dims).rev() {
+      indices[dim] += 1;
+      if indices[dim] < shape[dim] {
+        break;
+      }
+      indices[dim] = 0;
+    }
+  }
+
+  result
+}
+
+/// Calls `f` with every multi-index of `shape` in row-major order.
+pub(crate) fn for_each_index<F: FnMut(&[usize])>(shape: &[usize], mut f: F) {
+  if shape.contains(&0) {
+    return;
+  }
+
+  let mut position = vec![0; shape.len()];
+  loop {
+    f(&position);
+
+    let mut dim = shape.len();
+    loop {
+      if dim == 0 {
+        return;
+      }
+      dim -= 1;
+      position[dim] += 1;
+      if position[dim] < shape[dim] {
+        break;
+      }
+      position[dim] = 0;
+    }
+  }
+}
@@ -1,5 +1,7 @@
 mod base;
+mod buffer;
 mod utils;
 
 pub use base::*;
-pub use utils::*;
+pub use buffer::*;
+pub use utils::*;
\ No newline at end of file
@@ -4,30 +4,44 @@ use std::fmt;
 
 pub trait Display {
   fn print(&self);
-  fn print_data_recursive<'a>(data: &'a [f32], shape: &'a [usize], stride: &'a [usize]) -> String;
+  fn print_data_recursive<'a, T: fmt::Display>(data: &'a [T], shape: &'a [usize], stride: &'a [usize]) -> String;
   fn print_data(&self);
 }
 
+impl CpuStorage {
+  fn format_data(&self) -> String {
+    with_dtype!(self.dtype(), T => {
+      let data = T::unwrap(self.buffer()).unwrap().read().unwrap();
+      Self::print_data_recursive(&data[self.offset()..], self.shape(), self.stride())
+    })
+  }
+}
+
 impl fmt::Display for CpuStorage {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "{}", Self::print_data_recursive(&self.data().read().unwrap(), self.shape(), self.stride()))
+    write!(f, "{}", self.format_data())
   }
 }
 
 impl fmt::Debug for CpuStorage {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "{}", Self::print_data_recursive(&self.data().read().unwrap(), self.shape(), self.stride()))
+    // f32 is the default dtype, so only other dtypes are spelled out
+    match self.dtype() {
+      DType::F32 => write!(f, "{}", self.format_data()),
+      dtype => write!(f, "{}, dtype={}", self.format_data(), dtype),
+    }
   }
 }
 
 impl Display for CpuStorage {
   fn print(&self) {
-    println!("Data: {:?}", self.data());
+    println!("Data: {:?}", self.buffer());
+    println!("DType: {}", self.dtype());
     println!("Shape: {:?}", self.shape());
     println!("Strides: {:?}", self.stride());
   }
 
-  fn print_data_recursive<'a>(data: &'a [f32], shape: &'a [usize], stride: &'a [usize]) -> String {
+  fn print_data_recursive<'a, T: fmt::Display>(data: &'a [T], shape: &'a [usize], stride: &'a [usize]) -> String {
     let mut res = String::new();
     res += "[";
     if shape.len() == 1 {
@@ -55,7 +69,6 @@ impl Display for CpuStorage {
   }
 
   fn print_data(&self) {
-    let res = Self::print_data_recursive(&self.data().read().unwrap(), self.shape(), self.stride());
-    println!("{}", res);
+    println!("{}", self.format_data());
   }
 }
\ No newline at end of file
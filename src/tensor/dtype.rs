@@ -0,0 +1,184 @@
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use crate::CpuBuffer;
+
+pub use half::{bf16, f16};
+
+
+/// Element type of a tensor. Variants are ordered by promotion rank, so the
+/// result type of mixing two dtypes is the larger of the two.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub enum DType {
+  Bool,
+  U8,
+  I32,
+  I64,
+  F16,
+  BF16,
+  F32,
+  F64,
+}
+
+impl DType {
+  /// Result dtype of a binary op between `self` and `other`, e.g. `i64` and
+  /// `f32` promote to `f32`, `u8` and `i32` to `i32`. `f16` and `bf16` have no
+  /// common half format, so mixing them promotes to `f32`.
+  pub fn promote(self, other: DType) -> DType {
+    if self.is_half() && other.is_half() && self != other {
+      return DType::F32;
+    }
+    self.max(other)
+  }
+
+  pub fn is_floating_point(self) -> bool {
+    matches!(self, DType::F16 | DType::BF16 | DType::F32 | DType::F64)
+  }
+
+  /// Whether this is one of the 16-bit float formats.
+  pub fn is_half(self) -> bool {
+    matches!(self, DType::F16 | DType::BF16)
+  }
+
+  /// Float dtype that math on this dtype produces: floats are kept, integers
+  /// and bools become `f32`.
+  pub fn to_float(self) -> DType {
+    if self.is_floating_point() { self } else { DType::F32 }
+  }
+
+  /// Float dtype ops producing this dtype are evaluated in: `f32` and the half
+  /// formats accumulate in `f32`, everything else goes through `f64`, which is
+  /// exact for integers up to 2^53.
+  pub(crate) fn compute_dtype(self) -> DType {
+    if self == DType::F32 || self.is_half() { DType::F32 } else { DType::F64 }
+  }
+
+  /// Size of one element in bytes.
+  pub fn size(self) -> usize {
+    match self {
+      DType::Bool | DType::U8 => 1,
+      DType::F16 | DType::BF16 => 2,
+      DType::I32 | DType::F32 => 4,
+      DType::I64 | DType::F64 => 8,
+    }
+  }
+}
+
+impl fmt::Display for DType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      DType::Bool => "bool",
+      DType::U8 => "u8",
+      DType::I32 => "i32",
+      DType::I64 => "i64",
+      DType::F16 => "f16",
+      DType::BF16 => "bf16",
+      DType::F32 => "f32",
+      DType::F64 => "f64",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+
+/// A Rust type that can be stored in a tensor.
+pub trait Element: Copy + Default + PartialOrd + fmt::Debug + fmt::Display + Send + Sync + 'static {
+  const DTYPE: DType;
+
+  fn to_f64(self) -> f64;
+
+  /// Converts with `as` semantics: floats round towards zero and saturate when
+  /// cast to integers, and any non-zero value is `true`.
+  fn from_f64(value: f64) -> Self;
+
+  /// Wraps a buffer of this element type.
+  fn wrap(data: Arc<RwLock<Vec<Self>>>) -> CpuBuffer;
+
+  /// The buffer's data if it holds this element type.
+  fn unwrap(buffer: &CpuBuffer) -> Option<&Arc<RwLock<Vec<Self>>>>;
+}
+
+macro_rules! impl_element {
+  ($type:ty, $dtype:ident) => {
+    impl Element for $type {
+      const DTYPE: DType = DType::$dtype;
+
+      fn to_f64(self) -> f64 {
+        self as f64
+      }
+
+      fn from_f64(value: f64) -> Self {
+        value as $type
+      }
+
+      fn wrap(data: Arc<RwLock<Vec<Self>>>) -> CpuBuffer {
+        CpuBuffer::$dtype(data)
+      }
+
+      fn unwrap(buffer: &CpuBuffer) -> Option<&Arc<RwLock<Vec<Self>>>> {
+        match buffer {
+          CpuBuffer::$dtype(data) => Some(data),
+          _ => None,
+        }
+      }
+    }
+  };
+}
+
+impl_element!(f32, F32);
+impl_element!(f64, F64);
+impl_element!(i32, I32);
+impl_element!(i64, I64);
+impl_element!(u8, U8);
+
+macro_rules! impl_half_element {
+  ($type:ty, $dtype:ident) => {
+    impl Element for $type {
+      const DTYPE: DType = DType::$dtype;
+
+      fn to_f64(self) -> f64 {
+        <$type>::to_f64(self)
+      }
+
+      fn from_f64(value: f64) -> Self {
+        <$type>::from_f64(value)
+      }
+
+      fn wrap(data: Arc<RwLock<Vec<Self>>>) -> CpuBuffer {
+        CpuBuffer::$dtype(data)
+      }
+
+      fn unwrap(buffer: &CpuBuffer) -> Option<&Arc<RwLock<Vec<Self>>>> {
+        match buffer {
+          CpuBuffer::$dtype(data) => Some(data),
+          _ => None,
+        }
+      }
+    }
+  };
+}
+
+impl_half_element!(f16, F16);
+impl_half_element!(bf16, BF16);
+
+impl Element for bool {
+  const DTYPE: DType = DType::Bool;
+
+  fn to_f64(self) -> f64 {
+    if self { 1.0 } else { 0.0 }
+  }
+
+  fn from_f64(value: f64) -> Self {
+    value != 0.0
+  }
+
+  fn wrap(data: Arc<RwLock<Vec<Self>>>) -> CpuBuffer {
+    CpuBuffer::Bool(data)
+  }
+
+  fn unwrap(buffer: &CpuBuffer) -> Option<&Arc<RwLock<Vec<Self>>>> {
+    match buffer {
+      CpuBuffer::Bool(data) => Some(data),
+      _ => None,
+    }
+  }
+}
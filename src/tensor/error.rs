@@ -0,0 +1,68 @@
+use std::fmt;
+use crate::{DType, Device};
+
+
+/// Errors reported by the fallible `try_*` APIs. The panicking counterparts
+/// fail with the same messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FerriteError {
+  /// A shape did not match the one the operation required.
+  ShapeMismatch { op: &'static str, expected: Vec<usize>, found: Vec<usize> },
+  /// Two shapes cannot be broadcast against each other.
+  BroadcastError { op: &'static str, lhs: Vec<usize>, rhs: Vec<usize> },
+  /// An index (or the end of a range) lies outside dimension `dim`.
+  IndexOutOfBounds { op: &'static str, dim: usize, index: usize, size: usize },
+  /// A dimension argument is not smaller than the tensor's rank.
+  DimOutOfRange { op: &'static str, dim: usize, rank: usize },
+  /// The storage holds a different element type than the operation needs.
+  DTypeMismatch { op: &'static str, expected: DType, found: DType },
+  /// Operands live on different devices.
+  DeviceMismatch { op: &'static str, lhs: Device, rhs: Device },
+  /// A gradient was requested from a tensor that does not track one.
+  GradNotEnabled { op: &'static str },
+  /// `backward()` needs a single-element output (or an explicit output gradient).
+  NonScalarBackward { shape: Vec<usize> },
+  /// The graph was already freed by a backward pass without `retain_graph`.
+  GraphReleased { op: &'static str },
+  /// Any other invalid argument.
+  InvalidArgument { op: &'static str, message: String },
+  /// The operation has no kernel for this device yet.
+  Unimplemented { op: &'static str, device: Device },
+}
+
+impl fmt::Display for FerriteError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FerriteError::ShapeMismatch { op, expected, found } =>
+        write!(f, "{}: expected shape {:?}, got {:?}", op, expected, found),
+      FerriteError::BroadcastError { op, lhs, rhs } =>
+        write!(f, "{}: shapes {:?} and {:?} cannot be broadcast together", op, lhs, rhs),
+      FerriteError::IndexOutOfBounds { op, dim, index, size } =>
+        write!(f, "{}: index {} is out of bounds for dimension {} with size {}", op, index, dim, size),
+      FerriteError::DimOutOfRange { op, dim, rank } =>
+        write!(f, "{}: dimension {} is out of range for a tensor of rank {}", op, dim, rank),
+      FerriteError::DTypeMismatch { op, expected, found } =>
+        write!(f, "{}: expected dtype {}, got {}", op, expected, found),
+      FerriteError::DeviceMismatch { op, lhs, rhs } =>
+        write!(f, "{}: expected all tensors on the same device, found {:?} and {:?}", op, lhs, rhs),
+      FerriteError::GradNotEnabled { op } =>
+        write!(f, "{}: tensor does not require grad", op),
+      FerriteError::NonScalarBackward { shape } =>
+        write!(f, "backward: can only be called on a single-element tensor, got shape {:?}; pass a gradient to backward_with", shape),
+      FerriteError::GraphReleased { op } =>
+        write!(f, "{}: the graph was freed by an earlier backward pass; pass retain_graph = true to backpropagate through it again", op),
+      FerriteError::InvalidArgument { op, message } =>
+        write!(f, "{}: {}", op, message),
+      FerriteError::Unimplemented { op, device } =>
+        write!(f, "{}: not implemented for device {:?}", op, device),
+    }
+  }
+}
+
+impl std::error::Error for FerriteError {}
+
+impl FerriteError {
+  pub(crate) fn invalid(op: &'static str, message: impl Into<String>) -> Self {
+    FerriteError::InvalidArgument { op, message: message.into() }
+  }
+}
@@ -0,0 +1,568 @@
+use crate::*;
+
+// Argument checks shared by the panicking operations and their `try_*`
+// counterparts, so both report the same errors.
+
+pub(crate) fn check_data_len(op: &'static str, len: usize, shape: &[usize]) -> Result<(), FerriteError> {
+  let expected: usize = shape.iter().product();
+  if len != expected {
+    return Err(FerriteError::invalid(op, format!("{} values cannot fill shape {:?} ({} elements)", len, shape, expected)));
+  }
+  Ok(())
+}
+
+pub(crate) fn check_index(op: &'static str, shape: &[usize], indices: &[usize]) -> Result<(), FerriteError> {
+  if indices.len() != shape.len() {
+    return Err(FerriteError::invalid(op, format!("{} indices given for a tensor of shape {:?}", indices.len(), shape)));
+  }
+  for (dim, (&index, &size)) in indices.iter().zip(shape).enumerate() {
+    if index >= size {
+      return Err(FerriteError::IndexOutOfBounds { op, dim, index, size });
+    }
+  }
+  Ok(())
+}
+
+pub(crate) fn check_dim(op: &'static str, dim: usize, rank: usize) -> Result<(), FerriteError> {
+  if dim >= rank {
+    return Err(FerriteError::DimOutOfRange { op, dim, rank });
+  }
+  Ok(())
+}
+
+pub(crate) fn check_dims(op: &'static str, dims: &[usize], rank: usize) -> Result<(), FerriteError> {
+  dims.iter().try_for_each(|&dim| check_dim(op, dim, rank))
+}
+
+pub(crate) fn check_device(op: &'static str, lhs: Device, rhs: Device) -> Result<(), FerriteError> {
+  if lhs != rhs {
+    return Err(FerriteError::DeviceMismatch { op, lhs, rhs });
+  }
+  Ok(())
+}
+
+pub(crate) fn check_supported(op: &'static str, device: Device) -> Result<(), FerriteError> {
+  match device {
+    Device::Cpu => Ok(()),
+    device => Err(FerriteError::Unimplemented { op, device }),
+  }
+}
+
+/// NumPy broadcasting of two shapes.
+pub(crate) fn broadcast_shapes(op: &'static str, lhs: &[usize], rhs: &[usize]) -> Result<Vec<usize>, FerriteError> {
+  let rank = lhs.len().max(rhs.len());
+  (0..rank).map(|i| {
+    let l = if i + lhs.len() >= rank { lhs[i + lhs.len() - rank] } else { 1 };
+    let r = if i + rhs.len() >= rank { rhs[i + rhs.len() - rank] } else { 1 };
+    match (l, r) {
+      (l, r) if l == r || r == 1 => Ok(l),
+      (1, r) => Ok(r),
+      _ => Err(FerriteError::BroadcastError { op, lhs: lhs.to_vec(), rhs: rhs.to_vec() }),
+    }
+  }).collect()
+}
+
+pub(crate) fn check_arange(start: f32, end: f32, step: f32) -> Result<(), FerriteError> {
+  if step == 0. || (end - start) * step < 0. {
+    return Err(FerriteError::invalid("arange", format!("step {} must be non-zero and point from {} towards {}", step, start, end)));
+  }
+  Ok(())
+}
+
+pub(crate) fn check_probability(op: &'static str, p: f32) -> Result<(), FerriteError> {
+  if !(0. ..=1.).contains(&p) {
+    return Err(FerriteError::invalid(op, format!("probability {} must be in [0, 1]", p)));
+  }
+  Ok(())
+}
+
+pub(crate) fn check_randint(low: i64, high: i64) -> Result<(), FerriteError> {
+  if low >= high {
+    return Err(FerriteError::invalid("randint", format!("low ({}) must be smaller than high ({})", low, high)));
+  }
+  Ok(())
+}
+
+pub(crate) fn check_multinomial<S: DeviceStorage>(probs: &S, num_samples: usize, replacement: bool) -> Result<(), FerriteError> {
+  let classes = match probs.shape().len() {
+    1 | 2 => *probs.shape().last().unwrap(),
+    _ => return Err(FerriteError::invalid("multinomial", format!("probabilities must be 1 or 2 dimensional, got shape {:?}", probs.shape()))),
+  };
+  let rows: usize = probs.shape().iter().product::<usize>() / classes.max(1);
+  for row in 0..rows {
+    let weights: Vec<f32> = (0..classes)
+      .map(|c| if probs.shape().len() == 1 { probs.get(&[c]) } else { probs.get(&[row, c]) })
+      .collect();
+    if weights.iter().any(|&w| w < 0. || !w.is_finite()) {
+      return Err(FerriteError::invalid("multinomial", "probabilities must be finite and non-negative"));
+    }
+    let non_zero = weights.iter().filter(|&&w| w > 0.).count();
+    if non_zero == 0 {
+      return Err(FerriteError::invalid("multinomial", "probabilities must not sum to zero"));
+    }
+    if !replacement && num_samples > non_zero {
+      return Err(FerriteError::invalid("multinomial", format!("cannot draw {} samples without replacement from {} non-zero categories", num_samples, non_zero)));
+    }
+  }
+  Ok(())
+}
+
+fn check_slice(op: &'static str, args: &[SliceArg], shape: &[usize]) -> Result<(), FerriteError> {
+  if args.len() > shape.len() {
+    return Err(FerriteError::invalid(op, format!("{} slice arguments given for a tensor of rank {}", args.len(), shape.len())));
+  }
+  for (dim, (arg, &size)) in args.iter().zip(shape).enumerate() {
+    match *arg {
+      SliceArg::Index(index) => {
+        let position = if index < 0 { index + size as isize } else { index };
+        if position < 0 || position >= size as isize {
+          return Err(FerriteError::IndexOutOfBounds { op, dim, index: index.unsigned_abs(), size });
+        }
+      },
+      SliceArg::Range { step: 0, .. } => return Err(FerriteError::invalid(op, "slice step must be positive")),
+      SliceArg::Range { .. } => {},
+    }
+  }
+  Ok(())
+}
+
+/// Integer index values read from an index tensor, each required to address `dim` of size `size`.
+fn check_index_values(op: &'static str, index: &Tensor, dim: usize, size: usize, allow_negative: bool) -> Result<(), FerriteError> {
+  for value in index.tensor().to_vec::<f64>() {
+    let wrapped = if allow_negative && value < 0. { value + size as f64 } else { value };
+    if value.fract() != 0. || wrapped < 0. || wrapped >= size as f64 {
+      return Err(FerriteError::IndexOutOfBounds { op, dim, index: value.abs() as usize, size });
+    }
+  }
+  Ok(())
+}
+
+/// `index` must have the input's rank and not exceed it outside of `dim`.
+fn check_index_tensor(op: &'static str, input: &Tensor, dim: usize, index: &Tensor) -> Result<(), FerriteError> {
+  check_device(op, input.device(), index.device())?;
+  check_dim(op, dim, input.shape().len())?;
+  if index.shape().len() != input.shape().len()
+    || index.shape().iter().zip(input.shape()).enumerate().any(|(d, (i, s))| d != dim && i > s) {
+    return Err(FerriteError::invalid(op, format!("index shape {:?} does not fit input shape {:?} outside of dimension {}", index.shape(), input.shape(), dim)));
+  }
+  check_index_values(op, index, dim, input.shape()[dim], false)
+}
+
+fn check_scatter(op: &'static str, input: &Tensor, dim: usize, index: &Tensor, src: &Tensor) -> Result<(), FerriteError> {
+  check_index_tensor(op, input, dim, index)?;
+  check_device(op, input.device(), src.device())?;
+  if src.shape().len() != index.shape().len() || src.shape().iter().zip(index.shape()).any(|(s, i)| s < i) {
+    return Err(FerriteError::invalid(op, format!("src shape {:?} must cover index shape {:?}", src.shape(), index.shape())));
+  }
+  Ok(())
+}
+
+fn check_mask(op: &'static str, input: &Tensor, mask: &Tensor) -> Result<(), FerriteError> {
+  check_device(op, input.device(), mask.device())?;
+  if broadcast_shapes(op, mask.shape(), input.shape())? != *input.shape() {
+    return Err(FerriteError::BroadcastError { op, lhs: mask.shape().clone(), rhs: input.shape().clone() });
+  }
+  Ok(())
+}
+
+/// Validates a backward pass from `tensor` and returns the gradient to seed it
+/// with: `grad_output` cast to the tensor's dtype, or ones.
+pub(crate) fn check_backward(tensor: &Tensor, grad_output: Option<&Tensor>) -> Result<Storage, FerriteError> {
+  if tensor.grad().is_none() {
+    return Err(FerriteError::GradNotEnabled { op: "backward" });
+  }
+  match grad_output {
+    Some(grad_output) => {
+      if grad_output.shape() != tensor.shape() {
+        return Err(FerriteError::ShapeMismatch { op: "backward", expected: tensor.shape().clone(), found: grad_output.shape().clone() });
+      }
+      Ok(grad_output.tensor().to_dtype(tensor.dtype()))
+    }
+    None if tensor.shape().iter().product::<usize>() != 1 => {
+      Err(FerriteError::NonScalarBackward { shape: tensor.shape().clone() })
+    }
+    None => Ok(Storage::ones_like(tensor.tensor())),
+  }
+}
+
+/// Expands to a `try_` reduction that validates `dims` before reducing.
+macro_rules! try_reduction {
+  ($($name:ident => $op:ident;)*) => {
+    $(
+      pub fn $name(&self, dims: &[usize], keepdim: bool) -> Result<Tensor, FerriteError> {
+        check_dims(stringify!($op), dims, self.shape().len())?;
+        Ok(self.$op(dims, keepdim))
+      }
+    )*
+  };
+}
+
+
+impl Tensor {
+  /// Runs the backward pass, reporting a non-scalar output or a tensor that
+  /// does not require grad instead of panicking.
+  pub fn try_backward(&self) -> Result<(), FerriteError> {
+    self.try_backward_with(None, false, false)
+  }
+
+  pub fn try_backward_with(&self, grad_output: Option<&Tensor>, retain_graph: bool, create_graph: bool) -> Result<(), FerriteError> {
+    let seed = check_backward(self, grad_output)?;
+    run_backward(&[self], &[seed], retain_graph, create_graph)
+  }
+
+  // Creation
+
+  pub fn try_zeros(shape: Vec<usize>, device: Device, requires_grad: Option<bool>) -> Result<Self, FerriteError> {
+    check_supported("zeros", device)?;
+    Ok(Tensor::zeros(shape, device, requires_grad))
+  }
+
+  pub fn try_ones(shape: Vec<usize>, device: Device, requires_grad: Option<bool>) -> Result<Self, FerriteError> {
+    check_supported("ones", device)?;
+    Ok(Tensor::ones(shape, device, requires_grad))
+  }
+
+  pub fn try_full(shape: Vec<usize>, value: f32, device: Device, requires_grad: Option<bool>) -> Result<Self, FerriteError> {
+    check_supported("full", device)?;
+    Ok(Tensor::full(shape, value, device, requires_grad))
+  }
+
+  pub fn try_arange(start: f32, end: f32, step: f32, device: Device, requires_grad: Option<bool>) -> Result<Self, FerriteError> {
+    check_supported("arange", device)?;
+    check_arange(start, end, step)?;
+    Ok(Tensor::arange(start, end, step, device, requires_grad))
+  }
+
+  pub fn try_linspace(start: f32, end: f32, steps: usize, device: Device, requires_grad: Option<bool>) -> Result<Self, FerriteError> {
+    check_supported("linspace", device)?;
+    if steps == 0 {
+      return Err(FerriteError::invalid("linspace", "number of steps must be positive"));
+    }
+    Ok(Tensor::linspace(start, end, steps, device, requires_grad))
+  }
+
+  pub fn try_bernoulli(p: f32, shape: Vec<usize>, device: Device, requires_grad: Option<bool>, generator: Option<&Generator>) -> Result<Self, FerriteError> {
+    check_supported("bernoulli", device)?;
+    check_probability("bernoulli", p)?;
+    Ok(Tensor::bernoulli(p, shape, device, requires_grad, generator))
+  }
+
+  pub fn try_randint(low: i64, high: i64, shape: Vec<usize>, device: Device, requires_grad: Option<bool>, generator: Option<&Generator>) -> Result<Self, FerriteError> {
+    check_supported("randint", device)?;
+    check_randint(low, high)?;
+    Ok(Tensor::randint(low, high, shape, device, requires_grad, generator))
+  }
+
+  pub fn try_multinomial(probs: &Tensor, num_samples: usize, replacement: bool, generator: Option<&Generator>) -> Result<Self, FerriteError> {
+    check_multinomial(probs.tensor(), num_samples, replacement)?;
+    Ok(Tensor::multinomial(probs, num_samples, replacement, generator))
+  }
+
+  // Arithmetic
+
+  pub fn try_add_tensor(&self, other: &Tensor) -> Result<Tensor, FerriteError> {
+    check_device("add", self.device(), other.device())?;
+    broadcast_shapes("add", self.shape(), other.shape())?;
+    Ok(self.add_tensor(other))
+  }
+
+  pub fn try_sub_tensor(&self, other: &Tensor) -> Result<Tensor, FerriteError> {
+    check_device("sub", self.device(), other.device())?;
+    broadcast_shapes("sub", self.shape(), other.shape())?;
+    Ok(self.sub_tensor(other))
+  }
+
+  pub fn try_mul_tensor(&self, other: &Tensor) -> Result<Tensor, FerriteError> {
+    check_device("mul", self.device(), other.device())?;
+    broadcast_shapes("mul", self.shape(), other.shape())?;
+    Ok(self.mul_tensor(other))
+  }
+
+  pub fn try_div_tensor(&self, other: &Tensor) -> Result<Tensor, FerriteError> {
+    check_device("div", self.device(), other.device())?;
+    broadcast_shapes("div", self.shape(), other.shape())?;
+    Ok(self.div_tensor(other))
+  }
+
+  pub fn try_greater_than(&self, other: &Tensor, make_binary: bool) -> Result<Tensor, FerriteError> {
+    check_device("greater_than", self.device(), other.device())?;
+    broadcast_shapes("greater_than", self.shape(), other.shape())?;
+    Ok(self.greater_than(other, make_binary))
+  }
+
+  pub fn try_less_than(&self, other: &Tensor, make_binary: bool) -> Result<Tensor, FerriteError> {
+    check_device("less_than", self.device(), other.device())?;
+    broadcast_shapes("less_than", self.shape(), other.shape())?;
+    Ok(self.less_than(other, make_binary))
+  }
+
+  // Matrix products
+
+  pub fn try_matmul(&self, other: &Tensor, trans_a: bool, trans_b: bool) -> Result<Tensor, FerriteError> {
+    check_device("matmul", self.device(), other.device())?;
+    let (a, b) = (self.shape(), other.shape());
+    if a.is_empty() || b.is_empty() {
+      return Err(FerriteError::invalid("matmul", "operands must have at least one dimension"));
+    }
+    let k_a = match (a.len(), trans_a) {
+      (1, _) => a[0],
+      (rank, false) => a[rank - 1],
+      (rank, true) => a[rank - 2],
+    };
+    let k_b = match (b.len(), trans_b) {
+      (1, _) => b[0],
+      (rank, false) => b[rank - 2],
+      (rank, true) => b[rank - 1],
+    };
+    if k_a != k_b {
+      return Err(FerriteError::ShapeMismatch { op: "matmul", expected: vec![k_a], found: vec![k_b] });
+    }
+    broadcast_shapes("matmul", &a[..a.len().saturating_sub(2)], &b[..b.len().saturating_sub(2)])?;
+    Ok(self.matmul(other, trans_a, trans_b))
+  }
+
+  // Shape manipulation
+
+  pub fn try_reshape(&mut self, new_shape: Vec<usize>) -> Result<(), FerriteError> {
+    if new_shape.iter().product::<usize>() != self.shape().iter().product::<usize>() {
+      return Err(FerriteError::ShapeMismatch { op: "reshape", expected: self.shape().clone(), found: new_shape });
+    }
+    self.reshape(new_shape);
+    Ok(())
+  }
+
+  pub fn try_permute(&mut self, dims: &[usize]) -> Result<(), FerriteError> {
+    let rank = self.shape().len();
+    let mut seen = vec![false; rank];
+    for &dim in dims {
+      check_dim("permute", dim, rank)?;
+      if std::mem::replace(&mut seen[dim], true) {
+        return Err(FerriteError::invalid("permute", format!("dimension {} repeated in {:?}", dim, dims)));
+      }
+    }
+    if dims.len() != rank {
+      return Err(FerriteError::invalid("permute", format!("{} dimensions given for a tensor of rank {}", dims.len(), rank)));
+    }
+    self.permute(dims);
+    Ok(())
+  }
+
+  pub fn try_transpose(&self) -> Result<Tensor, FerriteError> {
+    if self.shape().len() != 2 {
+      return Err(FerriteError::invalid("transpose", format!("expected a matrix, got shape {:?}", self.shape())));
+    }
+    Ok(self.transpose())
+  }
+
+  pub fn try_unsqueeze(&mut self, dim: usize) -> Result<(), FerriteError> {
+    check_dim("unsqueeze", dim, self.shape().len() + 1)?;
+    self.unsqueeze(dim);
+    Ok(())
+  }
+
+  pub fn try_broadcast(&self, new_shape: &[usize]) -> Result<Tensor, FerriteError> {
+    if broadcast_shapes("broadcast", self.shape(), new_shape)? != new_shape {
+      return Err(FerriteError::BroadcastError { op: "broadcast", lhs: self.shape().clone(), rhs: new_shape.to_vec() });
+    }
+    Ok(self.broadcast(new_shape))
+  }
+
+  pub fn try_broadcast_tensors(a: &Tensor, b: &Tensor) -> Result<(Tensor, Tensor), FerriteError> {
+    check_device("broadcast_tensors", a.device(), b.device())?;
+    broadcast_shapes("broadcast_tensors", a.shape(), b.shape())?;
+    Ok(Tensor::broadcast_tensors(a, b))
+  }
+
+  // Indexing and slicing
+
+  pub fn try_index_select(&self, dim: usize, indices: &[usize]) -> Result<Tensor, FerriteError> {
+    check_dim("index_select", dim, self.shape().len())?;
+    let size = self.shape()[dim];
+    if let Some(&index) = indices.iter().find(|&&index| index >= size) {
+      return Err(FerriteError::IndexOutOfBounds { op: "index_select", dim, index, size });
+    }
+    Ok(self.index_select(dim, indices))
+  }
+
+  pub fn try_index_add(&self, dim: usize, indices: &[usize], source: &Tensor) -> Result<Tensor, FerriteError> {
+    check_device("index_add", self.device(), source.device())?;
+    check_dim("index_add", dim, self.shape().len())?;
+    let size = self.shape()[dim];
+    if let Some(&index) = indices.iter().find(|&&index| index >= size) {
+      return Err(FerriteError::IndexOutOfBounds { op: "index_add", dim, index, size });
+    }
+    let mut expected = self.shape().clone();
+    expected[dim] = indices.len();
+    if *source.shape() != expected {
+      return Err(FerriteError::ShapeMismatch { op: "index_add", expected, found: source.shape().clone() });
+    }
+    Ok(self.index_add(dim, indices, source))
+  }
+
+  pub fn try_gather(&self, dim: usize, index: &Tensor) -> Result<Tensor, FerriteError> {
+    check_index_tensor("gather", self, dim, index)?;
+    Ok(self.gather(dim, index))
+  }
+
+  pub fn try_scatter(&self, dim: usize, index: &Tensor, src: &Tensor) -> Result<Tensor, FerriteError> {
+    check_scatter("scatter", self, dim, index, src)?;
+    Ok(self.scatter(dim, index, src))
+  }
+
+  pub fn try_scatter_add(&self, dim: usize, index: &Tensor, src: &Tensor) -> Result<Tensor, FerriteError> {
+    check_scatter("scatter_add", self, dim, index, src)?;
+    Ok(self.scatter_add(dim, index, src))
+  }
+
+  pub fn try_slice(&self, args: &[SliceArg]) -> Result<Tensor, FerriteError> {
+    check_slice("slice", args, self.shape())?;
+    Ok(self.slice(args))
+  }
+
+  pub fn try_narrow(&self, dim: usize, start: usize, length: usize) -> Result<Tensor, FerriteError> {
+    check_dim("narrow", dim, self.shape().len())?;
+    let size = self.shape()[dim];
+    if start + length > size {
+      return Err(FerriteError::IndexOutOfBounds { op: "narrow", dim, index: start + length, size });
+    }
+    Ok(self.narrow(dim, start, length))
+  }
+
+  pub fn try_select(&self, dim: usize, index: usize) -> Result<Tensor, FerriteError> {
+    check_dim("select", dim, self.shape().len())?;
+    let size = self.shape()[dim];
+    if index >= size {
+      return Err(FerriteError::IndexOutOfBounds { op: "select", dim, index, size });
+    }
+    Ok(self.select(dim, index))
+  }
+
+  pub fn try_slice_scatter(&self, args: &[SliceArg], src: &Tensor) -> Result<Tensor, FerriteError> {
+    check_device("slice_scatter", self.device(), src.device())?;
+    check_slice("slice_scatter", args, self.shape())?;
+    let region = self.tensor().slice(args);
+    if region.shape() != src.shape() {
+      return Err(FerriteError::ShapeMismatch { op: "slice_scatter", expected: region.shape().clone(), found: src.shape().clone() });
+    }
+    Ok(self.slice_scatter(args, src))
+  }
+
+  pub fn try_masked_select(&self, mask: &Tensor) -> Result<Tensor, FerriteError> {
+    check_mask("masked_select", self, mask)?;
+    Ok(self.masked_select(mask))
+  }
+
+  pub fn try_masked_scatter(&self, mask: &Tensor, source: &Tensor) -> Result<Tensor, FerriteError> {
+    check_mask("masked_scatter", self, mask)?;
+    check_device("masked_scatter", self.device(), source.device())?;
+    let selected = mask.tensor().broadcast(self.shape()).to_vec::<bool>()
+      .into_iter().filter(|&keep| keep).count();
+    let available: usize = source.shape().iter().product();
+    if available < selected {
+      return Err(FerriteError::invalid("masked_scatter", format!("mask selects {} elements but source only has {}", selected, available)));
+    }
+    Ok(self.masked_scatter(mask, source))
+  }
+
+  pub fn try_take(&self, dim: usize, indices: &Tensor) -> Result<Tensor, FerriteError> {
+    check_device("take", self.device(), indices.device())?;
+    check_dim("take", dim, self.shape().len())?;
+    check_index_values("take", indices, dim, self.shape()[dim], true)?;
+    Ok(self.take(dim, indices))
+  }
+
+  // Joining and splitting
+
+  pub fn try_cat(tensors: &[&Tensor], dim: usize) -> Result<Tensor, FerriteError> {
+    let first = tensors.first().ok_or_else(|| FerriteError::invalid("cat", "expected at least one tensor"))?;
+    check_dim("cat", dim, first.shape().len())?;
+    for tensor in tensors {
+      check_device("cat", first.device(), tensor.device())?;
+      let compatible = tensor.shape().len() == first.shape().len()
+        && tensor.shape().iter().zip(first.shape()).enumerate().all(|(d, (a, b))| d == dim || a == b);
+      if !compatible {
+        return Err(FerriteError::ShapeMismatch { op: "cat", expected: first.shape().clone(), found: tensor.shape().clone() });
+      }
+    }
+    Ok(Tensor::cat(tensors, dim))
+  }
+
+  pub fn try_stack(tensors: &[&Tensor], dim: usize) -> Result<Tensor, FerriteError> {
+    let first = tensors.first().ok_or_else(|| FerriteError::invalid("stack", "expected at least one tensor"))?;
+    check_dim("stack", dim, first.shape().len() + 1)?;
+    for tensor in tensors {
+      check_device("stack", first.device(), tensor.device())?;
+      if tensor.shape() != first.shape() {
+        return Err(FerriteError::ShapeMismatch { op: "stack", expected: first.shape().clone(), found: tensor.shape().clone() });
+      }
+    }
+    Ok(Tensor::stack(tensors, dim))
+  }
+
+  pub fn try_split(&self, split_size: usize, dim: usize) -> Result<Vec<Tensor>, FerriteError> {
+    check_dim("split", dim, self.shape().len())?;
+    if split_size == 0 {
+      return Err(FerriteError::invalid("split", "split size must be positive"));
+    }
+    Ok(self.split(split_size, dim))
+  }
+
+  pub fn try_split_with_sizes(&self, sizes: &[usize], dim: usize) -> Result<Vec<Tensor>, FerriteError> {
+    check_dim("split_with_sizes", dim, self.shape().len())?;
+    if sizes.iter().sum::<usize>() != self.shape()[dim] {
+      return Err(FerriteError::invalid("split_with_sizes", format!("sizes {:?} don't add up to dimension {} of size {}", sizes, dim, self.shape()[dim])));
+    }
+    Ok(self.split_with_sizes(sizes, dim))
+  }
+
+  pub fn try_chunk(&self, chunks: usize, dim: usize) -> Result<Vec<Tensor>, FerriteError> {
+    check_dim("chunk", dim, self.shape().len())?;
+    if chunks == 0 {
+      return Err(FerriteError::invalid("chunk", "number of chunks must be positive"));
+    }
+    Ok(self.chunk(chunks, dim))
+  }
+
+  pub fn try_unbind(&self, dim: usize) -> Result<Vec<Tensor>, FerriteError> {
+    check_dim("unbind", dim, self.shape().len())?;
+    Ok(self.unbind(dim))
+  }
+
+  // Reductions
+
+  pub fn try_sum_axis(&self, axis: usize) -> Result<Tensor, FerriteError> {
+    check_dim("sum_axis", axis, self.shape().len())?;
+    Ok(self.sum_axis(axis))
+  }
+
+  try_reduction! {
+    try_sum_dims => sum_dims;
+    try_mean_dims => mean_dims;
+    try_prod_dims => prod_dims;
+    try_max => max;
+    try_min => min;
+    try_argmax => argmax;
+    try_argmin => argmin;
+    try_logsumexp => logsumexp;
+    try_all => all;
+    try_any => any;
+  }
+
+  pub fn try_var(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Result<Tensor, FerriteError> {
+    check_dims("var", dims, self.shape().len())?;
+    Ok(self.var(dims, unbiased, keepdim))
+  }
+
+  pub fn try_std(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Result<Tensor, FerriteError> {
+    check_dims("std", dims, self.shape().len())?;
+    Ok(self.std(dims, unbiased, keepdim))
+  }
+
+  pub fn try_norm(&self, p: f32, dims: &[usize], keepdim: bool) -> Result<Tensor, FerriteError> {
+    if p <= 0. || p.is_nan() {
+      return Err(FerriteError::invalid("norm", format!("p must be positive, got {}", p)));
+    }
+    check_dims("norm", dims, self.shape().len())?;
+    Ok(self.norm(p, dims, keepdim))
+  }
+}
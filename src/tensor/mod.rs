@@ -6,6 +6,12 @@ mod ops;
 mod creation;
 mod storage;
 mod device;
+mod random;
+mod slice;
+mod error;
+mod dtype;
+mod autocast;
+mod fallible;
 
 // Re-export everything we want to be publicly accessible
 pub use base::*;
@@ -14,4 +20,10 @@ pub use utils::*;
 pub use ops::*;
 pub use creation::*;
 pub use storage::*;
-pub use device::*;
\ No newline at end of file
+pub use device::*;
+pub use random::*;
+pub use slice::*;
+pub use error::*;
+pub use dtype::*;
+pub use autocast::*;
+pub(crate) use fallible::*;
\ No newline at end of file
@@ -57,7 +57,7 @@ impl ActivationOps for Tensor {
     let tensor = self.tensor().binary_step();
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -75,7 +75,7 @@ impl ActivationOps for Tensor {
     let tensor = self.tensor().sigmoid();
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -93,7 +93,7 @@ impl ActivationOps for Tensor {
     let tensor = self.tensor().tanh();
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -111,7 +111,7 @@ impl ActivationOps for Tensor {
     let tensor = self.tensor().relu();
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -129,7 +129,7 @@ impl ActivationOps for Tensor {
     let tensor = self.tensor().leaky_relu();
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -147,7 +147,7 @@ impl ActivationOps for Tensor {
     let tensor = self.tensor().parametric_relu(a);
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -166,7 +166,7 @@ impl ActivationOps for Tensor {
     let tensor = self.tensor().elu(alpha);
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -182,16 +182,21 @@ impl ActivationOps for Tensor {
   }
   
   fn softmax(&self, dim: usize) -> Self {
+    if let Some(full) = autocast_full(self) {
+      return full.softmax(dim);
+    }
+
     let tensor = self.tensor().softmax(dim);
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
     if requires_grad {
       result.set_grad_fn(Some(Rc::new(SoftmaxGrad::new(
         self, 
+        dim,
         &result
       ))));
     }
@@ -203,7 +208,7 @@ impl ActivationOps for Tensor {
     let tensor = self.tensor().swish();
     
     // Create result tensor
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -1,4 +1,5 @@
 use crate::*;
+use crate::autograd::forward::DualTensor;
 use std::{ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign}, rc::Rc};
 
 pub trait ArithmeticOps {
@@ -147,7 +148,7 @@ impl ArithmeticOps for Tensor {
     let tensor = self.tensor().add_tensor(other.tensor());
     
     // Create result tensor
-    let requires_grad = *self.requires_grad() || *other.requires_grad();
+    let requires_grad = self.tracks_grad() || other.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -167,7 +168,7 @@ impl ArithmeticOps for Tensor {
     let tensor = self.tensor().sub_tensor(other.tensor());
     
     // Create result tensor
-    let requires_grad = *self.requires_grad() || *other.requires_grad();
+    let requires_grad = self.tracks_grad() || other.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     // Set up gradient function if needed
@@ -185,7 +186,7 @@ impl ArithmeticOps for Tensor {
   fn mul_tensor(&self, other: &Self) -> Self {
     let tensor = self.tensor().mul_tensor(other.tensor());
     
-    let requires_grad = *self.requires_grad() || *other.requires_grad();
+    let requires_grad = self.tracks_grad() || other.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -202,7 +203,7 @@ impl ArithmeticOps for Tensor {
   fn div_tensor(&self, other: &Self) -> Self {
     let tensor = self.tensor().div_tensor(other.tensor());
     
-    let requires_grad = *self.requires_grad() || *other.requires_grad();
+    let requires_grad = self.tracks_grad() || other.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -220,7 +221,7 @@ impl ArithmeticOps for Tensor {
   fn pow_f32(&self, other: f32) -> Self {
     let tensor = self.tensor().pow_f32(other);
     
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -239,7 +240,7 @@ impl ArithmeticOps for Tensor {
   fn add_f32(&self, other: f32) -> Self {
     let tensor = self.tensor().add_f32(other);
     
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -255,7 +256,7 @@ impl ArithmeticOps for Tensor {
   fn sub_f32(&self, other: f32) -> Self {
     let tensor = self.tensor().sub_f32(other);
     
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -272,7 +273,7 @@ impl ArithmeticOps for Tensor {
   fn mul_f32(&self, other: f32) -> Self {
     let tensor = self.tensor().mul_f32(other);
     
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -289,7 +290,7 @@ impl ArithmeticOps for Tensor {
   fn div_f32(&self, other: f32) -> Self {
     let tensor = self.tensor().div_f32(other);
     
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -306,7 +307,7 @@ impl ArithmeticOps for Tensor {
   fn abs(&self) -> Self {
     let tensor = self.tensor().abs();
     
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -506,6 +507,8 @@ macro_rules! impl_scalar_ops {
 
 impl_binary_ops!(Tensor, Tensor);
 impl_binary_ops!(Storage, Storage);
+impl_binary_ops!(DualTensor, DualTensor);
 
 impl_scalar_ops!(Tensor);
-impl_scalar_ops!(Storage);
\ No newline at end of file
+impl_scalar_ops!(Storage);
+impl_scalar_ops!(DualTensor);
\ No newline at end of file
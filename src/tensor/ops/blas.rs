@@ -15,9 +15,15 @@ impl BlasOps for Storage {
 
 impl BlasOps for Tensor {
   fn matmul(&self, other: &Self, trans_a: bool, trans_b: bool) -> Self {
+    // Under autocast both operands run in the reduced-precision dtype
+    let (lhs, rhs) = (autocast_lower(self), autocast_lower(other));
+    if lhs.is_some() || rhs.is_some() {
+      return lhs.as_ref().unwrap_or(self).matmul(rhs.as_ref().unwrap_or(other), trans_a, trans_b);
+    }
+
     let tensor = self.tensor().matmul(other.tensor(), trans_a, trans_b);
     
-    let requires_grad = *self.requires_grad() || *other.requires_grad();
+    let requires_grad = self.tracks_grad() || other.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
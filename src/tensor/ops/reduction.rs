@@ -1,11 +1,40 @@
 use std::rc::Rc;
-use crate::{DeviceStorage, MeanGrad, ProductGrad, Storage, SumGrad, Tensor, match_storage, match_storage_assign};
+use crate::{autocast_full, LogSumExpGrad, MaxMinGrad, MeanDimsGrad, MeanGrad, NormGrad, ProdDimsGrad, ProductGrad, StdGrad, Storage, SumDimsGrad, SumGrad, Tensor, VarGrad, match_storage, match_storage_assign};
 
+/// Reductions. The `*_dims` style methods reduce over every dimension in `dims`
+/// (an empty slice reduces over all of them); with `keepdim` the reduced
+/// dimensions are kept with size 1 so the result broadcasts against the input.
 pub trait ReductionOps {
   fn sum(&self) -> Self;
   fn sum_axis(&self, axis: usize) -> Self;
   fn product(&self) -> Self;
   fn mean(&self) -> Self;
+
+  fn sum_dims(&self, dims: &[usize], keepdim: bool) -> Self;
+  fn mean_dims(&self, dims: &[usize], keepdim: bool) -> Self;
+  fn prod_dims(&self, dims: &[usize], keepdim: bool) -> Self;
+  fn max(&self, dims: &[usize], keepdim: bool) -> Self;
+  fn min(&self, dims: &[usize], keepdim: bool) -> Self;
+
+  /// Position of the maximum within each reduced lane, counted row-major over
+  /// the reduced dimensions. Not differentiable.
+  fn argmax(&self, dims: &[usize], keepdim: bool) -> Self;
+  /// Position of the minimum within each reduced lane. Not differentiable.
+  fn argmin(&self, dims: &[usize], keepdim: bool) -> Self;
+
+  /// Variance; `unbiased` applies Bessel's correction (divides by `n - 1`).
+  fn var(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self;
+  fn std(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self;
+
+  /// The `p`-norm; pass `f32::INFINITY` for the max-norm.
+  fn norm(&self, p: f32, dims: &[usize], keepdim: bool) -> Self;
+  /// Numerically stable `log(sum(exp(x)))`.
+  fn logsumexp(&self, dims: &[usize], keepdim: bool) -> Self;
+
+  /// 1.0 where every element of the lane is non-zero. Not differentiable.
+  fn all(&self, dims: &[usize], keepdim: bool) -> Self;
+  /// 1.0 where any element of the lane is non-zero. Not differentiable.
+  fn any(&self, dims: &[usize], keepdim: bool) -> Self;
 }
 
 
@@ -25,13 +54,65 @@ impl ReductionOps for Storage {
   fn mean(&self) -> Self {
     match_storage!(unary self, mean)
   }
+
+  fn sum_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, sum_dims, dims, keepdim)
+  }
+
+  fn mean_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, mean_dims, dims, keepdim)
+  }
+
+  fn prod_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, prod_dims, dims, keepdim)
+  }
+
+  fn max(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, max, dims, keepdim)
+  }
+
+  fn min(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, min, dims, keepdim)
+  }
+
+  fn argmax(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, argmax, dims, keepdim)
+  }
+
+  fn argmin(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, argmin, dims, keepdim)
+  }
+
+  fn var(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self {
+    match_storage!(unary self, var, dims, unbiased, keepdim)
+  }
+
+  fn std(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self {
+    match_storage!(unary self, std, dims, unbiased, keepdim)
+  }
+
+  fn norm(&self, p: f32, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, norm, p, dims, keepdim)
+  }
+
+  fn logsumexp(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, logsumexp, dims, keepdim)
+  }
+
+  fn all(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, all, dims, keepdim)
+  }
+
+  fn any(&self, dims: &[usize], keepdim: bool) -> Self {
+    match_storage!(unary self, any, dims, keepdim)
+  }
 }
 
 impl ReductionOps for Tensor {
 
   fn sum(&self) -> Self {
     let tensor = self.tensor().sum();
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -42,16 +123,12 @@ impl ReductionOps for Tensor {
   }
 
   fn sum_axis(&self, axis: usize) -> Self {
-    let tensor = self.tensor().sum_axis(axis);
-    let requires_grad = *self.requires_grad();
-    let mut result = Tensor::new(tensor, self.device(), requires_grad);
-    
-    result
+    self.sum_dims(&[axis], false)
   }
 
   fn mean(&self) -> Self {
     let tensor = self.tensor().mean();
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -63,7 +140,7 @@ impl ReductionOps for Tensor {
 
   fn product(&self) -> Self {
     let tensor = self.tensor().product();
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
@@ -73,4 +150,131 @@ impl ReductionOps for Tensor {
     result
   }
 
-}
\ No newline at end of file
+  fn sum_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    let tensor = self.tensor().sum_dims(dims, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(SumDimsGrad::new(self, dims, &result))));
+    }
+
+    result
+  }
+
+  fn mean_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    let tensor = self.tensor().mean_dims(dims, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(MeanDimsGrad::new(self, dims, &result))));
+    }
+
+    result
+  }
+
+  fn prod_dims(&self, dims: &[usize], keepdim: bool) -> Self {
+    let tensor = self.tensor().prod_dims(dims, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(ProdDimsGrad::new(self, dims, &result))));
+    }
+
+    result
+  }
+
+  fn max(&self, dims: &[usize], keepdim: bool) -> Self {
+    let tensor = self.tensor().max(dims, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(MaxMinGrad::new(self, dims, &result))));
+    }
+
+    result
+  }
+
+  fn min(&self, dims: &[usize], keepdim: bool) -> Self {
+    let tensor = self.tensor().min(dims, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(MaxMinGrad::new(self, dims, &result))));
+    }
+
+    result
+  }
+
+  fn argmax(&self, dims: &[usize], keepdim: bool) -> Self {
+    Tensor::new(self.tensor().argmax(dims, keepdim), self.device(), false)
+  }
+
+  fn argmin(&self, dims: &[usize], keepdim: bool) -> Self {
+    Tensor::new(self.tensor().argmin(dims, keepdim), self.device(), false)
+  }
+
+  fn var(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self {
+    let tensor = self.tensor().var(dims, unbiased, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(VarGrad::new(self, dims, unbiased, &result))));
+    }
+
+    result
+  }
+
+  fn std(&self, dims: &[usize], unbiased: bool, keepdim: bool) -> Self {
+    let tensor = self.tensor().std(dims, unbiased, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(StdGrad::new(self, dims, unbiased, &result))));
+    }
+
+    result
+  }
+
+  fn norm(&self, p: f32, dims: &[usize], keepdim: bool) -> Self {
+    let tensor = self.tensor().norm(p, dims, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(NormGrad::new(self, dims, p, &result))));
+    }
+
+    result
+  }
+
+  fn logsumexp(&self, dims: &[usize], keepdim: bool) -> Self {
+    if let Some(full) = autocast_full(self) {
+      return full.logsumexp(dims, keepdim);
+    }
+
+    let tensor = self.tensor().logsumexp(dims, keepdim);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(LogSumExpGrad::new(self, dims, &result))));
+    }
+
+    result
+  }
+
+  fn all(&self, dims: &[usize], keepdim: bool) -> Self {
+    Tensor::new(self.tensor().all(dims, keepdim), self.device(), false)
+  }
+
+  fn any(&self, dims: &[usize], keepdim: bool) -> Self {
+    Tensor::new(self.tensor().any(dims, keepdim), self.device(), false)
+  }
+}
@@ -1,6 +1,6 @@
 use std::rc::Rc;
 
-use crate::{match_storage, match_storage_assign, DeviceStorage, PermuteGrad, Storage, Tensor};
+use crate::{check_device, match_storage, match_storage_assign, BroadcastGrad, CatGrad, CpuStorage, DeviceStorage, GatherGrad, IndexAddGrad, IndexSelectGrad, MaskedScatterGrad, MaskedSelectGrad, PermuteGrad, ScatterGrad, SliceArg, SliceGrad, SliceScatterGrad, StackGrad, Storage, Tensor};
 
 
 pub trait TransformOps {
@@ -42,6 +42,67 @@ pub trait TransformOps {
   fn pad_shape(&self, target_rank: usize) -> Vec<usize>;
   fn broadcast_tensors(a: &Self, b: &Self) -> (Self, Self) where Self: Sized;
 
+  /// Selects the entries at `indices` along `dim`.
+  fn index_select(&self, dim: usize, indices: &[usize]) -> Self;
+
+  /// Adds the slices of `source` into the entries at `indices` along `dim`.
+  fn index_add(&self, dim: usize, indices: &[usize], source: &Self) -> Self;
+
+  /// Picks, for every position of `index`, the element whose coordinate along
+  /// `dim` is given by `index` (e.g. `out[i][j] = self[i][index[i][j]]` for dim 1).
+  fn gather(&self, dim: usize, index: &Self) -> Self;
+
+  /// Writes `src` into a copy of `self` at the positions given by `index` along `dim`
+  /// (e.g. `out[index[i][j]][j] = src[i][j]` for dim 0).
+  fn scatter(&self, dim: usize, index: &Self, src: &Self) -> Self;
+
+  /// Like `scatter`, but accumulates into the selected positions.
+  fn scatter_add(&self, dim: usize, index: &Self, src: &Self) -> Self;
+
+  /// Returns a row-major copy, or `self` if it is already laid out that way.
+  fn contiguous(&self) -> Self;
+
+  /// A view over the region described by `args` (see `s![]`) that shares data with
+  /// `self`. Missing trailing arguments select the whole dimension.
+  fn slice(&self, args: &[SliceArg]) -> Self;
+
+  /// A view of `length` positions of `dim`, starting at `start`.
+  fn narrow(&self, dim: usize, start: usize, length: usize) -> Self;
+
+  /// A view of position `index` of `dim`, with that dimension removed.
+  fn select(&self, dim: usize, index: usize) -> Self;
+
+  /// A copy of `self` with the region described by `args` replaced by `src`.
+  fn slice_scatter(&self, args: &[SliceArg], src: &Self) -> Self;
+
+  /// Copies the elements where the (broadcast) `mask` is non-zero into a 1-D result.
+  fn masked_select(&self, mask: &Self) -> Self;
+
+  /// A copy of `self` where the positions selected by `mask` take consecutive
+  /// values from `source`.
+  fn masked_scatter(&self, mask: &Self, source: &Self) -> Self;
+
+  /// Integer-array indexing along `dim`: the result has shape
+  /// `shape[..dim] + indices.shape + shape[dim + 1..]`.
+  fn take(&self, dim: usize, indices: &Self) -> Self;
+
+  /// Joins tensors that agree on every dimension but `dim` along that dimension.
+  fn cat(tensors: &[&Self], dim: usize) -> Self where Self: Sized;
+
+  /// Joins tensors of identical shape along a new dimension inserted at `dim`.
+  fn stack(tensors: &[&Self], dim: usize) -> Self where Self: Sized;
+
+  /// Views of `split_size` positions along `dim`; the last one may be smaller.
+  fn split(&self, split_size: usize, dim: usize) -> Vec<Self> where Self: Sized;
+
+  /// Views of the given sizes along `dim`, which must add up to its length.
+  fn split_with_sizes(&self, sizes: &[usize], dim: usize) -> Vec<Self> where Self: Sized;
+
+  /// At most `chunks` views of (nearly) equal size along `dim`.
+  fn chunk(&self, chunks: usize, dim: usize) -> Vec<Self> where Self: Sized;
+
+  /// Views of every position along `dim`, with that dimension removed.
+  fn unbind(&self, dim: usize) -> Vec<Self> where Self: Sized;
 }
 
 macro_rules! match_storage {
@@ -208,6 +269,123 @@ impl TransformOps for Storage {
     
     (broadcast_a, broadcast_b)
   }
+
+  fn index_select(&self, dim: usize, indices: &[usize]) -> Self {
+    match_storage!(unary self, index_select, dim, indices)
+  }
+
+  fn index_add(&self, dim: usize, indices: &[usize], source: &Self) -> Self {
+    match (self, source) {
+      (Storage::Cpu(cpu_self), Storage::Cpu(cpu_source)) => Storage::Cpu(cpu_self.index_add(dim, indices, cpu_source)),
+      _ => unimplemented!("Cross-device operations not supported"),
+    }
+  }
+
+  fn gather(&self, dim: usize, index: &Self) -> Self {
+    match (self, index) {
+      (Storage::Cpu(cpu_self), Storage::Cpu(cpu_index)) => Storage::Cpu(cpu_self.gather(dim, cpu_index)),
+      _ => unimplemented!("Cross-device operations not supported"),
+    }
+  }
+
+  fn scatter(&self, dim: usize, index: &Self, src: &Self) -> Self {
+    match (self, index, src) {
+      (Storage::Cpu(cpu_self), Storage::Cpu(cpu_index), Storage::Cpu(cpu_src)) => Storage::Cpu(cpu_self.scatter(dim, cpu_index, cpu_src)),
+      _ => unimplemented!("Cross-device operations not supported"),
+    }
+  }
+
+  fn scatter_add(&self, dim: usize, index: &Self, src: &Self) -> Self {
+    match (self, index, src) {
+      (Storage::Cpu(cpu_self), Storage::Cpu(cpu_index), Storage::Cpu(cpu_src)) => Storage::Cpu(cpu_self.scatter_add(dim, cpu_index, cpu_src)),
+      _ => unimplemented!("Cross-device operations not supported"),
+    }
+  }
+
+  fn contiguous(&self) -> Self {
+    match_storage!(unary self, contiguous)
+  }
+
+  fn slice(&self, args: &[SliceArg]) -> Self {
+    match_storage!(unary self, slice, args)
+  }
+
+  fn narrow(&self, dim: usize, start: usize, length: usize) -> Self {
+    match_storage!(unary self, narrow, dim, start, length)
+  }
+
+  fn select(&self, dim: usize, index: usize) -> Self {
+    match_storage!(unary self, select, dim, index)
+  }
+
+  fn slice_scatter(&self, args: &[SliceArg], src: &Self) -> Self {
+    match (self, src) {
+      (Storage::Cpu(cpu_self), Storage::Cpu(cpu_src)) => Storage::Cpu(cpu_self.slice_scatter(args, cpu_src)),
+      _ => unimplemented!("Cross-device operations not supported"),
+    }
+  }
+
+  fn masked_select(&self, mask: &Self) -> Self {
+    match_storage!(binary self, masked_select, mask)
+  }
+
+  fn masked_scatter(&self, mask: &Self, source: &Self) -> Self {
+    match (self, mask, source) {
+      (Storage::Cpu(cpu_self), Storage::Cpu(cpu_mask), Storage::Cpu(cpu_source)) => Storage::Cpu(cpu_self.masked_scatter(cpu_mask, cpu_source)),
+      _ => unimplemented!("Cross-device operations not supported"),
+    }
+  }
+
+  fn take(&self, dim: usize, indices: &Self) -> Self {
+    match (self, indices) {
+      (Storage::Cpu(cpu_self), Storage::Cpu(cpu_indices)) => Storage::Cpu(cpu_self.take(dim, cpu_indices)),
+      _ => unimplemented!("Cross-device operations not supported"),
+    }
+  }
+
+  fn cat(tensors: &[&Self], dim: usize) -> Self {
+    let cpu: Vec<&CpuStorage> = tensors.iter().map(|tensor| match tensor {
+      Storage::Cpu(cpu) => cpu,
+      _ => unimplemented!("Cross-device operations not supported"),
+    }).collect();
+    Storage::Cpu(CpuStorage::cat(&cpu, dim))
+  }
+
+  fn stack(tensors: &[&Self], dim: usize) -> Self {
+    let cpu: Vec<&CpuStorage> = tensors.iter().map(|tensor| match tensor {
+      Storage::Cpu(cpu) => cpu,
+      _ => unimplemented!("Cross-device operations not supported"),
+    }).collect();
+    Storage::Cpu(CpuStorage::stack(&cpu, dim))
+  }
+
+  fn split(&self, split_size: usize, dim: usize) -> Vec<Self> {
+    match self {
+      Storage::Cpu(cpu) => cpu.split(split_size, dim).into_iter().map(Storage::Cpu).collect(),
+      _ => unimplemented!("Device not supported"),
+    }
+  }
+
+  fn split_with_sizes(&self, sizes: &[usize], dim: usize) -> Vec<Self> {
+    match self {
+      Storage::Cpu(cpu) => cpu.split_with_sizes(sizes, dim).into_iter().map(Storage::Cpu).collect(),
+      _ => unimplemented!("Device not supported"),
+    }
+  }
+
+  fn chunk(&self, chunks: usize, dim: usize) -> Vec<Self> {
+    match self {
+      Storage::Cpu(cpu) => cpu.chunk(chunks, dim).into_iter().map(Storage::Cpu).collect(),
+      _ => unimplemented!("Device not supported"),
+    }
+  }
+
+  fn unbind(&self, dim: usize) -> Vec<Self> {
+    match self {
+      Storage::Cpu(cpu) => cpu.unbind(dim).into_iter().map(Storage::Cpu).collect(),
+      _ => unimplemented!("Device not supported"),
+    }
+  }
 }
 
 
@@ -252,51 +430,76 @@ impl TransformOps for Tensor {
     todo!()
   }
 
+  /// The in-place shape ops rebind a tensor that tracks gradients to a recorded
+  /// view of itself, so gradients still reach the original tensor.
   fn reshape(&mut self, new_shape: Vec<usize>) {
-    self.tensor_mut().set_shape(new_shape);
+    if self.tracks_grad() {
+      *self = self.view(new_shape);
+      return;
+    }
+    self.tensor_mut().reshape(new_shape);
   }
 
   fn transpose(&self) -> Self {
     // Transpose by swapping dimensions & strides
 
     let tensor = self.tensor().transpose();
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
     if requires_grad {
-      result.set_grad_fn(Some(Rc::new(PermuteGrad::new(self, &result))));
+      result.set_grad_fn(Some(Rc::new(PermuteGrad::new(self, &[1, 0], &result))));
     }
     
     result
   }
 
   fn permute(&mut self, dims: &[usize]) {
+    if self.tracks_grad() {
+      let mut tensor = self.tensor().clone();
+      tensor.permute(dims);
+      let mut result = Tensor::new(tensor, self.device(), true);
+      result.set_grad_fn(Some(Rc::new(PermuteGrad::new(self, dims, &result))));
+      *self = result;
+      return;
+    }
     self.tensor_mut().permute(dims);
   }
 
   fn flatten(&mut self) {
+    if self.tracks_grad() {
+      *self = self.view(vec![self.shape().iter().product()]);
+      return;
+    }
     self.tensor_mut().flatten();
   }
 
   fn squeeze(&mut self) {
+    if self.tracks_grad() {
+      *self = self.view(self.shape().iter().filter(|&&size| size != 1).cloned().collect());
+      return;
+    }
     self.tensor_mut().squeeze();
   } 
 
   fn unsqueeze(&mut self, dim: usize) {
+    if self.tracks_grad() {
+      let mut shape = self.shape().clone();
+      shape.insert(dim, 1);
+      *self = self.view(shape);
+      return;
+    }
     self.tensor_mut().unsqueeze(dim);
   }
 
   fn broadcast(&self, new_shape: &[usize]) -> Self {
     let tensor = self.tensor().broadcast(new_shape);
-    
-    // When broadcasting, we need to maintain the gradient tracking
-    let requires_grad = *self.requires_grad();
+    let requires_grad = self.tracks_grad();
     let mut result = Tensor::new(tensor, self.device(), requires_grad);
     
-    // If original tensor requires gradient, the broadcasted tensor
-    // should have the same gradient function
+    // Gradients are summed back over the broadcast dimensions
     if requires_grad {
-      result.set_grad_fn(self.grad_fn());
+      result.set_grad_fn(Some(Rc::new(BroadcastGrad::new(self, &result))));
     }
     
     result
@@ -315,6 +518,221 @@ impl TransformOps for Tensor {
   }
 
   fn broadcast_tensors(a: &Self, b: &Self) -> (Self, Self) where Self: Sized {
-    todo!()
+    if let Err(error) = check_device("broadcast_tensors", a.device(), b.device()) {
+      panic!("{}", error);
+    }
+    let broadcast_shape = a.compute_broadcast_shape(b.shape());
+    (a.broadcast(&broadcast_shape), b.broadcast(&broadcast_shape))
+  }
+
+  fn index_select(&self, dim: usize, indices: &[usize]) -> Self {
+    let tensor = self.tensor().index_select(dim, indices);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(IndexSelectGrad::new(self, dim, indices, &result))));
+    }
+
+    result
+  }
+
+  fn index_add(&self, dim: usize, indices: &[usize], source: &Self) -> Self {
+    let tensor = self.tensor().index_add(dim, indices, source.tensor());
+    let requires_grad = self.tracks_grad() || source.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(IndexAddGrad::new(self, dim, indices, source, &result))));
+    }
+
+    result
   }
-}
\ No newline at end of file
+
+  fn gather(&self, dim: usize, index: &Self) -> Self {
+    let tensor = self.tensor().gather(dim, index.tensor());
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(GatherGrad::new(self, dim, index, &result))));
+    }
+
+    result
+  }
+
+  fn scatter(&self, dim: usize, index: &Self, src: &Self) -> Self {
+    let tensor = self.tensor().scatter(dim, index.tensor(), src.tensor());
+    let requires_grad = self.tracks_grad() || src.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(ScatterGrad::new(self, dim, index, src, &result, false))));
+    }
+
+    result
+  }
+
+  fn scatter_add(&self, dim: usize, index: &Self, src: &Self) -> Self {
+    let tensor = self.tensor().scatter_add(dim, index.tensor(), src.tensor());
+    let requires_grad = self.tracks_grad() || src.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(ScatterGrad::new(self, dim, index, src, &result, true))));
+    }
+
+    result
+  }
+
+  fn contiguous(&self) -> Self {
+    let tensor = self.tensor().contiguous();
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(SliceGrad::new(self, &[], &result))));
+    }
+
+    result
+  }
+
+  fn slice(&self, args: &[SliceArg]) -> Self {
+    let tensor = self.tensor().slice(args);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(SliceGrad::new(self, args, &result))));
+    }
+
+    result
+  }
+
+  fn narrow(&self, dim: usize, start: usize, length: usize) -> Self {
+    if dim >= self.shape().len() || start + length > self.shape()[dim] {
+      panic!("narrow: range {}..{} out of bounds for dimension {} of tensor with shape {:?}", start, start + length, dim, self.shape());
+    }
+    self.slice(&SliceArg::narrow(dim, start, length))
+  }
+
+  fn select(&self, dim: usize, index: usize) -> Self {
+    if dim >= self.shape().len() {
+      panic!("select: dimension {} out of range for tensor of rank {}", dim, self.shape().len());
+    }
+    self.slice(&SliceArg::select(dim, index))
+  }
+
+  fn slice_scatter(&self, args: &[SliceArg], src: &Self) -> Self {
+    let tensor = self.tensor().slice_scatter(args, src.tensor());
+    let requires_grad = self.tracks_grad() || src.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(SliceScatterGrad::new(self, args, src, &result))));
+    }
+
+    result
+  }
+
+  fn masked_select(&self, mask: &Self) -> Self {
+    let tensor = self.tensor().masked_select(mask.tensor());
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(MaskedSelectGrad::new(self, mask, &result))));
+    }
+
+    result
+  }
+
+  fn masked_scatter(&self, mask: &Self, source: &Self) -> Self {
+    let tensor = self.tensor().masked_scatter(mask.tensor(), source.tensor());
+    let requires_grad = self.tracks_grad() || source.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(MaskedScatterGrad::new(self, mask, source, &result))));
+    }
+
+    result
+  }
+
+  fn take(&self, dim: usize, indices: &Self) -> Self {
+    let tensor = self.tensor().take(dim, indices.tensor());
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(tensor, self.device(), requires_grad);
+
+    if requires_grad {
+      // `take` is an index_select over the flattened indices
+      let count = indices.shape().iter().product();
+      let flat = indices.tensor().view(vec![count]);
+      let positions: Vec<usize> = (0..count)
+        .map(|i| SliceArg::Index(flat.get(&[i]) as isize).resolve(self.shape()[dim]).0)
+        .collect();
+      result.set_grad_fn(Some(Rc::new(IndexSelectGrad::new(self, dim, &positions, &result))));
+    }
+
+    result
+  }
+
+  fn cat(tensors: &[&Self], dim: usize) -> Self {
+    let storages: Vec<&Storage> = tensors.iter().map(|tensor| tensor.tensor()).collect();
+    let tensor = Storage::cat(&storages, dim);
+    let requires_grad = tensors.iter().any(|tensor| tensor.tracks_grad());
+    let mut result = Tensor::new(tensor, tensors[0].device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(CatGrad::new(tensors, dim, &result))));
+    }
+
+    result
+  }
+
+  fn stack(tensors: &[&Self], dim: usize) -> Self {
+    let storages: Vec<&Storage> = tensors.iter().map(|tensor| tensor.tensor()).collect();
+    let tensor = Storage::stack(&storages, dim);
+    let requires_grad = tensors.iter().any(|tensor| tensor.tracks_grad());
+    let mut result = Tensor::new(tensor, tensors[0].device(), requires_grad);
+
+    if requires_grad {
+      result.set_grad_fn(Some(Rc::new(StackGrad::new(tensors, dim, &result))));
+    }
+
+    result
+  }
+
+  fn split(&self, split_size: usize, dim: usize) -> Vec<Self> {
+    if split_size == 0 {
+      panic!("split: split size must be positive");
+    }
+    let len = self.shape()[dim];
+    let sizes: Vec<usize> = (0..len).step_by(split_size).map(|start| split_size.min(len - start)).collect();
+    self.split_with_sizes(&sizes, dim)
+  }
+
+  fn split_with_sizes(&self, sizes: &[usize], dim: usize) -> Vec<Self> {
+    if sizes.iter().sum::<usize>() != self.shape()[dim] {
+      panic!("split_with_sizes: sizes {:?} don't add up to dimension {} of size {}", sizes, dim, self.shape()[dim]);
+    }
+    let mut start = 0;
+    sizes.iter().map(|&size| {
+      let piece = self.narrow(dim, start, size);
+      start += size;
+      piece
+    }).collect()
+  }
+
+  fn chunk(&self, chunks: usize, dim: usize) -> Vec<Self> {
+    if chunks == 0 {
+      panic!("chunk: number of chunks must be positive");
+    }
+    let len = self.shape()[dim];
+    self.split(len.div_ceil(chunks).max(1), dim)
+  }
+
+  fn unbind(&self, dim: usize) -> Vec<Self> {
+    (0..self.shape()[dim]).map(|index| self.select(dim, index)).collect()
+  }
+}
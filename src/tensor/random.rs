@@ -0,0 +1,83 @@
+use std::sync::{Arc, Mutex, OnceLock};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+
+struct GeneratorState {
+  seed: u64,
+  rng: StdRng,
+}
+
+/// A seedable source of randomness. Clones share the same stream, so a generator
+/// can be handed to several creation functions and keep advancing; use `fork` to
+/// derive an independent, reproducible stream (e.g. one per data-loading worker).
+#[derive(Clone)]
+pub struct Generator {
+  state: Arc<Mutex<GeneratorState>>,
+}
+
+impl Generator {
+  pub fn new(seed: u64) -> Self {
+    Generator {
+      state: Arc::new(Mutex::new(GeneratorState { seed, rng: StdRng::seed_from_u64(seed) })),
+    }
+  }
+
+  /// Creates a generator seeded from the operating system's entropy source.
+  pub fn from_entropy() -> Self {
+    Generator::new(rand::random())
+  }
+
+  /// Resets the stream to the start of the sequence for `seed`.
+  pub fn manual_seed(&self, seed: u64) {
+    let mut state = self.state.lock().unwrap();
+    state.seed = seed;
+    state.rng = StdRng::seed_from_u64(seed);
+  }
+
+  /// The seed this stream was last (re)started from.
+  pub fn initial_seed(&self) -> u64 {
+    self.state.lock().unwrap().seed
+  }
+
+  /// Derives a new, independent generator. The child's seed is drawn from this
+  /// stream, so forking is itself deterministic.
+  pub fn fork(&self) -> Generator {
+    let seed = self.state.lock().unwrap().rng.next_u64();
+    Generator::new(seed)
+  }
+
+  /// Runs `f` with exclusive access to the underlying RNG.
+  pub fn with_rng<R>(&self, f: impl FnOnce(&mut StdRng) -> R) -> R {
+    let mut state = self.state.lock().unwrap();
+    f(&mut state.rng)
+  }
+}
+
+impl std::fmt::Debug for Generator {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Generator(seed={})", self.initial_seed())
+  }
+}
+
+
+static DEFAULT_GENERATOR: OnceLock<Generator> = OnceLock::new();
+
+/// The process-wide generator used whenever no explicit generator is passed.
+pub fn default_generator() -> Generator {
+  DEFAULT_GENERATOR.get_or_init(Generator::from_entropy).clone()
+}
+
+/// Seeds the default generator, making every subsequent random operation that
+/// does not receive its own generator reproducible.
+pub fn manual_seed(seed: u64) {
+  default_generator().manual_seed(seed);
+}
+
+/// Returns `generator` if one was given, the default generator otherwise.
+pub(crate) fn generator_or_default(generator: Option<&Generator>) -> Generator {
+  match generator {
+    Some(generator) => generator.clone(),
+    None => default_generator(),
+  }
+}
@@ -14,11 +14,11 @@ impl Tensor {
     // Transpose by swapping dimensions & strides
 
     let new_storage = self.tensor().transpose();
-    let requires_grad = *self.requires_grad();
-    let mut result = Tensor::new(new_storage, self.device(), *self.requires_grad());
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(new_storage, self.device(), self.tracks_grad());
     
     if requires_grad {
-      result.set_grad_fn(Some(Rc::new(PermuteGrad::new(self, &result))));
+      result.set_grad_fn(Some(Rc::new(PermuteGrad::new(self, &[1, 0], &result))));
     }
     
     result
@@ -42,15 +42,12 @@ impl Tensor {
 
   fn broadcast(&self, new_shape: &[usize]) -> Self {
     let new_storage = self.tensor().broadcast(new_shape);
+    let requires_grad = self.tracks_grad();
+    let mut result = Tensor::new(new_storage, self.device(), requires_grad);
     
-    // When broadcasting, we need to maintain the gradient tracking
-    let requires_grad = *self.requires_grad();
-    let mut result = Tensor::new(new_storage, self.device(), *self.requires_grad());
-    
-    // If original tensor requires gradient, the broadcasted tensor
-    // should have the same gradient function
+    // Gradients are summed back over the broadcast dimensions
     if requires_grad {
-      result.set_grad_fn(self.grad_fn());
+      result.set_grad_fn(Some(Rc::new(BroadcastGrad::new(self, &result))));
     }
     
     result
@@ -72,18 +69,7 @@ impl Tensor {
   fn broadcast_tensors(a: &Self, b: &Self) -> (Self, Self) {
     if (a.device() != b.device()) { panic!("Tensors not on same device!") }
 
-    let (broadcast_a, broadcast_b) = Storage::broadcast_tensors(a.tensor(), b.tensor());
-
-    let mut tensor_a = Tensor::new(broadcast_a, a.device(), *a.requires_grad());
-    let mut tensor_b = Tensor::new(broadcast_b, b.device(), *b.requires_grad());
-
-    if *a.requires_grad() {
-      tensor_a.set_grad_fn(a.grad_fn());
-    }
-    if *b.requires_grad() {
-      tensor_b.set_grad_fn(b.grad_fn());
-    }
-
-    (tensor_a, tensor_b)
+    let broadcast_shape = a.compute_broadcast_shape(b.shape());
+    (a.broadcast(&broadcast_shape), b.broadcast(&broadcast_shape))
   }
 }
\ No newline at end of file
@@ -0,0 +1,122 @@
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+
+/// One entry of a slicing expression. Negative positions count from the end of the
+/// dimension, as in Python. Build these with the `s![]` macro, e.g.
+/// `s![1..3, .., ..;2]` or `s![-1, 0]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliceArg {
+  /// Selects a single position and drops the dimension.
+  Index(isize),
+  /// Keeps every `step`-th position in [start, end); `None` means the end of the dimension.
+  Range { start: isize, end: Option<isize>, step: usize },
+}
+
+impl SliceArg {
+  /// The whole dimension (`..`).
+  pub fn full() -> Self {
+    SliceArg::Range { start: 0, end: None, step: 1 }
+  }
+
+  /// Arguments keeping `length` positions of `dim`, starting at `start`.
+  pub fn narrow(dim: usize, start: usize, length: usize) -> Vec<SliceArg> {
+    let mut args = vec![SliceArg::full(); dim];
+    args.push(SliceArg::Range { start: start as isize, end: Some((start + length) as isize), step: 1 });
+    args
+  }
+
+  /// Arguments picking position `index` of `dim`.
+  pub fn select(dim: usize, index: usize) -> Vec<SliceArg> {
+    let mut args = vec![SliceArg::full(); dim];
+    args.push(SliceArg::Index(index as isize));
+    args
+  }
+
+  /// Replaces the step of a range, as in `..;2`.
+  pub fn step_by(self, step: usize) -> Self {
+    if step == 0 {
+      panic!("Slice step must be positive");
+    }
+    match self {
+      SliceArg::Range { start, end, .. } => SliceArg::Range { start, end, step },
+      SliceArg::Index(_) => panic!("A step can only be applied to a range"),
+    }
+  }
+
+  /// Resolves the argument against a dimension of `size`, returning the start
+  /// position, the number of selected positions and the step. Ranges are clamped
+  /// to the dimension; out-of-range indices panic.
+  pub fn resolve(&self, size: usize) -> (usize, usize, usize) {
+    let size = size as isize;
+    let wrap = |position: isize| if position < 0 { position + size } else { position };
+
+    match *self {
+      SliceArg::Index(index) => {
+        let position = wrap(index);
+        if position < 0 || position >= size {
+          panic!("Index {} out of bounds for dimension of size {}", index, size);
+        }
+        (position as usize, 1, 1)
+      },
+      SliceArg::Range { start, end, step } => {
+        let start = wrap(start).clamp(0, size);
+        let end = end.map(wrap).unwrap_or(size).clamp(0, size);
+        let len = if end > start { (end - start + step as isize - 1) / step as isize } else { 0 };
+        (start as usize, len as usize, step)
+      },
+    }
+  }
+}
+
+macro_rules! impl_slice_arg_from {
+  ($($t:ty),*) => {
+    $(
+      impl From<$t> for SliceArg {
+        fn from(index: $t) -> Self {
+          SliceArg::Index(index as isize)
+        }
+      }
+
+      impl From<Range<$t>> for SliceArg {
+        fn from(range: Range<$t>) -> Self {
+          SliceArg::Range { start: range.start as isize, end: Some(range.end as isize), step: 1 }
+        }
+      }
+
+      impl From<RangeInclusive<$t>> for SliceArg {
+        fn from(range: RangeInclusive<$t>) -> Self {
+          let end = *range.end() as isize + 1;
+          // `..=-1` reaches the end of the dimension
+          SliceArg::Range { start: *range.start() as isize, end: if end == 0 { None } else { Some(end) }, step: 1 }
+        }
+      }
+
+      impl From<RangeFrom<$t>> for SliceArg {
+        fn from(range: RangeFrom<$t>) -> Self {
+          SliceArg::Range { start: range.start as isize, end: None, step: 1 }
+        }
+      }
+
+      impl From<RangeTo<$t>> for SliceArg {
+        fn from(range: RangeTo<$t>) -> Self {
+          SliceArg::Range { start: 0, end: Some(range.end as isize), step: 1 }
+        }
+      }
+
+      impl From<RangeToInclusive<$t>> for SliceArg {
+        fn from(range: RangeToInclusive<$t>) -> Self {
+          let end = range.end as isize + 1;
+          SliceArg::Range { start: 0, end: if end == 0 { None } else { Some(end) }, step: 1 }
+        }
+      }
+    )*
+  };
+}
+
+impl_slice_arg_from!(i32, i64, isize, usize);
+
+impl From<RangeFull> for SliceArg {
+  fn from(_: RangeFull) -> Self {
+    SliceArg::full()
+  }
+}
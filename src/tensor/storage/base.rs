@@ -39,6 +39,14 @@ impl DeviceStorage for Storage {
     match_self!(call self, set_data(data));
   }
 
+  fn dtype(&self) -> DType {
+    match_self!(call self, dtype())
+  }
+
+  fn to_dtype(&self, dtype: DType) -> Self {
+    match_self!(storage self, to_dtype(dtype))
+  }
+
   fn shape(&self) -> &Vec<usize> {
     match_self!(call self, shape())
   }
@@ -59,6 +67,14 @@ impl DeviceStorage for Storage {
     match_self!(call self, offset())
   }
 
+  fn set_offset(&mut self, offset: usize) {
+    match_self!(call self, set_offset(offset));
+  }
+
+  fn device(&self) -> Device {
+    match_self!(call self, device())
+  }
+
   fn get(&self, indices: &[usize]) -> f32 {
     match_self!(call self, get(indices))
   }
@@ -75,3 +91,12 @@ impl DeviceStorage for Storage {
     match_self!(call self, is_contiguous())
   }
 }
+
+impl Storage {
+  /// The elements in row-major order, cast to `T`.
+  pub fn to_vec<T: Element>(&self) -> Vec<T> {
+    match self {
+      Storage::Cpu(cpu) => strided_values(cpu),
+    }
+  }
+}
@@ -19,6 +19,14 @@ macro_rules! match_device {
   };
 }
 
+impl Storage {
+  /// Creates a storage holding `data` with its native dtype.
+  pub fn from_vec<T: Element>(data: Vec<T>, shape: Vec<usize>, device: Option<Device>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, from_vec(data, shape))
+  }
+}
+
 impl DeviceStorageCreation for Storage {
   fn zeros(shape: Vec<usize>, device: Option<Device>, _requires_grad: Option<bool>) -> Self {
     let device = device.expect("Storage: device must be non-null!");
@@ -40,8 +48,55 @@ impl DeviceStorageCreation for Storage {
     match_device!(storage device, from_ndarray(data, None, None))
   }
 
-  fn uniform(l_bound: f32, r_bound: f32, shape: Vec<usize>, device: Option<Device>, _requires_grad: Option<bool>) -> Self {
+  fn uniform(l_bound: f32, r_bound: f32, shape: Vec<usize>, device: Option<Device>, _requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, uniform(l_bound, r_bound, shape, None, None, generator))
+  }
+
+  fn full(shape: Vec<usize>, value: f32, device: Option<Device>, _requires_grad: Option<bool>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, full(shape, value, None, None))
+  }
+
+  fn arange(start: f32, end: f32, step: f32, device: Option<Device>, _requires_grad: Option<bool>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, arange(start, end, step, None, None))
+  }
+
+  fn linspace(start: f32, end: f32, steps: usize, device: Option<Device>, _requires_grad: Option<bool>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, linspace(start, end, steps, None, None))
+  }
+
+  fn eye(n: usize, device: Option<Device>, _requires_grad: Option<bool>) -> Self {
     let device = device.expect("Storage: device must be non-null!");
-    match_device!(storage device, uniform(l_bound, r_bound, shape, None, None))
+    match_device!(storage device, eye(n, None, None))
+  }
+
+  fn normal(mean: f32, std: f32, shape: Vec<usize>, device: Option<Device>, _requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, normal(mean, std, shape, None, None, generator))
+  }
+
+  fn bernoulli(p: f32, shape: Vec<usize>, device: Option<Device>, _requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, bernoulli(p, shape, None, None, generator))
+  }
+
+  fn randint(low: i64, high: i64, shape: Vec<usize>, device: Option<Device>, _requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, randint(low, high, shape, None, None, generator))
+  }
+
+  fn randperm(n: usize, device: Option<Device>, _requires_grad: Option<bool>, generator: Option<&Generator>) -> Self {
+    let device = device.expect("Storage: device must be non-null!");
+    match_device!(storage device, randperm(n, None, None, generator))
+  }
+
+  fn multinomial(probs: &Self, num_samples: usize, replacement: bool, generator: Option<&Generator>) -> Self {
+    match probs {
+      Storage::Cpu(cpu) => Storage::Cpu(CpuStorage::multinomial(cpu, num_samples, replacement, generator)),
+      _ => unimplemented!("Device not supported"),
+    }
   }
 }
@@ -4,10 +4,10 @@ use std::cell::RefCell;
 use ndarray::{ArrayBase, Dimension};
 use num_traits::cast::AsPrimitive;
 
-use crate::{CpuStorage};
+use crate::{check_data_len, check_index, CpuStorage, DType, FerriteError, Generator};
 
 // Device types
-#[derive(Clone, Copy, Hash, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
 pub enum Device {
   Cpu,
   Cuda,
@@ -19,6 +19,21 @@ pub trait DeviceStorageStatic : DeviceStorage {
 
   fn new_with_stride(data: Vec<f32>, shape: Vec<usize>, stride: Vec<usize>) -> Self;
 
+  /// Like `new`, but reports a data/shape mismatch instead of panicking.
+  fn try_new(data: Vec<f32>, shape: Vec<usize>) -> Result<Self, FerriteError> where Self: Sized {
+    check_data_len("new", data.len(), &shape)?;
+    Ok(Self::new(data, shape))
+  }
+
+  /// Like `new_with_stride`, but reports invalid layouts instead of panicking.
+  fn try_new_with_stride(data: Vec<f32>, shape: Vec<usize>, stride: Vec<usize>) -> Result<Self, FerriteError> where Self: Sized {
+    check_data_len("new_with_stride", data.len(), &shape)?;
+    if stride.len() != shape.len() {
+      return Err(FerriteError::invalid("new_with_stride", format!("{} strides given for a tensor of rank {}", stride.len(), shape.len())));
+    }
+    Ok(Self::new_with_stride(data, shape, stride))
+  }
+
   fn create(data: Arc<RwLock<Vec<f32>>>, shape: Vec<usize>, stride: Vec<usize>) -> Self;
 
   fn compute_strides(shape: &Vec<usize>) -> Vec<usize>;
@@ -33,7 +48,70 @@ pub trait DeviceStorageCreation : DeviceStorage {
     T: AsPrimitive<f32>,
     D: Dimension;
 
-  fn uniform(l_bound: f32, r_bound: f32, shape: Vec<usize>, device: Option<Device>, requires_grad: Option<bool>) -> Self;
+  fn uniform(l_bound: f32, r_bound: f32, shape: Vec<usize>, device: Option<Device>, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self;
+
+  fn full(shape: Vec<usize>, value: f32, device: Option<Device>, requires_grad: Option<bool>) -> Self;
+
+  /// Values from `start` (inclusive) to `end` (exclusive), `step` apart.
+  fn arange(start: f32, end: f32, step: f32, device: Option<Device>, requires_grad: Option<bool>) -> Self;
+
+  /// `steps` evenly spaced values from `start` to `end`, both inclusive.
+  fn linspace(start: f32, end: f32, steps: usize, device: Option<Device>, requires_grad: Option<bool>) -> Self;
+
+  /// The [n, n] identity matrix.
+  fn eye(n: usize, device: Option<Device>, requires_grad: Option<bool>) -> Self;
+
+  fn normal(mean: f32, std: f32, shape: Vec<usize>, device: Option<Device>, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self;
+
+  fn randn(shape: Vec<usize>, device: Option<Device>, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self where Self: Sized {
+    Self::normal(0., 1., shape, device, requires_grad, generator)
+  }
+
+  /// Ones with probability `p`, zeros otherwise.
+  fn bernoulli(p: f32, shape: Vec<usize>, device: Option<Device>, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self;
+
+  /// Integers drawn uniformly from [low, high).
+  fn randint(low: i64, high: i64, shape: Vec<usize>, device: Option<Device>, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self;
+
+  /// A random permutation of 0..n.
+  fn randperm(n: usize, device: Option<Device>, requires_grad: Option<bool>, generator: Option<&Generator>) -> Self;
+
+  /// Draws `num_samples` category indices from each row of the [C] or [N, C]
+  /// (unnormalized) probabilities, returning a [num_samples] or [N, num_samples] result.
+  fn multinomial(probs: &Self, num_samples: usize, replacement: bool, generator: Option<&Generator>) -> Self;
+
+  // The `*_like` constructors keep the dtype of `other`
+  fn zeros_like(other: &Self) -> Self where Self: Sized {
+    Self::zeros(other.shape().clone(), Some(other.device()), None).to_dtype(other.dtype())
+  }
+
+  fn ones_like(other: &Self) -> Self where Self: Sized {
+    Self::ones(other.shape().clone(), Some(other.device()), None).to_dtype(other.dtype())
+  }
+
+  fn full_like(other: &Self, value: f32) -> Self where Self: Sized {
+    Self::full(other.shape().clone(), value, Some(other.device()), None).to_dtype(other.dtype())
+  }
+
+  fn uniform_like(other: &Self, l_bound: f32, r_bound: f32, generator: Option<&Generator>) -> Self where Self: Sized {
+    Self::uniform(l_bound, r_bound, other.shape().clone(), Some(other.device()), None, generator).to_dtype(other.dtype())
+  }
+
+  fn normal_like(other: &Self, mean: f32, std: f32, generator: Option<&Generator>) -> Self where Self: Sized {
+    Self::normal(mean, std, other.shape().clone(), Some(other.device()), None, generator).to_dtype(other.dtype())
+  }
+
+  fn randn_like(other: &Self, generator: Option<&Generator>) -> Self where Self: Sized {
+    Self::randn(other.shape().clone(), Some(other.device()), None, generator).to_dtype(other.dtype())
+  }
+
+  fn bernoulli_like(other: &Self, p: f32, generator: Option<&Generator>) -> Self where Self: Sized {
+    Self::bernoulli(p, other.shape().clone(), Some(other.device()), None, generator).to_dtype(other.dtype())
+  }
+
+  fn randint_like(other: &Self, low: i64, high: i64, generator: Option<&Generator>) -> Self where Self: Sized {
+    Self::randint(low, high, other.shape().clone(), Some(other.device()), None, generator).to_dtype(other.dtype())
+  }
 }
 
 
@@ -46,6 +124,11 @@ pub trait DeviceStorage  {
 
   fn set_data(&mut self, data: Vec<f32>);
 
+  fn dtype(&self) -> DType;
+
+  /// Copy of the storage converted to `dtype`, or a cheap clone if it already matches.
+  fn to_dtype(&self, dtype: DType) -> Self where Self: Sized;
+
   fn shape(&self) -> &Vec<usize>;
 
   fn set_shape(&mut self, shape: Vec<usize>);
@@ -56,10 +139,25 @@ pub trait DeviceStorage  {
 
   fn offset(&self) -> usize;
 
+  fn set_offset(&mut self, offset: usize);
+
+  fn device(&self) -> Device;
+
   fn get(&self, indices: &[usize]) -> f32;
 
   fn set(&mut self, indices: &[usize], value: f32);
 
+  fn try_get(&self, indices: &[usize]) -> Result<f32, FerriteError> {
+    check_index("get", self.shape(), indices)?;
+    Ok(self.get(indices))
+  }
+
+  fn try_set(&mut self, indices: &[usize], value: f32) -> Result<(), FerriteError> {
+    check_index("set", self.shape(), indices)?;
+    self.set(indices, value);
+    Ok(())
+  }
+
   fn make_contiguous(&self) -> (Vec<f32>, i32);
 
   fn is_contiguous(&self) -> bool;
@@ -21,7 +21,7 @@ impl Display for Tensor {
     self.tensor();
   }
 
-  fn print_data_recursive<'a>(data: &'a [f32], shape: &'a [usize], stride: &'a [usize]) -> String {
+  fn print_data_recursive<'a, T: fmt::Display>(data: &'a [T], shape: &'a [usize], stride: &'a [usize]) -> String {
     unimplemented!()
   }
 
@@ -0,0 +1,78 @@
+use ferrite::gemm::{dgemm, sgemm};
+
+/// Deterministic values in [-1, 1).
+fn values(len: usize, seed: u64) -> Vec<f64> {
+  let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+  (0..len).map(|_| {
+    state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    (state >> 11) as f64 / (1u64 << 52) as f64 - 1.
+  }).collect()
+}
+
+/// `alpha * op(A) * op(B) + beta * C` with a plain triple loop.
+#[allow(clippy::too_many_arguments)]
+fn naive(trans_a: bool, trans_b: bool, m: usize, n: usize, k: usize, alpha: f64, a: &[f64], b: &[f64], beta: f64, c: &[f64]) -> Vec<f64> {
+  let (lda, ldb) = (if trans_a { m } else { k }, if trans_b { k } else { n });
+  let mut result = vec![0.; m * n];
+  for i in 0..m {
+    for j in 0..n {
+      let mut sum = 0.;
+      for p in 0..k {
+        let a_ip = if trans_a { a[p * lda + i] } else { a[i * lda + p] };
+        let b_pj = if trans_b { b[j * ldb + p] } else { b[p * ldb + j] };
+        sum += a_ip * b_pj;
+      }
+      result[i * n + j] = alpha * sum + beta * c[i * n + j];
+    }
+  }
+  result
+}
+
+const SIZES: [(usize, usize, usize); 4] = [(1, 1, 1), (7, 13, 5), (65, 300, 129), (1, 513, 257)];
+const TRANSPOSES: [(bool, bool); 4] = [(false, false), (true, false), (false, true), (true, true)];
+
+fn check(m: usize, n: usize, k: usize, trans_a: bool, trans_b: bool, alpha: f64, beta: f64) {
+  let a = values(m * k, 1);
+  let b = values(k * n, 2);
+  let c = values(m * n, 3);
+  let (lda, ldb) = (if trans_a { m } else { k }, if trans_b { k } else { n });
+  let expected = naive(trans_a, trans_b, m, n, k, alpha, &a, &b, beta, &c);
+  let case = format!("{}x{}x{} trans_a={} trans_b={}", m, n, k, trans_a, trans_b);
+
+  let mut c64 = c.clone();
+  dgemm(trans_a, trans_b, m, n, k, alpha, &a, lda, &b, ldb, beta, &mut c64, n);
+  for (index, (&value, &expected)) in c64.iter().zip(&expected).enumerate() {
+    assert!((value - expected).abs() < 1e-12 * k as f64, "dgemm {} at {}: {} != {}", case, index, value, expected);
+  }
+
+  let to_f32 = |v: &[f64]| v.iter().map(|&x| x as f32).collect::<Vec<f32>>();
+  let mut c32 = to_f32(&c);
+  sgemm(trans_a, trans_b, m, n, k, alpha as f32, &to_f32(&a), lda, &to_f32(&b), ldb, beta as f32, &mut c32, n);
+  for (index, (&value, &expected)) in c32.iter().zip(&expected).enumerate() {
+    assert!((value as f64 - expected).abs() < 1e-5 * k as f64, "sgemm {} at {}: {} != {}", case, index, value, expected);
+  }
+}
+
+#[test]
+fn gemm_matches_naive_matmul() {
+  for &(m, n, k) in &SIZES {
+    for &(trans_a, trans_b) in &TRANSPOSES {
+      check(m, n, k, trans_a, trans_b, 1., 0.);
+    }
+  }
+}
+
+#[test]
+fn gemm_scales_and_accumulates() {
+  for &(m, n, k) in &SIZES {
+    check(m, n, k, false, true, 1.5, 0.5);
+  }
+}
+
+#[test]
+fn gemm_overwrites_nan_when_beta_is_zero() {
+  let a = [1., 2., 3., 4.];
+  let mut c = [f64::NAN; 4];
+  dgemm(false, false, 2, 2, 2, 1., &a, 2, &a, 2, 0., &mut c, 2);
+  assert_eq!(c, [7., 10., 15., 22.]);
+}
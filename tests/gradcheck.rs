@@ -0,0 +1,363 @@
+use ferrite::prelude::*;
+use ferrite::loss::*;
+
+
+fn tensor(data: &[f32], shape: &[usize]) -> Tensor {
+  Tensor::from_vec(data.to_vec(), shape.to_vec(), Device::Cpu, None)
+}
+
+/// A [2, 3] input away from zero and without ties, so kinks and argmax
+/// switches stay out of the finite-difference stencil.
+fn input() -> Tensor {
+  tensor(&[0.5, -1.2, 2.1, 0.3, -0.7, 1.4], &[2, 3])
+}
+
+fn positive() -> Tensor {
+  tensor(&[0.5, 1.2, 2.1, 0.3, 0.7, 1.4], &[2, 3])
+}
+
+fn check<F: Fn(&[Tensor]) -> Tensor>(f: F, inputs: &[&Tensor]) {
+  let report = gradcheck(f, inputs, 1e-6, 1e-5, 1e-3);
+  assert!(report.is_ok(), "{}", report);
+}
+
+/// For ops whose output is f32 even for f64 inputs.
+fn check_f32<F: Fn(&[Tensor]) -> Tensor>(f: F, inputs: &[&Tensor]) {
+  let report = gradcheck(f, inputs, 1e-3, 1e-3, 1e-2);
+  assert!(report.is_ok(), "{}", report);
+}
+
+
+#[test]
+fn gradcheck_reports_wrong_gradients() {
+  // Detaching hides the dependency from backward but not from finite differences
+  let report = gradcheck(|x| &x[0] * &x[0].detach(), &[&input()], 1e-6, 1e-5, 1e-3);
+  assert_eq!(report.mismatches.len(), 6);
+  let mismatch = &report.mismatches[1];
+  assert_eq!((mismatch.input, mismatch.element.clone(), mismatch.output.clone()), (0, vec![0, 1], vec![0, 1]));
+  assert!((mismatch.analytical - -1.2).abs() < 1e-6);
+  assert!((mismatch.numerical - -2.4).abs() < 1e-4);
+}
+
+#[test]
+fn f64_gradients_keep_their_precision() {
+  // 1/3 rounded through f32 would be off by ~1e-8
+  let x = Tensor::from_vec(vec![0.5f64, -1.2, 2.1], vec![3], Device::Cpu, Some(true));
+  let three = Tensor::from_vec(vec![3f64], vec![1], Device::Cpu, None);
+  let y = (&x.narrow(0, 0, 2) / &three).sum();
+  let grad = grad(&[&y], &[&x], None, false, false)[0].clone().unwrap();
+  assert_eq!(grad.tensor().to_vec::<f64>(), vec![1. / 3., 1. / 3., 0.]);
+}
+
+
+// Arithmetic
+
+#[test]
+fn add_sub_mul_div() {
+  let other = tensor(&[1.3, 0.8, -1.9, 2.2, -0.6, 1.1], &[2, 3]);
+  check(|x| &x[0] + &x[1], &[&input(), &other]);
+  check(|x| &x[0] - &x[1], &[&input(), &other]);
+  check(|x| &x[0] * &x[1], &[&input(), &other]);
+  check(|x| &x[0] / &x[1], &[&input(), &other]);
+}
+
+#[test]
+fn broadcasting_arithmetic() {
+  let row = tensor(&[1.3, 0.8, -1.9], &[3]);
+  let column = tensor(&[0.9, -1.7], &[2, 1]);
+  check(|x| &x[0] + &x[1], &[&input(), &row]);
+  check(|x| &x[0] * &x[1], &[&input(), &column]);
+  check(|x| &x[1] - &x[0], &[&input(), &row]);
+  check(|x| &x[0] / &x[1], &[&input(), &column]);
+}
+
+#[test]
+fn scalar_arithmetic() {
+  check(|x| x[0].add_f32(1.5), &[&input()]);
+  check(|x| x[0].sub_f32(1.5), &[&input()]);
+  check(|x| x[0].mul_f32(-2.5), &[&input()]);
+  check(|x| x[0].div_f32(4.0), &[&input()]);
+  check(|x| x[0].pow_f32(2.5), &[&positive()]);
+  check(|x| x[0].pow_f32(3.0), &[&input()]);
+}
+
+#[test]
+fn abs() {
+  check(|x| x[0].abs(), &[&input()]);
+}
+
+
+// Activations
+
+#[test]
+fn activations() {
+  check(|x| x[0].binary_step(), &[&input()]);
+  check(|x| x[0].sigmoid(), &[&input()]);
+  check(|x| x[0].tanh(), &[&input()]);
+  check(|x| x[0].relu(), &[&input()]);
+  check(|x| x[0].leaky_relu(), &[&input()]);
+  check(|x| x[0].parametric_relu(0.25), &[&input()]);
+  check(|x| x[0].elu(0.8), &[&input()]);
+  check(|x| x[0].swish(), &[&input()]);
+}
+
+#[test]
+fn softmax() {
+  check(|x| x[0].softmax(0), &[&input()]);
+  check(|x| x[0].softmax(1), &[&input()]);
+  check(|x| x[0].view(vec![3, 2]).softmax(1), &[&input()]);
+}
+
+
+// Reductions
+
+#[test]
+fn sum_mean_product() {
+  check(|x| x[0].sum(), &[&input()]);
+  check(|x| x[0].mean(), &[&input()]);
+  check(|x| x[0].product(), &[&input()]);
+  check(|x| x[0].sum_axis(1), &[&input()]);
+}
+
+#[test]
+fn dim_reductions() {
+  for (dims, keepdim) in [(vec![0], false), (vec![1], true), (vec![], false), (vec![0, 1], true)] {
+    check(|x| x[0].sum_dims(&dims, keepdim), &[&input()]);
+    check(|x| x[0].mean_dims(&dims, keepdim), &[&input()]);
+    check(|x| x[0].prod_dims(&dims, keepdim), &[&input()]);
+    check(|x| x[0].max(&dims, keepdim), &[&input()]);
+    check(|x| x[0].min(&dims, keepdim), &[&input()]);
+    check(|x| x[0].logsumexp(&dims, keepdim), &[&input()]);
+  }
+}
+
+#[test]
+fn product_with_zero() {
+  let x = tensor(&[0.0, -1.2, 2.1, 0.3, -0.7, 1.4], &[2, 3]);
+  check(|x| x[0].prod_dims(&[1], false), &[&x]);
+}
+
+#[test]
+fn statistics() {
+  for unbiased in [false, true] {
+    check(|x| x[0].var(&[1], unbiased, false), &[&input()]);
+    check(|x| x[0].var(&[], unbiased, true), &[&input()]);
+    check(|x| x[0].std(&[0], unbiased, false), &[&input()]);
+    check(|x| x[0].std(&[], unbiased, false), &[&input()]);
+  }
+}
+
+#[test]
+fn norms() {
+  check(|x| x[0].norm(2.0, &[1], false), &[&input()]);
+  check(|x| x[0].norm(1.0, &[], false), &[&input()]);
+  check(|x| x[0].norm(3.0, &[0], true), &[&input()]);
+  check(|x| x[0].norm(f32::INFINITY, &[1], false), &[&input()]);
+}
+
+
+// Matrix multiplication
+
+#[test]
+fn matmul() {
+  let a = input();
+  let b = tensor(&[0.2, -0.4, 1.1, 0.7, -1.3, 0.5], &[3, 2]);
+  check(|x| x[0].matmul(&x[1], false, false), &[&a, &b]);
+  check(|x| x[0].matmul(&x[1], true, true), &[&a, &b]);
+  check(|x| x[0].matmul(&x[1], false, true), &[&a, &a]);
+  check(|x| x[0].matmul(&x[1], true, false), &[&a, &a]);
+}
+
+#[test]
+fn matmul_vectors_and_batches() {
+  let matrix = input();
+  let vector = tensor(&[0.2, -0.4, 1.1], &[3]);
+  check(|x| x[0].matmul(&x[1], false, false), &[&matrix, &vector]);
+  check(|x| x[1].matmul(&x[0], false, true), &[&matrix, &vector]);
+
+  let batch = tensor(&[0.5, -1.2, 2.1, 0.3, -0.7, 1.4, 0.9, -0.1, 0.6, 1.7, -0.8, 0.4], &[2, 2, 3]);
+  let weight = tensor(&[0.2, -0.4, 1.1, 0.7, -1.3, 0.5], &[3, 2]);
+  check(|x| x[0].matmul(&x[1], false, false), &[&batch, &weight]);
+}
+
+
+// Shape and indexing
+
+#[test]
+fn views() {
+  check(|x| x[0].transpose(), &[&input()]);
+  check(|x| x[0].view(vec![3, 2]), &[&input()]);
+  check(|x| x[0].transpose().contiguous(), &[&input()]);
+  check(|x| x[0].broadcast(&[4, 2, 3]), &[&input()]);
+  check(|x| x[0].view(vec![2, 1, 3]).broadcast(&[2, 4, 3]), &[&input()]);
+  check_f32(|x| x[0].to_dtype(DType::F32), &[&input()]);
+}
+
+/// Runs an in-place shape op on a copy of `x`, then multiplies by fixed weights so
+/// a wrong element order shows up in the gradient.
+fn in_place<F: Fn(&mut Tensor)>(x: &Tensor, op: F) -> Tensor {
+  let mut y = x.clone();
+  op(&mut y);
+  let weights: Vec<f32> = (1..=y.shape().iter().product::<usize>()).map(|i| i as f32).collect();
+  &y * &Tensor::from_vec(weights, y.shape().clone(), Device::Cpu, None)
+}
+
+#[test]
+fn in_place_shape_ops() {
+  let batch = tensor(&[0.5, -1.2, 2.1, 0.3, -0.7, 1.4, 0.9, -0.4, 1.1, -2.0, 0.6, 0.2], &[2, 3, 2]);
+  // On a leaf and on the output of an op
+  check(|x| in_place(&x[0], |y| y.permute(&[1, 0])), &[&input()]);
+  check(|x| in_place(&x[0].mul_f32(1.), |y| y.permute(&[1, 0])), &[&input()]);
+  check(|x| in_place(&x[0], |y| y.reshape(vec![3, 2])), &[&input()]);
+  check(|x| in_place(&x[0].mul_f32(1.), |y| y.reshape(vec![3, 2])), &[&input()]);
+  check(|x| in_place(&x[0], |y| y.flatten()), &[&input()]);
+  check(|x| in_place(&x[0].mul_f32(1.), |y| y.flatten()), &[&input()]);
+  check(|x| in_place(&x[0], |y| y.unsqueeze(1)), &[&input()]);
+  check(|x| in_place(&x[0].mul_f32(1.), |y| y.unsqueeze(1)), &[&input()]);
+  check(|x| in_place(&x[0], |y| y.permute(&[2, 0, 1])), &[&batch]);
+  check(|x| in_place(&x[0], |y| { y.permute(&[1, 0, 2]); y.flatten() }), &[&batch]);
+  check(|x| in_place(&x[0].view(vec![2, 1, 3]), |y| y.squeeze()), &[&input()]);
+}
+
+#[test]
+fn slicing() {
+  check(|x| x[0].slice(&SliceArg::narrow(1, 1, 2)), &[&input()]);
+  check(|x| x[0].narrow(1, 1, 2), &[&input()]);
+  check(|x| x[0].select(0, 1), &[&input()]);
+  let src = tensor(&[0.9, -0.3], &[2, 1]);
+  check(|x| x[0].slice_scatter(&SliceArg::narrow(1, 1, 1), &x[1]), &[&input(), &src]);
+}
+
+#[test]
+fn index_select_and_add() {
+  check(|x| x[0].index_select(1, &[2, 0, 2]), &[&input()]);
+  let source = tensor(&[0.9, -0.3, 1.5, 0.2], &[2, 2]);
+  check(|x| x[0].index_add(1, &[2, 2], &x[1]), &[&input(), &source]);
+}
+
+#[test]
+fn gather_and_scatter() {
+  let index = Tensor::from_vec(vec![2i64, 0, 1, 1], vec![2, 2], Device::Cpu, None);
+  check(|x| x[0].gather(1, &index), &[&input()]);
+
+  let src = tensor(&[0.9, -0.3, 1.5, 0.2, 0.6, -1.1], &[2, 3]);
+  check(|x| x[0].scatter_add(1, &index, &x[1]), &[&input(), &src]);
+  // With repeated indices only one write survives, so scatter is checked on unique ones
+  let unique = Tensor::from_vec(vec![2i64, 0, 1, 2], vec![2, 2], Device::Cpu, None);
+  check(|x| x[0].scatter(1, &unique, &x[1]), &[&input(), &src]);
+}
+
+#[test]
+fn masked_select_and_scatter() {
+  let mask = tensor(&[1.0, 0.0, 1.0, 0.0, 0.0, 1.0], &[2, 3]);
+  check(|x| x[0].masked_select(&mask), &[&input()]);
+  let source = tensor(&[0.9, -0.3, 1.5, 0.2], &[4]);
+  check(|x| x[0].masked_scatter(&mask, &x[1]), &[&input(), &source]);
+}
+
+#[test]
+fn take() {
+  let indices = Tensor::from_vec(vec![2i64, 0, 1, 2], vec![2, 2], Device::Cpu, None);
+  check(|x| x[0].take(1, &indices), &[&input()]);
+}
+
+#[test]
+fn cat_and_stack() {
+  let other = tensor(&[1.3, 0.8, -1.9], &[1, 3]);
+  check(|x| Tensor::cat(&[&x[0], &x[1]], 0), &[&input(), &other]);
+  check(|x| Tensor::cat(&[&x[0], &x[0]], 1), &[&input()]);
+  check(|x| Tensor::stack(&[&x[0], &x[1]], 1), &[&input(), &positive()]);
+}
+
+#[test]
+fn split_chunk_unbind() {
+  check(|x| x[0].split(2, 1)[1].clone(), &[&input()]);
+  check(|x| x[0].split_with_sizes(&[1, 2], 1)[0].clone(), &[&input()]);
+  check(|x| x[0].chunk(2, 0)[1].clone(), &[&input()]);
+  check(|x| &x[0].unbind(0)[0] * &x[0].unbind(0)[1], &[&input()]);
+}
+
+
+// Losses
+
+#[test]
+fn losses() {
+  let target = tensor(&[0.2, -0.9, 1.7, 0.1, -0.2, 1.1], &[2, 3]);
+  check(|x| MSELoss::new("mean").loss(&x[0], &target), &[&input()]);
+  check(|x| MAELoss::new("sum").loss(&x[0], &target), &[&input()]);
+
+  let classes = Tensor::from_vec(vec![2i64, 0], vec![2], Device::Cpu, None);
+  check(|x| CrossEntropyLoss::new("mean", None, None, 0.0).loss(&x[0], &classes), &[&input()]);
+  check(|x| CrossEntropyLoss::new("sum", None, None, 0.1).loss(&x[0], &classes), &[&input()]);
+  let weight = tensor(&[0.5, 2.0, 1.0], &[3]);
+  check(|x| CrossEntropyLoss::new("mean", Some(weight.clone()), None, 0.0).loss(&x[0], &classes), &[&input()]);
+  let probabilities = tensor(&[0.2, 0.3, 0.5, 0.6, 0.1, 0.3], &[2, 3]);
+  check(|x| CrossEntropyLoss::new("mean", None, None, 0.0).loss(&x[0], &probabilities), &[&input()]);
+}
+
+
+// Higher order
+
+/// Checks the gradient of `f` computed with `create_graph`, i.e. the backward
+/// functions' own derivatives.
+fn check_second_order<F: Fn(&Tensor) -> Tensor>(f: F, input: &Tensor) {
+  check(|x| {
+    let y = f(&x[0]);
+    let weights = Tensor::ones_like(&y, None);
+    grad(&[&y], &[&x[0]], Some(&[&weights]), false, true)[0].clone().unwrap()
+  }, &[input]);
+}
+
+#[test]
+fn second_order() {
+  check_second_order(|x| &(x * x) * x, &input());
+  check_second_order(|x| x.sigmoid(), &input());
+  check_second_order(|x| x.tanh(), &input());
+  check_second_order(|x| x.swish(), &input());
+  check_second_order(|x| x.softmax(1), &input());
+  check_second_order(|x| &x.logsumexp(&[1], false) * &x.var(&[1], true, false), &input());
+  check_second_order(|x| x.norm(3.0, &[0], false), &input());
+  check_second_order(|x| x.prod_dims(&[1], false), &input());
+  check_second_order(|x| x.matmul(x, false, true), &input());
+  check_second_order(|x| (&x.view(vec![3, 2]).transpose() * x).sum(), &input());
+  check_second_order(|x| in_place(x, |y| y.permute(&[1, 0])).pow_f32(2.), &input());
+}
+
+#[test]
+fn second_order_products_with_zeros() {
+  check_second_order(|x| x.product(), &tensor(&[0.0, 2.0, 3.0], &[3]));
+  check_second_order(|x| x.prod_dims(&[1], false), &tensor(&[0.0, 2.0, 3.0, 2.0, 3.0, 4.0], &[2, 3]));
+  check_second_order(|x| x.prod_dims(&[1], false), &tensor(&[0.0, 0.0, 3.0, 2.0, 0.0, 4.0], &[2, 3]));
+  check_second_order(|x| x.prod_dims(&[0], false), &tensor(&[0.0, 0.0, 3.0, 0.0, 0.0, 4.0], &[3, 2]));
+
+  let hessian = functional::hessian(|x| x.product(), &tensor(&[0.0, 2.0, 3.0], &[3]));
+  assert_eq!(hessian.tensor().to_vec::<f32>(), vec![0., 3., 2., 3., 0., 0., 2., 0., 0.]);
+}
+
+#[test]
+fn second_order_logsumexp_over_separate_dims() {
+  let x = tensor(&[0.5, -1.2, 2.1, 0.3, -0.7, 1.4, 0.9, -0.4, 1.1, -2.0, 0.6, 0.2], &[2, 3, 2]);
+  check_second_order(|x| x.logsumexp(&[0, 2], false), &x);
+  check_second_order(|x| x.logsumexp(&[0, 2], true), &x);
+}
+
+#[test]
+fn backward_with_create_graph() {
+  let x = Tensor::from_vec(vec![1.5f64, -2.0], vec![2], Device::Cpu, Some(true));
+  (&(&x * &x) * &x).sum().backward_with(None, false, true);
+
+  // .grad holds 3x^2, and its recorded copy differentiates to 6x
+  let first = x.grad_tensor().unwrap();
+  assert_eq!(first.tensor().to_vec::<f64>(), vec![6.75, 12.0]);
+  assert_eq!(x.grad().unwrap().borrow().clone().unwrap().to_vec::<f64>(), vec![6.75, 12.0]);
+  assert!(first.grad_fn().is_some());
+
+  // A second pass accumulates into the recorded gradient
+  (&x * &x).sum().backward_with(None, false, true);
+  let accumulated = x.grad_tensor().unwrap();
+  assert_eq!(accumulated.tensor().to_vec::<f64>(), vec![9.75, 8.0]);
+
+  x.zero_grad(true);
+  accumulated.sum().backward();
+  assert_eq!(x.grad().unwrap().borrow().clone().unwrap().to_vec::<f64>(), vec![11.0, -10.0]);
+}
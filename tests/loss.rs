@@ -0,0 +1,81 @@
+use ferrite::prelude::*;
+use ferrite::loss::*;
+
+// Expected values are computed by hand from log_softmax of the logits below.
+
+fn logits() -> Tensor {
+  Tensor::from_vec(vec![0.5f64, -1.2, 2.1, 0.3, -0.7, 1.4], vec![2, 3], Device::Cpu, None)
+}
+
+fn classes(labels: &[i64]) -> Tensor {
+  Tensor::from_vec(labels.to_vec(), vec![labels.len()], Device::Cpu, None)
+}
+
+fn assert_loss(loss: Tensor, expected: f64) {
+  let value = loss.tensor().to_vec::<f64>()[0];
+  assert!((value - expected).abs() < 1e-9, "loss {} != expected {}", value, expected);
+}
+
+
+#[test]
+fn cross_entropy_class_indices() {
+  let target = classes(&[2, 0]);
+  assert_loss(CrossEntropyLoss::new("mean", None, None, 0.0).loss(&logits(), &target), 0.8446788698927971);
+  assert_loss(CrossEntropyLoss::new("sum", None, None, 0.0).loss(&logits(), &target), 1.6893577397855941);
+}
+
+#[test]
+fn cross_entropy_single_sample() {
+  let logits = Tensor::from_vec(vec![0.5f64, -1.2, 2.1], vec![3], Device::Cpu, None);
+  assert_loss(CrossEntropyLoss::new("mean", None, None, 0.0).loss(&logits, &classes(&[2])), 0.21412677036757888);
+}
+
+#[test]
+fn cross_entropy_class_weights() {
+  // Weighted mean: sum w_y * nll / sum w_y
+  let weight = Tensor::from_vec(vec![0.5f32, 2.0, 1.0], vec![3], Device::Cpu, None);
+  let loss = CrossEntropyLoss::new("mean", Some(weight), None, 0.0).loss(&logits(), &classes(&[2, 0]));
+  assert_loss(loss, 0.6344948367177243);
+}
+
+#[test]
+fn cross_entropy_ignore_index() {
+  // The ignored sample counts neither in the sum nor in the mean's denominator
+  let loss = CrossEntropyLoss::new("mean", None, Some(-100), 0.0).loss(&logits(), &classes(&[2, -100]));
+  assert_loss(loss, 0.21412677036757888);
+  let loss = CrossEntropyLoss::new("sum", None, Some(0), 0.0).loss(&logits(), &classes(&[2, 0]));
+  assert_loss(loss, 0.21412677036757888);
+}
+
+#[test]
+fn cross_entropy_label_smoothing() {
+  // (1 - eps) * nll + eps / C * sum_c -log p_c, averaged over the samples, with
+  // eps = 0.1f32 as stored
+  let loss = CrossEntropyLoss::new("mean", None, None, 0.1).loss(&logits(), &classes(&[2, 0]));
+  assert_loss(loss, 0.9246788710848899);
+}
+
+#[test]
+fn cross_entropy_probabilities() {
+  let target = Tensor::from_vec(vec![0.2f64, 0.3, 0.5, 0.6, 0.1, 0.3], vec![2, 3], Device::Cpu, None);
+  assert_loss(CrossEntropyLoss::new("mean", None, None, 0.0).loss(&logits(), &target), 1.384678869892797);
+}
+
+#[test]
+#[should_panic(expected = "not a class index")]
+fn cross_entropy_rejects_negative_labels() {
+  CrossEntropyLoss::new("mean", None, None, 0.0).loss(&logits(), &classes(&[-1, 0]));
+}
+
+#[test]
+#[should_panic(expected = "not a class index")]
+fn cross_entropy_rejects_fractional_labels() {
+  let target = Tensor::from_vec(vec![1.7f32, 0.0], vec![2], Device::Cpu, None);
+  CrossEntropyLoss::new("mean", None, None, 0.0).loss(&logits(), &target);
+}
+
+#[test]
+#[should_panic(expected = "not a class index")]
+fn cross_entropy_rejects_out_of_range_labels() {
+  CrossEntropyLoss::new("mean", None, None, 0.0).loss(&logits(), &classes(&[3, 0]));
+}
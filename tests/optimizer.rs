@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use ferrite::prelude::*;
+use ferrite::optimizer::*;
+
+// Every test starts from an f64 weight w = 1. The SGD steps use a constant
+// gradient of 1 and are worked out by hand; the adaptive optimizers use
+// hyperparameters that are exact in f32 and are checked against a float64
+// reference implementation of each update rule.
+
+#[allow(clippy::arc_with_non_send_sync)]
+fn param() -> Arc<RwLock<Tensor>> {
+  Arc::new(RwLock::new(Tensor::from_vec(vec![1.0f64], vec![1], Device::Cpu, Some(true))))
+}
+
+fn params(param: &Arc<RwLock<Tensor>>) -> HashMap<String, Arc<RwLock<Tensor>>> {
+  HashMap::from([("w".to_string(), param.clone())])
+}
+
+/// Runs one optimizer step per gradient and checks the weight after each one.
+fn assert_steps<O: OptimizerTrait>(optimizer: &mut O, param: &Arc<RwLock<Tensor>>, grads: &[f64], expected: &[f64], tolerance: f64) {
+  for (step, (&grad, &expected)) in grads.iter().zip(expected).enumerate() {
+    let slot = param.read().unwrap().grad().unwrap();
+    *slot.borrow_mut() = Some(Storage::from_vec(vec![grad], vec![1], Some(Device::Cpu)));
+    optimizer.step();
+
+    let weight = param.read().unwrap();
+    assert_eq!(weight.dtype(), DType::F64);
+    let value = weight.tensor().to_vec::<f64>()[0];
+    assert!((value - expected).abs() < tolerance, "step {}: w {} != expected {}", step, value, expected);
+  }
+}
+
+/// SGD steps with a gradient of 1; its f32 hyperparameters limit the match to ~1e-7.
+fn assert_sgd(optimizer: &mut SGD, param: &Arc<RwLock<Tensor>>, expected: &[f64]) {
+  assert_steps(optimizer, param, &[1., 1.], expected, 1e-6);
+}
+
+const GRADS: [f64; 3] = [1., -0.5, 2.];
+
+
+#[test]
+fn sgd_plain() {
+  let w = param();
+  assert_sgd(&mut SGD::new(params(&w), 0.1, 0., 0., 0., false), &w, &[0.9, 0.8]);
+}
+
+#[test]
+fn sgd_momentum() {
+  // buf = 1, then 0.9 * 1 + 1 = 1.9
+  let w = param();
+  assert_sgd(&mut SGD::new(params(&w), 0.1, 0.9, 0., 0., false), &w, &[0.9, 0.71]);
+}
+
+#[test]
+fn sgd_dampening() {
+  // The first step seeds the buffer with the raw gradient, then
+  // buf = 0.9 * 1 + (1 - 0.5) * 1 = 1.4
+  let w = param();
+  assert_sgd(&mut SGD::new(params(&w), 0.1, 0.9, 0.5, 0., false), &w, &[0.9, 0.76]);
+}
+
+#[test]
+fn sgd_nesterov() {
+  // Step with g + momentum * buf: 1 + 0.9 * 1 = 1.9, then 1 + 0.9 * 1.9 = 2.71
+  let w = param();
+  assert_sgd(&mut SGD::new(params(&w), 0.1, 0.9, 0., 0., true), &w, &[0.81, 0.539]);
+}
+
+#[test]
+fn sgd_weight_decay() {
+  // g + 0.1 * w: 1.1, then 1 + 0.1 * 0.89 = 1.089
+  let w = param();
+  assert_sgd(&mut SGD::new(params(&w), 0.1, 0., 0., 0.1, false), &w, &[0.89, 0.7811]);
+}
+
+#[test]
+fn sgd_weight_decay_with_momentum() {
+  // The decayed gradient goes into the buffer: 1.1, then 0.9 * 1.1 + 1.089 = 2.079
+  let w = param();
+  assert_sgd(&mut SGD::new(params(&w), 0.1, 0.9, 0., 0.1, false), &w, &[0.89, 0.6821]);
+}
+
+#[test]
+#[should_panic(expected = "Nesterov momentum requires a momentum and zero dampening")]
+fn sgd_nesterov_rejects_dampening() {
+  SGD::new(params(&param()), 0.1, 0.9, 0.5, 0., true);
+}
+
+#[test]
+fn adam() {
+  let w = param();
+  let mut adam = Adam::new(params(&w), 0.25, (0.5, 0.75), 1e-8, 0., false);
+  assert_steps(&mut adam, &w, &GRADS, &[0.7500000025, 0.7500000025, 0.5506455050007486], 1e-12);
+}
+
+#[test]
+fn adam_l2_weight_decay() {
+  let w = param();
+  let mut adam = Adam::new(params(&w), 0.25, (0.5, 0.75), 1e-8, 0.5, false);
+  assert_steps(&mut adam, &w, &GRADS, &[0.7500000016666666, 0.644409576530585, 0.42278772408855014], 1e-12);
+}
+
+#[test]
+fn adamw_decoupled_weight_decay() {
+  let w = param();
+  let mut adamw = AdamW::new(params(&w), 0.25, (0.5, 0.75), 1e-8, 0.5, false);
+  assert_steps(&mut adamw, &w, &GRADS, &[0.6250000025, 0.5468750021875, 0.279161129414811], 1e-12);
+}
+
+#[test]
+fn adam_amsgrad() {
+  // The second moment drops after the first step, so amsgrad keeps the first
+  // one as the denominator; plain Adam would give 0.56655, 0.40091
+  let w = param();
+  let mut adam = Adam::new(params(&w), 0.25, (0.5, 0.75), 1e-8, 0., true);
+  assert_steps(&mut adam, &w, &[2., 0.5, 0.5], &[0.75000000125, 0.5846405404741173, 0.4488645860998116], 1e-12);
+}
+
+#[test]
+fn rmsprop() {
+  let w = param();
+  let mut rmsprop = RMSprop::new(params(&w), 0.25, 0.75, 1e-8, 0., 0., false);
+  assert_steps(&mut rmsprop, &w, &GRADS, &[0.5000000099999997, 0.7500000049999999, 0.29116854146940263], 1e-12);
+}
+
+#[test]
+fn rmsprop_weight_decay() {
+  let w = param();
+  let mut rmsprop = RMSprop::new(params(&w), 0.25, 0.75, 1e-8, 0.5, 0., false);
+  assert_steps(&mut rmsprop, &w, &GRADS, &[0.5000000066666666, 0.5944911222755161, 0.14704568865710765], 1e-12);
+}
+
+#[test]
+fn rmsprop_centered_with_momentum() {
+  let w = param();
+  let mut rmsprop = RMSprop::new(params(&w), 0.25, 0.75, 1e-8, 0., 0.5, true);
+  assert_steps(&mut rmsprop, &w, &GRADS, &[0.42264974414370715, 0.3859509264756806, -0.16286618348433618], 1e-12);
+}
+
+#[test]
+fn adagrad() {
+  let w = param();
+  let mut adagrad = Adagrad::new(params(&w), 0.25, 0., 0., 0., 1e-10);
+  assert_steps(&mut adagrad, &w, &GRADS, &[0.750000000025, 0.8618033988899895, 0.6435855086635209], 1e-12);
+}
+
+#[test]
+fn adagrad_lr_decay_and_initial_accumulator() {
+  let w = param();
+  let mut adagrad = Adagrad::new(params(&w), 0.25, 0.5, 0.5, 0.25, 1e-10);
+  assert_steps(&mut adagrad, &w, &GRADS, &[0.7628291755023715, 0.7752941672458928, 0.671163548438623], 1e-12);
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use ferrite::prelude::*;
+use ferrite::optimizer::*;
+use ferrite::scheduler::*;
+
+// Expected learning rates follow the reference closed forms, with a base lr of 0.1.
+
+fn assert_schedule<S: SchedulerTrait>(schedule: &S, expected: &[f32]) {
+  for (epoch, &lr) in expected.iter().enumerate() {
+    let actual = schedule.lr_at(0.1, epoch);
+    assert!((actual - lr).abs() <= 1e-6 * lr.max(1e-2), "epoch {}: lr {} != expected {}", epoch, actual, lr);
+  }
+}
+
+#[allow(clippy::arc_with_non_send_sync)]
+fn optimizer(lr: f32) -> SGD {
+  let param = Tensor::from_vec(vec![1.0f32], vec![1], Device::Cpu, Some(true));
+  let params = HashMap::from([("w".to_string(), Arc::new(RwLock::new(param)))]);
+  SGD::new(params, lr, 0., 0., 0., false)
+}
+
+
+#[test]
+fn step_lr() {
+  assert_schedule(&StepLR::new(3, 0.5), &[0.1, 0.1, 0.1, 0.05, 0.05, 0.05, 0.025, 0.025]);
+}
+
+#[test]
+fn multi_step_lr() {
+  assert_schedule(&MultiStepLR::new(vec![2, 5], 0.1), &[0.1, 0.1, 0.01, 0.01, 0.01, 0.001, 0.001]);
+}
+
+#[test]
+fn exponential_lr() {
+  assert_schedule(&ExponentialLR::new(0.9), &[0.1, 0.09, 0.081, 0.0729, 0.06561]);
+}
+
+#[test]
+fn cosine_annealing_lr() {
+  let schedule = CosineAnnealingLR::new(4, 0.01);
+  assert_schedule(&schedule, &[0.1, 0.08681981, 0.055, 0.02318019, 0.01]);
+  // Past T_max the cosine keeps going, back up to the base lr at 2 * T_max
+  assert!((schedule.lr_at(0.1, 6) - 0.055).abs() < 1e-7);
+  assert!((schedule.lr_at(0.1, 8) - 0.1).abs() < 1e-7);
+}
+
+#[test]
+fn cosine_annealing_warm_restarts() {
+  // Cycles of 2, 4 and 8 epochs start at epochs 0, 2, 6 and 14
+  assert_schedule(&CosineAnnealingWarmRestarts::new(2, 2, 0.), &[
+    0.1, 0.05,
+    0.1, 0.08535534, 0.05, 0.01464466,
+    0.1, 0.09619398, 0.08535534, 0.06913417, 0.05, 0.03086583, 0.01464466, 0.00380602,
+    0.1,
+  ]);
+  assert_schedule(&CosineAnnealingWarmRestarts::new(3, 1, 0.), &[0.1, 0.075, 0.025, 0.1, 0.075, 0.025, 0.1]);
+}
+
+#[test]
+fn linear_warmup() {
+  assert_schedule(&LinearWarmup::new(4, 0.25, None), &[0.025, 0.04375, 0.0625, 0.08125, 0.1, 0.1]);
+  // The wrapped schedule starts from its own epoch 0 once the warmup ends
+  let schedule = LinearWarmup::new(2, 0.5, Some(Box::new(StepLR::new(2, 0.5))));
+  assert_schedule(&schedule, &[0.05, 0.075, 0.1, 0.1, 0.05, 0.05, 0.025]);
+}
+
+#[test]
+fn one_cycle_lr() {
+  // Warmup ends at step pct_start * total - 1 = 2, the minimum is 0.1 / 25 / 1e4
+  assert_schedule(&OneCycleLR::new(10, 0.3, 25., 1e4, "cos"), &[
+    0.004, 0.052, 0.1, 0.09504846, 0.08117457, 0.0611262, 0.0388742, 0.01882583, 0.00495194, 4e-7, 4e-7,
+  ]);
+  assert_schedule(&OneCycleLR::new(10, 0.3, 25., 1e4, "linear"), &[
+    0.004, 0.052, 0.1, 0.08571434, 0.07142869, 0.05714303, 0.04285737, 0.02857171, 0.01428606, 4e-7, 4e-7,
+  ]);
+}
+
+#[test]
+fn lr_scheduler_applies_the_schedule() {
+  let mut optimizer = optimizer(0.1);
+  let mut scheduler = LRScheduler::new(&mut optimizer, StepLR::new(2, 0.5));
+  let mut lrs = vec![optimizer.lr()];
+  for _ in 0..4 {
+    scheduler.step(&mut optimizer);
+    lrs.push(optimizer.lr());
+  }
+  assert_eq!(lrs, vec![0.1, 0.1, 0.05, 0.05, 0.025]);
+  assert_eq!(scheduler.last_epoch(), 4);
+}
+
+#[test]
+fn reduce_lr_on_plateau_patience_and_cooldown() {
+  // patience 2: the lr drops on the third bad epoch in a row; cooldown 1: the
+  // epoch after a drop never counts as bad
+  let mut optimizer = optimizer(0.1);
+  let mut plateau = ReduceLROnPlateau::new("min", 0.5, 2, 0., 1, 0.01);
+  let metrics = [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5];
+  let expected = [
+    0.1, 0.1, 0.1, 0.05,
+    0.05, 0.05, 0.05, 0.025,
+    0.025, 0.025, 0.025, 0.0125,
+    0.0125, 0.0125, 0.0125, 0.01,
+    0.01, 0.01, 0.01, 0.01,
+  ];
+  for (epoch, (&metric, &lr)) in metrics.iter().zip(&expected).enumerate() {
+    plateau.step(&mut optimizer, metric);
+    assert!((optimizer.lr() - lr).abs() < 1e-9, "epoch {}: lr {} != expected {}", epoch, optimizer.lr(), lr);
+  }
+}
+
+#[test]
+fn reduce_lr_on_plateau_threshold() {
+  // In max mode an improvement must exceed best * (1 + threshold)
+  let mut optimizer = optimizer(0.1);
+  let mut plateau = ReduceLROnPlateau::new("max", 0.1, 0, 0.1, 0, 0.);
+  let mut lrs = vec![];
+  for metric in [1.0, 1.05, 1.2, 1.2] {
+    plateau.step(&mut optimizer, metric);
+    lrs.push(optimizer.lr());
+  }
+  let expected = [0.1, 0.01, 0.01, 0.001];
+  for (lr, expected) in lrs.iter().zip(expected) {
+    assert!((lr - expected).abs() < 1e-9, "{:?} != {:?}", lrs, expected);
+  }
+}